@@ -0,0 +1,47 @@
+//! Instantiates every method of the controller checked by the `no-panic` feature,
+//! which then fails to link if any of them can panic.
+//!
+//! The check needs optimizations and the controller to be in a single codegen unit,
+//! build it with:
+//!
+//! ```text
+//! CARGO_PROFILE_RELEASE_CODEGEN_UNITS=1 cargo build --release --features no-panic --example no_panic
+//! ```
+
+use core::hint::black_box;
+
+use control::pid::{Saturation, PID};
+use num_traits::float::FloatCore;
+
+fn exercise<T: FloatCore>(values: [T; 6]) -> T {
+    let [kp, ki, kd, tau, sampling_time, setpoint] = values;
+    let mut pid = match PID::try_new(kp, ki, kd, tau, sampling_time, setpoint) {
+        Ok(pid) => pid,
+        Err(_) => PID::new(kp, ki, kd, tau, sampling_time, setpoint),
+    };
+    pid.set_gains(kp, ki, kd)
+        .bound_integral(ki..=kd)
+        .bound_output(kp..)
+        .invert_output(kp > ki)
+        .soften_output(tau)
+        .limit_rate(kd, tau)
+        .set_bias(setpoint)
+        .inhibit_integral(Saturation::Upper)
+        .gate_integral(3)
+        .fade_integral(tau);
+    let validated = pid.validate().count();
+    let mut output = pid.step(kp);
+    output = output + pid.update(ki);
+    output = output + pid.step_with_feedforward(ki, kd);
+    pid.set_manual(tau).track(output).report_actual_output(setpoint);
+    output = output + pid.step_with_rate(kd, tau);
+    pid.set_automatic();
+    pid.reset();
+    output + T::from(validated).unwrap_or_else(T::zero)
+}
+
+fn main() {
+    let single = exercise(black_box([1.0_f32, 0.5, 0.1, 0.01, 0.001, 2.0]));
+    let double = exercise(black_box([1.0_f64, 0.5, 0.1, 0.01, 0.001, 2.0]));
+    black_box((single, double));
+}
@@ -0,0 +1,176 @@
+//! Fixed-capacity storage for the many loops of a single device, without allocation.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// A configuration change applied to a controller of a [`ControllerBank`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Update<T> {
+    /// Changes the setpoint.
+    Setpoint(T),
+    /// Changes the proportional, integral and derivative gains, see [`PID::set_gains`].
+    Gains(T, T, T),
+    /// Restricts the output to the `[min, max]` interval.
+    OutputBounds(T, T),
+    /// Switches to manual mode with the given output.
+    Manual(T),
+    /// Switches back to automatic mode.
+    Automatic,
+    /// Clears the controller state.
+    Reset,
+}
+
+/// Implementation of a bank of up to `N` controllers addressed by small IDs, for
+/// devices running dozens of loops.
+///
+/// The ID of a controller is the index of its slot, it stays the same until the
+/// controller is removed and is then handed out again.
+#[derive(Clone, Copy, Debug)]
+pub struct ControllerBank<T, const N: usize> {
+    /// Controller slots, `None` when free.
+    slots: [Option<PID<T>>; N],
+}
+
+impl<T: FloatCore, const N: usize> ControllerBank<T, N> {
+    /// Creates a new empty `ControllerBank`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is larger than 256, the number of IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::bank::{ControllerBank, Update};
+    /// use control::pid::PID;
+    ///
+    /// let mut bank = ControllerBank::<f32, 16>::new();
+    /// let zone = bank.insert(PID::new(1.0, 0.0, 0.0, 0.0, 1.0, 20.0)).unwrap();
+    /// let fan = bank.insert(PID::new(2.0, 0.0, 0.0, 0.0, 1.0, 5.0)).unwrap();
+    ///
+    /// bank.apply(&[(zone, Update::Setpoint(22.0)), (fan, Update::Gains(4.0, 0.0, 0.0))]);
+    ///
+    /// let mut outputs = [0.0; 16];
+    /// bank.step_all(|_| 1.0, |id, output| outputs[usize::from(id)] = output);
+    /// assert_eq!(outputs[..2], [21.0, 16.0]);
+    /// ```
+    pub fn new() -> Self {
+        assert!(N <= 256);
+        Self { slots: [None; N] }
+    }
+
+    /// Returns the number of controllers in the bank.
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Returns whether the bank holds no controller.
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(|slot| slot.is_none())
+    }
+
+    /// Adds a controller to the first free slot and returns its ID, or gives the
+    /// controller back if the bank is full.
+    pub fn insert(&mut self, controller: PID<T>) -> Result<u8, PID<T>> {
+        match self.slots.iter().position(|slot| slot.is_none()) {
+            Some(index) => {
+                self.slots[index] = Some(controller);
+                Ok(index as u8)
+            }
+            None => Err(controller),
+        }
+    }
+
+    /// Removes the controller with the given ID and returns it.
+    pub fn remove(&mut self, id: u8) -> Option<PID<T>> {
+        self.slots.get_mut(usize::from(id))?.take()
+    }
+
+    /// Returns the controller with the given ID.
+    pub fn get(&self, id: u8) -> Option<&PID<T>> {
+        self.slots.get(usize::from(id))?.as_ref()
+    }
+
+    /// Returns the controller with the given ID, mutably.
+    pub fn get_mut(&mut self, id: u8) -> Option<&mut PID<T>> {
+        self.slots.get_mut(usize::from(id))?.as_mut()
+    }
+
+    /// Returns an iterator over the controllers and their IDs, in ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &PID<T>)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((index as u8, slot.as_ref()?)))
+    }
+
+    /// Returns an iterator over the controllers and their IDs, mutably, in ID order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (u8, &mut PID<T>)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((index as u8, slot.as_mut()?)))
+    }
+
+    /// Performs a single step of the controller with the given ID and returns its
+    /// output, or `None` if there is no such controller.
+    pub fn step(&mut self, id: u8, measurement: T) -> Option<T> {
+        self.get_mut(id).map(|controller| controller.step(measurement))
+    }
+
+    /// Performs a single step of every controller, in ID order.
+    ///
+    /// `sensor` returns the measurement of the loop with the given ID and `actuator`
+    /// applies its output.
+    pub fn step_all<S, A>(&mut self, mut sensor: S, mut actuator: A)
+    where
+        S: FnMut(u8) -> T,
+        A: FnMut(u8, T),
+    {
+        for (id, controller) in self.iter_mut() {
+            let output = controller.step(sensor(id));
+            actuator(id, output);
+        }
+    }
+
+    /// Applies a batch of configuration changes, in order, and returns how many of them
+    /// addressed a controller of the bank. Changes to unknown IDs are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an [`Update::OutputBounds`] has its minimum larger than its maximum.
+    pub fn apply(&mut self, updates: &[(u8, Update<T>)]) -> usize {
+        let mut applied = 0;
+        for &(id, update) in updates {
+            let controller = match self.get_mut(id) {
+                Some(controller) => controller,
+                None => continue,
+            };
+            match update {
+                Update::Setpoint(setpoint) => controller.setpoint = setpoint,
+                Update::Gains(kp, ki, kd) => {
+                    controller.set_gains(kp, ki, kd);
+                }
+                Update::OutputBounds(min, max) => {
+                    controller.bound_output(min..=max);
+                }
+                Update::Manual(output) => {
+                    controller.set_manual(output);
+                }
+                Update::Automatic => {
+                    controller.set_automatic();
+                }
+                Update::Reset => controller.reset(),
+            }
+            applied += 1;
+        }
+        applied
+    }
+}
+
+impl<T: FloatCore, const N: usize> Default for ControllerBank<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
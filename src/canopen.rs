@@ -0,0 +1,280 @@
+//! A CANopen style object dictionary record exposing the parameters and live values
+//! of a controller, so devices on a CAN network can be tuned and monitored through
+//! SDO transfers.
+//!
+//! # Record
+//!
+//! | Subindex | Entry        | Type       | Access     |
+//! |----------|--------------|------------|------------|
+//! | 0        | Highest sub  | UNSIGNED8  | read-only  |
+//! | 1        | Setpoint     | REAL32     | read-write |
+//! | 2        | Measurement  | REAL32     | read-only  |
+//! | 3        | Output       | REAL32     | read-write |
+//! | 4        | Mode         | UNSIGNED8  | read-write |
+//! | 5        | Kp           | REAL32     | read-write |
+//! | 6        | Ki           | REAL32     | read-write |
+//! | 7        | Kd           | REAL32     | read-write |
+//! | 8        | Output min   | REAL32     | read-write |
+//! | 9        | Output max   | REAL32     | read-write |
+//! | 10       | Status       | UNSIGNED8  | read-only  |
+//!
+//! Values are little endian. The mode is 0 in automatic and 1 in manual, the output
+//! can only be written in manual mode. Bits 0 and 1 of the status hold the output
+//! saturation, as 0 for none, 1 for the lower bound and 2 for the upper bound, and bit
+//! 2 is set in manual mode. Values that are not finite are rejected, except infinite
+//! output bounds which leave the output unbounded on that side.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, PID};
+
+/// Type of the value of an object dictionary entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataType {
+    /// `UNSIGNED8`, one byte.
+    Unsigned8,
+    /// `REAL32`, an IEEE 754 single precision number on four bytes.
+    Real32,
+}
+
+impl DataType {
+    /// Returns the number of bytes of the value.
+    pub fn size(self) -> usize {
+        match self {
+            DataType::Unsigned8 => 1,
+            DataType::Real32 => 4,
+        }
+    }
+}
+
+/// Access rights of an object dictionary entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// The entry can only be read.
+    ReadOnly,
+    /// The entry can be read and written.
+    ReadWrite,
+}
+
+/// Description of a subindex of the record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Subindex of the entry.
+    pub subindex: u8,
+    /// Name of the entry, for electronic data sheets and host tools.
+    pub name: &'static str,
+    /// Type of the value.
+    pub data_type: DataType,
+    /// Access rights.
+    pub access: Access,
+}
+
+/// Entries of the record, by subindex.
+pub const ENTRIES: [Entry; 11] = [
+    entry(0, "Highest sub-index supported", DataType::Unsigned8, Access::ReadOnly),
+    entry(1, "Setpoint", DataType::Real32, Access::ReadWrite),
+    entry(2, "Measurement", DataType::Real32, Access::ReadOnly),
+    entry(3, "Output", DataType::Real32, Access::ReadWrite),
+    entry(4, "Mode", DataType::Unsigned8, Access::ReadWrite),
+    entry(5, "Kp", DataType::Real32, Access::ReadWrite),
+    entry(6, "Ki", DataType::Real32, Access::ReadWrite),
+    entry(7, "Kd", DataType::Real32, Access::ReadWrite),
+    entry(8, "Output min", DataType::Real32, Access::ReadWrite),
+    entry(9, "Output max", DataType::Real32, Access::ReadWrite),
+    entry(10, "Status", DataType::Unsigned8, Access::ReadOnly),
+];
+
+/// Creates an entry of the record.
+const fn entry(subindex: u8, name: &'static str, data_type: DataType, access: Access) -> Entry {
+    Entry { subindex, name, data_type, access }
+}
+
+/// Reason of a failed SDO transfer, to send back as an abort.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Abort {
+    /// The index is not the one of the record.
+    ObjectDoesNotExist,
+    /// The subindex is not part of the record.
+    SubindexDoesNotExist,
+    /// The entry is read-only.
+    ReadOnly,
+    /// The length of the data does not match the type of the entry.
+    LengthMismatch,
+    /// The value is outside of the range of the entry.
+    ValueRange,
+    /// The value cannot be written in the current state of the controller.
+    DeviceState,
+}
+
+impl Abort {
+    /// Returns the CANopen SDO abort code.
+    pub fn code(self) -> u32 {
+        match self {
+            Abort::ObjectDoesNotExist => 0x0602_0000,
+            Abort::SubindexDoesNotExist => 0x0609_0011,
+            Abort::ReadOnly => 0x0601_0002,
+            Abort::LengthMismatch => 0x0607_0010,
+            Abort::ValueRange => 0x0609_0030,
+            Abort::DeviceState => 0x0800_0022,
+        }
+    }
+}
+
+/// Implementation of the object dictionary record of a [`PID`] controller at a
+/// given index, dispatching SDO reads and writes to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObjectDictionary {
+    /// Index of the record.
+    index: u16,
+}
+
+impl ObjectDictionary {
+    /// Creates a new `ObjectDictionary` record at `index`, usually in the
+    /// manufacturer specific area starting at `0x2000`, one index per loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::canopen::{Abort, ObjectDictionary};
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 20.0);
+    /// let dictionary = ObjectDictionary::new(0x2000);
+    ///
+    /// // Expedited SDO download of a new proportional gain.
+    /// dictionary.write(&mut pid, 0x2000, 5, &2.5_f32.to_le_bytes()).unwrap();
+    /// assert_eq!(pid.gains().0, 2.5);
+    ///
+    /// let mut data = [0; 4];
+    /// let len = dictionary.read(&pid, 0x2000, 1, &mut data).unwrap();
+    /// assert_eq!(f32::from_le_bytes(data), 20.0);
+    /// assert_eq!(len, 4);
+    ///
+    /// let error = dictionary.write(&mut pid, 0x2000, 2, &[0; 4]).unwrap_err();
+    /// assert_eq!(error, Abort::ReadOnly);
+    ///
+    /// let error = dictionary.write(&mut pid, 0x2000, 5, &f32::INFINITY.to_le_bytes()).unwrap_err();
+    /// assert_eq!(error, Abort::ValueRange);
+    /// dictionary.write(&mut pid, 0x2000, 9, &f32::INFINITY.to_le_bytes()).unwrap();
+    /// assert_eq!(pid.output_bounds().1, f32::INFINITY);
+    /// ```
+    pub const fn new(index: u16) -> Self {
+        Self { index }
+    }
+
+    /// Returns the entry at `index` and `subindex`.
+    pub fn entry(&self, index: u16, subindex: u8) -> Result<&'static Entry, Abort> {
+        if index != self.index {
+            return Err(Abort::ObjectDoesNotExist);
+        }
+        ENTRIES.get(usize::from(subindex)).ok_or(Abort::SubindexDoesNotExist)
+    }
+
+    /// Reads the entry at `index` and `subindex` into `data` and returns its length.
+    pub fn read<T: FloatCore>(
+        &self,
+        controller: &PID<T>,
+        index: u16,
+        subindex: u8,
+        data: &mut [u8; 4],
+    ) -> Result<usize, Abort> {
+        let entry = self.entry(index, subindex)?;
+        let (kp, ki, kd) = controller.gains();
+        let (min, max) = controller.output_bounds();
+        let value = match subindex {
+            0 => return Ok(byte(data, (ENTRIES.len() - 1) as u8)),
+            1 => controller.setpoint,
+            2 => controller.record().measurement,
+            3 => controller.output(),
+            4 => return Ok(byte(data, u8::from(controller.is_manual()))),
+            5 => kp,
+            6 => ki,
+            7 => kd,
+            8 => min,
+            9 => max,
+            _ => {
+                let saturation = match controller.saturation() {
+                    Saturation::Unsaturated => 0,
+                    Saturation::Lower => 1,
+                    Saturation::Upper => 2,
+                };
+                return Ok(byte(data, saturation | u8::from(controller.is_manual()) << 2));
+            }
+        };
+        let value = value.to_f32().unwrap_or(f32::NAN);
+        *data = value.to_le_bytes();
+        Ok(entry.data_type.size())
+    }
+
+    /// Writes `data` to the entry at `index` and `subindex`.
+    pub fn write<T: FloatCore>(
+        &self,
+        controller: &mut PID<T>,
+        index: u16,
+        subindex: u8,
+        data: &[u8],
+    ) -> Result<(), Abort> {
+        let entry = self.entry(index, subindex)?;
+        if entry.access == Access::ReadOnly {
+            return Err(Abort::ReadOnly);
+        }
+        if data.len() != entry.data_type.size() {
+            return Err(Abort::LengthMismatch);
+        }
+
+        if entry.data_type == DataType::Unsigned8 {
+            // The mode is the only writable byte.
+            match data[0] {
+                0 => {
+                    controller.set_automatic();
+                }
+                1 if !controller.is_manual() => {
+                    let output = controller.output();
+                    controller.set_manual(output);
+                }
+                1 => {}
+                _ => return Err(Abort::ValueRange),
+            }
+            return Ok(());
+        }
+
+        let value = f32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let value = T::from(value).filter(|value| !value.is_nan()).ok_or(Abort::ValueRange)?;
+        // Only the output bounds can be lifted, by writing an infinity.
+        if !value.is_finite() && !matches!(subindex, 8 | 9) {
+            return Err(Abort::ValueRange);
+        }
+        let (kp, ki, kd) = controller.gains();
+        let (min, max) = controller.output_bounds();
+        match subindex {
+            1 => controller.setpoint = value,
+            3 if controller.is_manual() => {
+                controller.set_manual(value);
+            }
+            3 => return Err(Abort::DeviceState),
+            5 => {
+                controller.set_gains(value, ki, kd);
+            }
+            6 => {
+                controller.set_gains(kp, value, kd);
+            }
+            7 => {
+                controller.set_gains(kp, ki, value);
+            }
+            8 if value <= max => {
+                controller.bound_output(value..=max);
+            }
+            9 if value >= min => {
+                controller.bound_output(min..=value);
+            }
+            _ => return Err(Abort::ValueRange),
+        }
+        Ok(())
+    }
+}
+
+/// Writes a single byte value to `data` and returns its length.
+fn byte(data: &mut [u8; 4], value: u8) -> usize {
+    *data = [value, 0, 0, 0];
+    1
+}
@@ -0,0 +1,146 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, PID};
+
+/// Implementation of a cascade of two controllers, where the output of the `primary`
+/// (outer) controller is the setpoint of the `secondary` (inner) controller.
+///
+/// The secondary controller usually runs faster than the primary one, each primary
+/// step is followed by `ratio` secondary steps. Both controllers must be created with
+/// their own sampling time, the one of the primary being `ratio` times longer.
+///
+/// When the secondary output saturates, the primary integral term is kept from winding
+/// up as selected by [`Windup`]. This assumes an increase of the secondary setpoint
+/// increases its output.
+#[derive(Clone, Copy, Debug)]
+pub struct Cascade<P, S> {
+    /// Outer controller, its output drives the setpoint of the secondary.
+    pub primary: P,
+    /// Inner controller, its output drives the actuator.
+    pub secondary: S,
+
+    /// Number of secondary steps per primary step.
+    ratio: u32,
+    /// Number of secondary steps since the last primary step.
+    count: u32,
+    /// How the primary integral reacts to a saturated secondary.
+    windup: Windup,
+}
+
+/// How the integral term of the primary controller of a [`Cascade`] is kept from
+/// winding up while the secondary output is saturated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Windup {
+    /// The primary integral is prevented from moving in the direction that would drive
+    /// the secondary further into saturation.
+    Inhibit,
+    /// The primary integral is recalculated against the secondary measurement, the
+    /// setpoint the secondary actually achieves while saturated.
+    BackCalculate,
+}
+
+impl<T: FloatCore> Cascade<PID<T>, PID<T>> {
+    /// Creates a new `Cascade` from two controllers, where the `secondary` controller
+    /// steps `ratio` times for every step of the `primary` controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::cascade::Cascade;
+    /// use control::pid::PID;
+    ///
+    /// // Temperature loop at 10 Hz driving a flow loop at 100 Hz.
+    /// let temperature = PID::new(2.0, 0.1, 0.0, 0.0, 0.1, 60.0);
+    /// let mut flow = PID::new(0.5, 2.0, 0.0, 0.0, 0.01, 0.0);
+    /// flow.bound_output(0.0..=100.0);
+    ///
+    /// let mut cascade = Cascade::new(temperature, flow, 10);
+    /// let valve = cascade.step(20.0, 0.0);
+    /// assert!(valve > 0.0);
+    /// ```
+    pub fn new(primary: PID<T>, secondary: PID<T>, ratio: u32) -> Self {
+        assert!(ratio > 0);
+        Self { primary, secondary, ratio, count: 0, windup: Windup::Inhibit }
+    }
+
+    /// Changes how the primary integral reacts to a saturated secondary.
+    pub fn set_windup(&mut self, windup: Windup) -> &mut Self {
+        self.windup = windup;
+        self
+    }
+
+    /// Returns whether the output of the secondary controller is saturated.
+    pub fn saturation(&self) -> Saturation {
+        self.secondary.saturation()
+    }
+
+    /// Switches both controllers to manual mode, imposing `output` on the actuator.
+    ///
+    /// The primary controller tracks the secondary measurement, so that switching back
+    /// to automatic mode is bumpless for both loops.
+    pub fn set_manual(&mut self, output: T) -> &mut Self {
+        self.secondary.set_manual(output);
+        self.primary.set_manual(self.secondary.setpoint);
+        self
+    }
+
+    /// Switches both controllers back to automatic mode.
+    pub fn set_automatic(&mut self) -> &mut Self {
+        self.primary.set_automatic();
+        self.secondary.set_automatic();
+        self
+    }
+
+    /// Returns whether the cascade is in manual mode.
+    pub fn is_manual(&self) -> bool {
+        self.secondary.is_manual()
+    }
+
+    /// Clears the state of both controllers.
+    pub fn reset(&mut self) {
+        self.primary.reset();
+        self.secondary.reset();
+        self.count = 0;
+    }
+
+    /// Performs a single step of the secondary loop, and of the primary loop when it is
+    /// due. It should be called exactly once every secondary sampling time.
+    pub fn step(&mut self, primary_measurement: T, secondary_measurement: T) -> T {
+        if self.count == 0 {
+            if self.secondary.is_manual() {
+                // Keep the primary output on the current inner process value.
+                self.primary.set_manual(secondary_measurement);
+            }
+            match self.windup {
+                Windup::Inhibit => {
+                    self.primary.inhibit_integral(self.secondary.saturation());
+                    self.secondary.setpoint = self.primary.step(primary_measurement);
+                }
+                Windup::BackCalculate => {
+                    self.primary.inhibit_integral(Saturation::Unsaturated);
+                    let setpoint = self.primary.step(primary_measurement);
+                    // Only correct the primary when it pushes further into saturation.
+                    let unrealized = match self.secondary.saturation() {
+                        Saturation::Upper => setpoint > secondary_measurement,
+                        Saturation::Lower => setpoint < secondary_measurement,
+                        Saturation::Unsaturated => false,
+                    };
+                    if unrealized && !self.primary.is_manual() {
+                        self.primary.track(secondary_measurement);
+                    }
+                    self.secondary.setpoint = setpoint;
+                }
+            }
+        }
+        self.count = (self.count + 1) % self.ratio;
+
+        if self.secondary.is_manual() {
+            self.secondary.setpoint = secondary_measurement;
+        }
+        self.secondary.step(secondary_measurement)
+    }
+}
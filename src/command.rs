@@ -0,0 +1,410 @@
+//! A line-based text protocol to tune a controller over a serial console, such as
+//! `SET KP 1.5` or `GET STATE`, independent of the transport.
+//!
+//! # Commands
+//!
+//! | Command               | Effect                                               |
+//! |-----------------------|------------------------------------------------------|
+//! | `SET <param> <value>` | Changes a parameter                                  |
+//! | `SET MODE AUTO\|MAN`  | Switches to automatic mode or to manual mode         |
+//! | `GET <param>`         | Returns a parameter as `<param> <value>`             |
+//! | `GET STATE`           | Returns `SP=<sp> PV=<pv> OUT=<out> MODE=<mode> SAT=<sat>` |
+//! | `RESET`               | Clears the controller state                          |
+//! | `AT START\|STOP`      | Starts or stops an autotuner, left to the application |
+//!
+//! The parameters are `SP`, `KP`, `KI`, `KD`, `OMIN`, `OMAX` and `OUT`, setting the
+//! output switching to manual mode. Commands are case insensitive, successful ones
+//! without a value answer `OK` and failed ones `ERR <reason>`.
+
+use core::fmt::{self, Write};
+use core::str;
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, PID};
+
+/// A parameter of the controller reachable with `SET` and `GET`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameter {
+    /// `SP`, the setpoint.
+    Setpoint,
+    /// `KP`, the proportional gain.
+    Kp,
+    /// `KI`, the integral gain.
+    Ki,
+    /// `KD`, the derivative gain.
+    Kd,
+    /// `OMIN`, the lower bound of the output.
+    OutputMin,
+    /// `OMAX`, the upper bound of the output.
+    OutputMax,
+    /// `OUT`, the output, only settable in manual mode.
+    Output,
+}
+
+impl Parameter {
+    /// Returns the name of the parameter in commands.
+    pub fn name(self) -> &'static str {
+        match self {
+            Parameter::Setpoint => "SP",
+            Parameter::Kp => "KP",
+            Parameter::Ki => "KI",
+            Parameter::Kd => "KD",
+            Parameter::OutputMin => "OMIN",
+            Parameter::OutputMax => "OMAX",
+            Parameter::Output => "OUT",
+        }
+    }
+
+    /// Returns the parameter with the given name, ignoring case.
+    fn from_name(name: &str) -> Option<Self> {
+        let parameters = [
+            Parameter::Setpoint,
+            Parameter::Kp,
+            Parameter::Ki,
+            Parameter::Kd,
+            Parameter::OutputMin,
+            Parameter::OutputMax,
+            Parameter::Output,
+        ];
+        parameters.iter().copied().find(|parameter| parameter.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A parsed command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command<T> {
+    /// `SET <param> <value>`.
+    Set(Parameter, T),
+    /// `SET MODE MAN` if `true`, `SET MODE AUTO` otherwise.
+    SetManual(bool),
+    /// `GET <param>`.
+    Get(Parameter),
+    /// `GET STATE`.
+    GetState,
+    /// `RESET`.
+    Reset,
+    /// `AT START` if `true`, `AT STOP` otherwise.
+    Autotune(bool),
+}
+
+/// Why a command line failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The line holds no command.
+    Empty,
+    /// The command is not part of the protocol.
+    UnknownCommand,
+    /// The parameter is not part of the protocol.
+    UnknownParameter,
+    /// The value is missing or is not a number.
+    InvalidValue,
+    /// The command is valid but cannot be applied in the current state, such as an
+    /// output bound beyond the other one or an output set in automatic mode.
+    Rejected,
+    /// The command is handled by the application, such as `AT`.
+    Unsupported,
+    /// The response does not fit in the buffer.
+    Overflow,
+}
+
+impl Error {
+    /// Returns the reason given in the `ERR` response.
+    pub fn reason(self) -> &'static str {
+        match self {
+            Error::Empty => "EMPTY",
+            Error::UnknownCommand => "UNKNOWN COMMAND",
+            Error::UnknownParameter => "UNKNOWN PARAMETER",
+            Error::InvalidValue => "INVALID VALUE",
+            Error::Rejected => "REJECTED",
+            Error::Unsupported => "UNSUPPORTED",
+            Error::Overflow => "OVERFLOW",
+        }
+    }
+}
+
+impl<T: FloatCore> Command<T> {
+    /// Parses a command line, with or without its line terminator.
+    ///
+    /// Values that are not finite, such as `nan` or `inf`, are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::command::{Command, Error, Parameter};
+    ///
+    /// assert_eq!(Command::parse(b"set kp 1.5\r\n"), Ok(Command::Set(Parameter::Kp, 1.5)));
+    /// assert_eq!(Command::<f32>::parse(b"SET KP nan"), Err(Error::InvalidValue));
+    /// assert_eq!(Command::<f32>::parse(b"SET SP inf"), Err(Error::InvalidValue));
+    /// ```
+    pub fn parse(line: &[u8]) -> Result<Self, Error> {
+        let line = str::from_utf8(line).map_err(|_| Error::UnknownCommand)?;
+        let mut words = line.split_ascii_whitespace();
+        let command = words.next().ok_or(Error::Empty)?;
+        let argument = words.next();
+        let value = words.next();
+        if words.next().is_some() {
+            return Err(Error::InvalidValue);
+        }
+
+        let is = |word: Option<&str>, expected: &str| match word {
+            Some(word) => word.eq_ignore_ascii_case(expected),
+            None => false,
+        };
+        let command = if command.eq_ignore_ascii_case("SET") {
+            if is(argument, "MODE") {
+                match value {
+                    _ if is(value, "AUTO") => Command::SetManual(false),
+                    _ if is(value, "MAN") => Command::SetManual(true),
+                    _ => return Err(Error::InvalidValue),
+                }
+            } else {
+                let parameter = parameter(argument)?;
+                let value = value.and_then(|value| value.parse::<f64>().ok());
+                let value = value.and_then(T::from).filter(|value| value.is_finite());
+                let value = value.ok_or(Error::InvalidValue)?;
+                Command::Set(parameter, value)
+            }
+        } else if command.eq_ignore_ascii_case("GET") {
+            if value.is_some() {
+                return Err(Error::InvalidValue);
+            }
+            if is(argument, "STATE") {
+                Command::GetState
+            } else {
+                Command::Get(parameter(argument)?)
+            }
+        } else if command.eq_ignore_ascii_case("RESET") {
+            if argument.is_some() {
+                return Err(Error::InvalidValue);
+            }
+            Command::Reset
+        } else if command.eq_ignore_ascii_case("AT") {
+            match argument {
+                _ if value.is_some() => return Err(Error::InvalidValue),
+                _ if is(argument, "START") => Command::Autotune(true),
+                _ if is(argument, "STOP") => Command::Autotune(false),
+                _ => return Err(Error::InvalidValue),
+            }
+        } else {
+            return Err(Error::UnknownCommand);
+        };
+        Ok(command)
+    }
+
+    /// Applies the command to `controller` and writes the response, without line
+    /// terminator, to `response`. Returns the length of the response.
+    ///
+    /// [`Command::Autotune`] fails with [`Error::Unsupported`], so the application
+    /// should handle it before.
+    pub fn apply(self, controller: &mut PID<T>, response: &mut [u8]) -> Result<usize, Error> {
+        let mut cursor = Cursor { buffer: response, len: 0 };
+        match self {
+            Command::Set(parameter, value) => {
+                let (kp, ki, kd) = controller.gains();
+                let (min, max) = controller.output_bounds();
+                match parameter {
+                    Parameter::Setpoint => controller.setpoint = value,
+                    Parameter::Kp => {
+                        controller.set_gains(value, ki, kd);
+                    }
+                    Parameter::Ki => {
+                        controller.set_gains(kp, value, kd);
+                    }
+                    Parameter::Kd => {
+                        controller.set_gains(kp, ki, value);
+                    }
+                    Parameter::OutputMin if value <= max => {
+                        controller.bound_output(value..=max);
+                    }
+                    Parameter::OutputMax if value >= min => {
+                        controller.bound_output(min..=value);
+                    }
+                    Parameter::Output if controller.is_manual() => {
+                        controller.set_manual(value);
+                    }
+                    _ => return Err(Error::Rejected),
+                }
+                cursor.write_str("OK")
+            }
+            Command::SetManual(true) => {
+                if !controller.is_manual() {
+                    let output = controller.output();
+                    controller.set_manual(output);
+                }
+                cursor.write_str("OK")
+            }
+            Command::SetManual(false) => {
+                controller.set_automatic();
+                cursor.write_str("OK")
+            }
+            Command::Get(parameter) => {
+                let (kp, ki, kd) = controller.gains();
+                let (min, max) = controller.output_bounds();
+                let value = match parameter {
+                    Parameter::Setpoint => controller.setpoint,
+                    Parameter::Kp => kp,
+                    Parameter::Ki => ki,
+                    Parameter::Kd => kd,
+                    Parameter::OutputMin => min,
+                    Parameter::OutputMax => max,
+                    Parameter::Output => controller.output(),
+                };
+                write!(cursor, "{} {}", parameter.name(), number(value))
+            }
+            Command::GetState => {
+                let record = controller.record();
+                let mode = if record.manual { "MAN" } else { "AUTO" };
+                let saturation = match record.saturation {
+                    Saturation::Unsaturated => "NONE",
+                    Saturation::Lower => "LOW",
+                    Saturation::Upper => "HIGH",
+                };
+                write!(
+                    cursor,
+                    "SP={} PV={} OUT={} MODE={} SAT={}",
+                    number(record.setpoint),
+                    number(record.measurement),
+                    number(record.output),
+                    mode,
+                    saturation
+                )
+            }
+            Command::Reset => {
+                controller.reset();
+                cursor.write_str("OK")
+            }
+            Command::Autotune(_) => return Err(Error::Unsupported),
+        }
+        .map_err(|_| Error::Overflow)?;
+        Ok(cursor.len)
+    }
+}
+
+/// Parses a command line, applies it to `controller` and writes the response line,
+/// `ERR <reason>` on failure, to `response`. Returns the length of the response,
+/// without line terminator.
+///
+/// # Examples
+///
+/// ```
+/// use control::command;
+/// use control::pid::PID;
+///
+/// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 20.0);
+/// let mut response = [0; 64];
+///
+/// let len = command::execute(&mut pid, b"SET KP 1.5\r\n", &mut response);
+/// assert_eq!(&response[..len], b"OK");
+/// pid.step(18.0);
+///
+/// let len = command::execute(&mut pid, b"get state", &mut response);
+/// assert_eq!(&response[..len], b"SP=20 PV=18 OUT=3 MODE=AUTO SAT=NONE");
+///
+/// let len = command::execute(&mut pid, b"SET OUT 5", &mut response);
+/// assert_eq!(&response[..len], b"ERR REJECTED");
+/// ```
+pub fn execute<T: FloatCore>(controller: &mut PID<T>, line: &[u8], response: &mut [u8]) -> usize {
+    let result = Command::parse(line).and_then(|command| command.apply(controller, response));
+    match result {
+        Ok(len) => len,
+        Err(error) => {
+            let mut cursor = Cursor { buffer: response, len: 0 };
+            // A truncated error is the best that fits.
+            let _ = write!(cursor, "ERR {}", error.reason());
+            cursor.len
+        }
+    }
+}
+
+/// Implementation of a buffer assembling the bytes received from a transport into
+/// command lines of up to `N` bytes.
+#[derive(Clone, Copy, Debug)]
+pub struct LineBuffer<const N: usize> {
+    /// Bytes of the current line.
+    bytes: [u8; N],
+    /// Number of bytes of the current line.
+    len: usize,
+    /// Whether the current line is longer than the buffer and must be dropped.
+    overflow: bool,
+}
+
+impl<const N: usize> LineBuffer<N> {
+    /// Creates a new empty `LineBuffer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::command::LineBuffer;
+    ///
+    /// let mut buffer = LineBuffer::<32>::new();
+    /// let mut lines = b"GET SP\r\nRESET\n".iter().filter_map(|&byte| buffer.push(byte).map(<[u8]>::to_vec));
+    /// assert_eq!(lines.next().unwrap(), b"GET SP");
+    /// assert_eq!(lines.next().unwrap(), b"RESET");
+    /// ```
+    pub fn new() -> Self {
+        Self { bytes: [0; N], len: 0, overflow: false }
+    }
+
+    /// Adds a received byte and returns the complete line, without its terminator, on
+    /// a line feed. Carriage returns are ignored and lines longer than `N` bytes are
+    /// dropped.
+    pub fn push(&mut self, byte: u8) -> Option<&[u8]> {
+        match byte {
+            b'\n' => {
+                let len = core::mem::replace(&mut self.len, 0);
+                if core::mem::replace(&mut self.overflow, false) {
+                    None
+                } else {
+                    Some(&self.bytes[..len])
+                }
+            }
+            b'\r' => None,
+            _ if self.len < N => {
+                self.bytes[self.len] = byte;
+                self.len += 1;
+                None
+            }
+            _ => {
+                self.overflow = true;
+                None
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for LineBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the parameter named by `word`.
+fn parameter(word: Option<&str>) -> Result<Parameter, Error> {
+    word.and_then(Parameter::from_name).ok_or(Error::UnknownParameter)
+}
+
+/// Converts a value to a `f64` for printing, without the sign of a negative zero.
+fn number<T: FloatCore>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN) + 0.0
+}
+
+/// Formatter writing into a byte buffer.
+struct Cursor<'a> {
+    /// Destination of the text.
+    buffer: &'a mut [u8],
+    /// Number of bytes written.
+    len: usize,
+}
+
+impl Write for Cursor<'_> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use num_traits::float::FloatCore;
+
+use crate::lookup::Table;
+use crate::pid::PID;
+
+/// Signal of a [`Compensated`] controller passed through the compensation table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Compensation {
+    /// The setpoint and the measurement are mapped to linear units, so the controller
+    /// sees the error in units in which the process is linear.
+    Measurement,
+    /// The error is mapped through the table, which should pass through zero, so the
+    /// controller acts harder or softer depending on how large the error is. The
+    /// derivative term then acts on the error, setpoint changes included.
+    Error,
+}
+
+/// Implementation of a gain compensation for strongly nonlinear processes, such as pH,
+/// so a single tuning of the controller works across the operating range.
+///
+/// The table is the inverse of the static nonlinearity of the process, mapping the
+/// measurement or the error to units in which the process is linear. For pH it comes
+/// from the titration curve, mapping each pH to the amount of reagent that reaches it,
+/// which flattens the steep region around neutrality.
+#[derive(Clone, Copy, Debug)]
+pub struct Compensated<T, const N: usize> {
+    /// Controller working in linear units.
+    pub controller: PID<T>,
+
+    /// Inverse of the process nonlinearity.
+    table: Table<T, N>,
+    /// Signal passed through the table.
+    compensation: Compensation,
+    /// Setpoint in process units.
+    setpoint: T,
+}
+
+impl<T: FloatCore, const N: usize> Compensated<T, N> {
+    /// Creates a new `Compensated` controller passing the signal chosen by
+    /// `compensation` through `table`. The setpoint of `controller` is taken in process
+    /// units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::compensation::{Compensated, Compensation};
+    /// use control::lookup::Table;
+    /// use control::pid::PID;
+    ///
+    /// // Reagent excess reaching each pH, from the titration curve.
+    /// let titration = Table::new(
+    ///     [2.0, 4.0, 6.0, 7.0, 8.0, 10.0, 12.0],
+    ///     [-1.0, -0.9, -0.1, 0.0, 0.1, 0.9, 1.0],
+    /// );
+    /// let pid = PID::new(1.0, 0.0, 0.0, 0.0, 1.0, 7.0);
+    /// let mut neutralizer = Compensated::new(pid, titration, Compensation::Measurement);
+    ///
+    /// // One pH unit off near neutrality needs far less reagent than far from it.
+    /// assert_eq!(neutralizer.step(6.0), 0.1);
+    /// assert_eq!(neutralizer.step(4.0), 0.9);
+    /// ```
+    pub fn new(controller: PID<T>, table: Table<T, N>, compensation: Compensation) -> Self {
+        let setpoint = controller.setpoint;
+        Self { controller, table, compensation, setpoint }
+    }
+
+    /// Changes the setpoint, in process units.
+    pub fn set_setpoint(&mut self, setpoint: T) -> &mut Self {
+        self.setpoint = setpoint;
+        self
+    }
+
+    /// Returns the setpoint, in process units.
+    pub fn setpoint(&self) -> T {
+        self.setpoint
+    }
+
+    /// Replaces the compensation table.
+    pub fn set_table(&mut self, table: Table<T, N>) -> &mut Self {
+        self.table = table;
+        self
+    }
+
+    /// Clears the controller state.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+    }
+
+    /// Performs a single step of the control loop from the measurement in process
+    /// units and returns the output. It should be called exactly once every sampling
+    /// time.
+    pub fn step(&mut self, measurement: T) -> T {
+        match self.compensation {
+            Compensation::Measurement => {
+                self.controller.setpoint = self.table.interpolate(self.setpoint);
+                self.controller.step(self.table.interpolate(measurement))
+            }
+            Compensation::Error => {
+                self.controller.setpoint = T::zero();
+                self.controller.step(-self.table.interpolate(self.setpoint - measurement))
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! A packed, CRC protected and versioned representation of the configuration of a
+//! controller, so saved tunings survive power cycles and firmware upgrades.
+//!
+//! # Format
+//!
+//! Each record is [`RECORD_LEN`] bytes long, so a flash or EEPROM page holds a whole
+//! number of them. Writing each new record to the next slot of the page, and reading
+//! back the one with the latest generation, spreads the wear over the page.
+//!
+//! | Offset | Type     | Field                                            |
+//! |--------|----------|--------------------------------------------------|
+//! | 0      | `u8`     | Format version, [`VERSION`]                      |
+//! | 1      | `u8`     | Length `n` of the fields                         |
+//! | 2      | `u16`    | Generation, wrapping                             |
+//! | 4      | `n` bytes| Fields, see below                                |
+//! | 4 + n  | `u16`    | CRC-16/CCITT-FALSE of the bytes above            |
+//!
+//! The fields are, as `f32`, the proportional, integral and derivative gains, the
+//! derivative filter time constant, the sampling time, the setpoint, the output
+//! bounds, the integral bounds and the bias, followed by a `u8` set to 1 for an
+//! inverted output. Unbounded sides are infinite. All the fields are little endian and
+//! the rest of the record is left erased, at `0xff`.
+//!
+//! New fields are only ever appended within a version: a record with fewer fields
+//! than known is migrated by giving the missing ones their default value, and fields
+//! unknown to an older firmware are ignored. The version only changes for layouts that
+//! cannot be read this way.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+use crate::telemetry::crc16;
+
+/// Version of the record format.
+pub const VERSION: u8 = 1;
+
+/// Length of a record.
+pub const RECORD_LEN: usize = 64;
+
+/// Length of the fields written by this version.
+const FIELDS_LEN: usize = 45;
+
+/// Why a record could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The record is erased, truncated or its CRC is wrong.
+    Corrupted,
+    /// The record has a version this firmware cannot read.
+    Version(u8),
+}
+
+/// Configuration of a [`PID`] controller.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config<T> {
+    /// Proportional gain.
+    pub kp: T,
+    /// Integral gain.
+    pub ki: T,
+    /// Derivative gain.
+    pub kd: T,
+    /// Time constant of the derivative low-pass filter.
+    pub tau: T,
+    /// Time difference in seconds between two consecutive steps.
+    pub sampling_time: T,
+    /// Setpoint.
+    pub setpoint: T,
+    /// Lower bound of the output.
+    pub output_min: T,
+    /// Upper bound of the output.
+    pub output_max: T,
+    /// Lower bound of the integral term.
+    pub integral_min: T,
+    /// Upper bound of the integral term.
+    pub integral_max: T,
+    /// Constant offset added to the output.
+    pub bias: T,
+    /// Whether a larger control effort corresponds to a smaller output.
+    pub inverted: bool,
+}
+
+impl<T: FloatCore> Config<T> {
+    /// Creates a new `Config` with the arguments of [`PID::new`], no bounds, no bias
+    /// and a direct acting output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::config::{self, Config};
+    ///
+    /// let mut config = Config::new(2.0_f32, 0.5, 0.0, 0.0, 0.1, 60.0);
+    /// config.output_min = 0.0;
+    /// config.output_max = 100.0;
+    ///
+    /// // A page of four records, as erased.
+    /// let mut page = [0xff; 4 * config::RECORD_LEN];
+    /// let (slot, generation) = config::next_slot(&page);
+    /// page[slot * config::RECORD_LEN..][..config::RECORD_LEN]
+    ///     .copy_from_slice(&config.encode(generation));
+    ///
+    /// let (_, _, saved) = config::latest::<f32>(&page).unwrap();
+    /// assert_eq!(saved, config);
+    /// let mut pid = saved.pid();
+    /// assert_eq!(pid.step(0.0), 100.0);
+    /// ```
+    pub fn new(kp: T, ki: T, kd: T, tau: T, sampling_time: T, setpoint: T) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            tau,
+            sampling_time,
+            setpoint,
+            output_min: T::neg_infinity(),
+            output_max: T::infinity(),
+            integral_min: T::neg_infinity(),
+            integral_max: T::infinity(),
+            bias: T::zero(),
+            inverted: false,
+        }
+    }
+
+    /// Creates a controller with this configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lower bound is larger than its upper bound.
+    pub fn pid(&self) -> PID<T> {
+        let mut pid =
+            PID::new(self.kp, self.ki, self.kd, self.tau, self.sampling_time, self.setpoint);
+        pid.bound_output(self.output_min..=self.output_max)
+            .bound_integral(self.integral_min..=self.integral_max)
+            .set_bias(self.bias)
+            .invert_output(self.inverted);
+        pid
+    }
+
+    /// Encodes the configuration into a record of the given generation, which should
+    /// be one more than the one of the record it replaces.
+    pub fn encode(&self, generation: u16) -> [u8; RECORD_LEN] {
+        let mut record = [0xff; RECORD_LEN];
+        record[0] = VERSION;
+        record[1] = FIELDS_LEN as u8;
+        record[2..4].copy_from_slice(&generation.to_le_bytes());
+
+        let values = [
+            self.kp,
+            self.ki,
+            self.kd,
+            self.tau,
+            self.sampling_time,
+            self.setpoint,
+            self.output_min,
+            self.output_max,
+            self.integral_min,
+            self.integral_max,
+            self.bias,
+        ];
+        for (chunk, value) in record[4..].chunks_exact_mut(4).zip(values.iter()) {
+            let value = value.to_f32().unwrap_or(f32::NAN);
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        record[4 + FIELDS_LEN - 1] = self.inverted as u8;
+
+        let end = 4 + FIELDS_LEN;
+        let crc = crc16(&record[..end]);
+        record[end..end + 2].copy_from_slice(&crc.to_le_bytes());
+        record
+    }
+
+    /// Decodes a record, migrating it from an older layout if needed, and returns the
+    /// configuration with the generation of the record.
+    pub fn decode(record: &[u8]) -> Result<(Self, u16), Error> {
+        if record.len() < 4 {
+            return Err(Error::Corrupted);
+        }
+        let end = 4 + usize::from(record[1]);
+        if end + 2 > record.len() {
+            return Err(Error::Corrupted);
+        }
+        if crc16(&record[..end]) != u16::from_le_bytes([record[end], record[end + 1]]) {
+            return Err(Error::Corrupted);
+        }
+        if record[0] != VERSION {
+            return Err(Error::Version(record[0]));
+        }
+        let generation = u16::from_le_bytes([record[2], record[3]]);
+
+        let fields = &record[4..end];
+        let value = |index: usize, default: T| -> Result<T, Error> {
+            match fields.get(4 * index..4 * index + 4) {
+                Some(bytes) => {
+                    let value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    T::from(value).ok_or(Error::Corrupted)
+                }
+                None => Ok(default),
+            }
+        };
+        // The gains, sampling time and setpoint were part of the first layout.
+        if fields.len() < 24 {
+            return Err(Error::Corrupted);
+        }
+        let zero = T::zero();
+        let mut config = Self::new(
+            value(0, zero)?,
+            value(1, zero)?,
+            value(2, zero)?,
+            value(3, zero)?,
+            value(4, zero)?,
+            value(5, zero)?,
+        );
+        config.output_min = value(6, config.output_min)?;
+        config.output_max = value(7, config.output_max)?;
+        config.integral_min = value(8, config.integral_min)?;
+        config.integral_max = value(9, config.integral_max)?;
+        config.bias = value(10, config.bias)?;
+        config.inverted = fields.get(44).map_or(config.inverted, |&flag| flag == 1);
+        Ok((config, generation))
+    }
+}
+
+/// Returns the slot, generation and configuration of the latest valid record of a
+/// page, or `None` if the page holds none.
+pub fn latest<T: FloatCore>(page: &[u8]) -> Option<(usize, u16, Config<T>)> {
+    let mut latest: Option<(usize, u16, Config<T>)> = None;
+    for (slot, record) in page.chunks_exact(RECORD_LEN).enumerate() {
+        if let Ok((config, generation)) = Config::decode(record) {
+            let newer = match latest {
+                // Generations wrap, the newer one is less than half a turn ahead.
+                Some((_, current, _)) => generation.wrapping_sub(current) as i16 > 0,
+                None => true,
+            };
+            if newer {
+                latest = Some((slot, generation, config));
+            }
+        }
+    }
+    latest
+}
+
+/// Returns the slot and generation of the next record to write to a page, after the
+/// latest valid one.
+///
+/// # Panics
+///
+/// Panics if the page is shorter than a record.
+pub fn next_slot(page: &[u8]) -> (usize, u16) {
+    let slots = page.len() / RECORD_LEN;
+    assert!(slots > 0, "The page is shorter than a record");
+    match latest::<f32>(page) {
+        Some((slot, generation, _)) => ((slot + 1) % slots, generation.wrapping_add(1)),
+        None => (0, 0),
+    }
+}
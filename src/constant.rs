@@ -0,0 +1,37 @@
+//! Exact constants for any floating point type.
+//!
+//! They are built from one with additions and divisions that are exact in binary,
+//! instead of casting `f32` literals, which can fail and loses the precision of types
+//! wider than `f32`.
+
+use num_traits::Num;
+
+/// Returns two.
+pub(crate) fn two<T: Num + Copy>() -> T {
+    T::one() + T::one()
+}
+
+/// Returns four.
+pub(crate) fn four<T: Num + Copy>() -> T {
+    two::<T>() + two::<T>()
+}
+
+/// Returns one half.
+pub(crate) fn half<T: Num + Copy>() -> T {
+    T::one() / two::<T>()
+}
+
+/// Returns six.
+pub(crate) fn six<T: Num + Copy>() -> T {
+    four::<T>() + two::<T>()
+}
+
+/// Returns ten.
+pub(crate) fn ten<T: Num + Copy>() -> T {
+    four::<T>() + six::<T>()
+}
+
+/// Returns one hundred.
+pub(crate) fn hundred<T: Num + Copy>() -> T {
+    ten::<T>() * ten::<T>()
+}
@@ -0,0 +1,159 @@
+//! A common interface to the single-input single-output controllers of the crate.
+
+use num_traits::float::FloatCore;
+
+use crate::integrating::Integrating;
+use crate::multirate::MultiRate;
+use crate::pid::{Saturation, PID};
+
+/// A controller computing an output from a single measurement, so application code
+/// and composition utilities can be written independently of the control law.
+///
+/// # Examples
+///
+/// ```
+/// use control::controller::Controller;
+/// use control::multirate::MultiRate;
+/// use control::pid::PID;
+///
+/// fn settle<C: Controller<f64>>(controller: &mut C, measurement: f64) -> f64 {
+///     controller.limit_output(0.0, 10.0);
+///     (0..10).fold(0.0, |_, _| controller.step(measurement))
+/// }
+///
+/// assert_eq!(settle(&mut PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 50.0), 0.0), 10.0);
+/// assert_eq!(settle(&mut MultiRate::new(1.0, 0.0, 0.0, 0.0, 0.1, 2, 50.0), 0.0), 10.0);
+/// ```
+pub trait Controller<T> {
+    /// Performs a single step of the control loop and returns the output. It should be
+    /// called exactly once every sampling time.
+    fn step(&mut self, measurement: T) -> T;
+
+    /// Returns the setpoint.
+    fn setpoint(&self) -> T;
+
+    /// Changes the setpoint.
+    fn set_setpoint(&mut self, setpoint: T);
+
+    /// Returns the output of the last step.
+    fn output(&self) -> T;
+
+    /// Returns whether the output of the last step had to be clamped.
+    fn saturation(&self) -> Saturation;
+
+    /// Restricts the output to the `[min, max]` interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` > `max`.
+    fn limit_output(&mut self, min: T, max: T);
+
+    /// Recomputes the internal state so that the last step would have produced
+    /// `output`, for bumpless transfers and external anti-windup.
+    fn track(&mut self, output: T);
+
+    /// Clears the controller state, as if it had just been created.
+    fn reset(&mut self);
+}
+
+impl<T: FloatCore> Controller<T> for PID<T> {
+    fn step(&mut self, measurement: T) -> T {
+        PID::step(self, measurement)
+    }
+
+    fn setpoint(&self) -> T {
+        self.setpoint
+    }
+
+    fn set_setpoint(&mut self, setpoint: T) {
+        self.setpoint = setpoint;
+    }
+
+    fn output(&self) -> T {
+        PID::output(self)
+    }
+
+    fn saturation(&self) -> Saturation {
+        PID::saturation(self)
+    }
+
+    fn limit_output(&mut self, min: T, max: T) {
+        self.bound_output(min..=max);
+    }
+
+    fn track(&mut self, output: T) {
+        PID::track(self, output);
+    }
+
+    fn reset(&mut self) {
+        PID::reset(self);
+    }
+}
+
+impl<T: FloatCore> Controller<T> for MultiRate<T> {
+    fn step(&mut self, measurement: T) -> T {
+        MultiRate::step(self, measurement)
+    }
+
+    fn setpoint(&self) -> T {
+        self.controller.setpoint
+    }
+
+    fn set_setpoint(&mut self, setpoint: T) {
+        self.controller.setpoint = setpoint;
+    }
+
+    fn output(&self) -> T {
+        self.controller.output()
+    }
+
+    fn saturation(&self) -> Saturation {
+        self.controller.saturation()
+    }
+
+    fn limit_output(&mut self, min: T, max: T) {
+        self.controller.bound_output(min..=max);
+    }
+
+    fn track(&mut self, output: T) {
+        self.controller.track(output);
+    }
+
+    fn reset(&mut self) {
+        MultiRate::reset(self);
+    }
+}
+
+impl<T: FloatCore> Controller<T> for Integrating<T> {
+    fn step(&mut self, measurement: T) -> T {
+        Integrating::step(self, measurement)
+    }
+
+    fn setpoint(&self) -> T {
+        self.controller.setpoint
+    }
+
+    fn set_setpoint(&mut self, setpoint: T) {
+        self.controller.setpoint = setpoint;
+    }
+
+    fn output(&self) -> T {
+        self.controller.output()
+    }
+
+    fn saturation(&self) -> Saturation {
+        self.controller.saturation()
+    }
+
+    fn limit_output(&mut self, min: T, max: T) {
+        self.controller.bound_output(min..=max);
+    }
+
+    fn track(&mut self, output: T) {
+        self.controller.track(output);
+    }
+
+    fn reset(&mut self) {
+        Integrating::reset(self);
+    }
+}
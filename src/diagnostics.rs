@@ -0,0 +1,21 @@
+//! Tools to assess the performance and health of a control loop on-device.
+
+mod cycles;
+mod noise;
+mod oscillation;
+mod performance;
+mod statistics;
+mod step_response;
+mod stiction;
+mod trace;
+
+#[cfg(feature = "cortex-m")]
+pub use self::cycles::dwt_cycles;
+pub use self::cycles::{Cycles, Instrumented};
+pub use self::noise::Noise;
+pub use self::oscillation::Oscillation;
+pub use self::performance::{Criteria, Performance};
+pub use self::statistics::{Report, Statistics, Summary};
+pub use self::step_response::{step_response, StepResponse};
+pub use self::stiction::Stiction;
+pub use self::trace::Trace;
@@ -0,0 +1,153 @@
+use crate::controller::Controller;
+use crate::pid::Saturation;
+
+/// Minimum, mean and maximum durations of the steps measured by an [`Instrumented`]
+/// controller, in ticks of its clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycles {
+    /// Shortest step.
+    pub min: u32,
+    /// Mean step, over the first `u32::MAX` steps once the count saturates.
+    pub mean: u32,
+    /// Longest step.
+    pub max: u32,
+    /// Number of measured steps, saturating.
+    pub steps: u32,
+}
+
+/// Implementation of an instrumentation layer measuring how long each step of a
+/// controller takes, to check the control law fits in its interrupt budget.
+///
+/// The clock is a free running counter which may wrap around, such as the DWT cycle
+/// counter of a Cortex-M, see `dwt_cycles` with the `cortex-m` feature.
+#[derive(Clone, Copy, Debug)]
+pub struct Instrumented<C, N> {
+    /// Controller being measured.
+    pub controller: C,
+
+    /// Free running counter, wrapping around.
+    clock: N,
+    /// Shortest step.
+    min: u32,
+    /// Longest step.
+    max: u32,
+    /// Sum of the step durations.
+    total: u64,
+    /// Number of measured steps.
+    steps: u32,
+}
+
+impl<C, N: FnMut() -> u32> Instrumented<C, N> {
+    /// Creates a new `Instrumented` controller measuring the steps of `controller`
+    /// against `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::cell::Cell;
+    /// use control::diagnostics::Instrumented;
+    /// use control::pid::PID;
+    ///
+    /// // A fake clock advancing by 100 ticks on every read.
+    /// let clock = Cell::new(0_u32);
+    /// let tick = || {
+    ///     clock.set(clock.get().wrapping_add(100));
+    ///     clock.get()
+    /// };
+    ///
+    /// let mut pid = Instrumented::new(PID::new(1.0, 0.5, 0.0, 0.0, 0.01, 1.0), tick);
+    /// pid.step(0.0);
+    /// pid.step(0.5);
+    ///
+    /// let cycles = pid.cycles().unwrap();
+    /// assert_eq!((cycles.min, cycles.mean, cycles.max, cycles.steps), (100, 100, 100, 2));
+    /// ```
+    pub fn new(controller: C, clock: N) -> Self {
+        Self { controller, clock, min: u32::MAX, max: 0, total: 0, steps: 0 }
+    }
+
+    /// Returns the durations of the steps measured so far, `None` before the first
+    /// one.
+    pub fn cycles(&self) -> Option<Cycles> {
+        if self.steps == 0 {
+            return None;
+        }
+        let mean = (self.total / u64::from(self.steps)) as u32;
+        Some(Cycles { min: self.min, mean, max: self.max, steps: self.steps })
+    }
+
+    /// Forgets the durations measured so far, such as after enabling another feature.
+    pub fn reset_cycles(&mut self) {
+        self.min = u32::MAX;
+        self.max = 0;
+        self.total = 0;
+        self.steps = 0;
+    }
+
+    /// Performs a single step of the controller and measures its duration.
+    pub fn step<T>(&mut self, measurement: T) -> T
+    where
+        C: Controller<T>,
+    {
+        let start = (self.clock)();
+        let output = self.controller.step(measurement);
+        let elapsed = (self.clock)().wrapping_sub(start);
+
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+        // The mean stops following once the count saturates, so it stays the mean of
+        // the steps it counts.
+        if self.steps < u32::MAX {
+            self.total += u64::from(elapsed);
+            self.steps += 1;
+        }
+        output
+    }
+}
+
+impl<T, C, N> Controller<T> for Instrumented<C, N>
+where
+    C: Controller<T>,
+    N: FnMut() -> u32,
+{
+    fn step(&mut self, measurement: T) -> T {
+        Instrumented::step(self, measurement)
+    }
+
+    fn setpoint(&self) -> T {
+        self.controller.setpoint()
+    }
+
+    fn set_setpoint(&mut self, setpoint: T) {
+        self.controller.set_setpoint(setpoint);
+    }
+
+    fn output(&self) -> T {
+        self.controller.output()
+    }
+
+    fn saturation(&self) -> Saturation {
+        self.controller.saturation()
+    }
+
+    fn limit_output(&mut self, min: T, max: T) {
+        self.controller.limit_output(min, max);
+    }
+
+    fn track(&mut self, output: T) {
+        self.controller.track(output);
+    }
+
+    fn reset(&mut self) {
+        self.controller.reset();
+    }
+}
+
+/// Reads the DWT cycle counter of a Cortex-M, as a clock for [`Instrumented`].
+///
+/// The counter must have been enabled beforehand, for instance with
+/// `DCB::enable_trace` and `DWT::enable_cycle_counter` of the `cortex-m` crate.
+#[cfg(feature = "cortex-m")]
+pub fn dwt_cycles() -> u32 {
+    cortex_m::peripheral::DWT::cycle_count()
+}
@@ -0,0 +1,78 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of an online estimator of the measurement noise variance, so the
+/// derivative gain or the filter time constants can be adapted when a sensor gets
+/// noisy.
+///
+/// The measurement is high-passed with a second difference, which cancels constant
+/// and ramping signals, and the variance of the result is averaged exponentially. For
+/// white noise of variance `σ²` the second difference has a variance of `6σ²`, which
+/// is accounted for.
+#[derive(Clone, Copy, Debug)]
+pub struct Noise<T> {
+    /// Weight of the newest sample, between zero and one.
+    alpha: T,
+
+    /// Last two measurements, the newest first.
+    history: [Option<T>; 2],
+    /// Estimated noise variance, `None` until enough samples arrived.
+    variance: Option<T>,
+}
+
+impl<T: FloatCore> Noise<T> {
+    /// Creates a new `Noise` estimator, `alpha` being the weight of the newest sample
+    /// in the estimate, between zero and one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in the `(0, 1]` interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Noise;
+    ///
+    /// let mut noise = Noise::new(0.1);
+    /// // A ramp alternating by ±0.5 around its trend.
+    /// for k in 0..100 {
+    ///     let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+    ///     noise.step(0.3 * k as f64 + 0.5 * sign);
+    /// }
+    /// // Alternating noise is the worst case of the second difference, which sees
+    /// // it 16 times instead of 6.
+    /// let variance = noise.variance().unwrap();
+    /// assert!((variance - 0.25 * 16.0 / 6.0).abs() < 1e-6);
+    /// ```
+    pub fn new(alpha: T) -> Self {
+        assert!(alpha > T::zero() && alpha <= T::one());
+        Self { alpha, history: [None, None], variance: None }
+    }
+
+    /// Returns the estimated noise variance, `None` until three samples arrived.
+    pub fn variance(&self) -> Option<T> {
+        self.variance
+    }
+
+    /// Clears the internal state.
+    pub fn reset(&mut self) {
+        self.history = [None, None];
+        self.variance = None;
+    }
+
+    /// Takes a single measurement sample and returns the estimated noise variance.
+    pub fn step(&mut self, measurement: T) -> Option<T> {
+        if let [Some(previous), Some(before)] = self.history {
+            let six = constant::six::<T>();
+            let difference = measurement - (previous + previous) + before;
+            let sample = difference * difference / six;
+            self.variance = Some(match self.variance {
+                Some(variance) => variance + self.alpha * (sample - variance),
+                None => sample,
+            });
+        }
+        self.history = [Some(measurement), self.history[0]];
+        self.variance
+    }
+}
@@ -0,0 +1,116 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of an oscillation detector, which raises a flag when the control
+/// error keeps swinging from one side of the setpoint to the other, a sign of a loop
+/// gone marginally stable after a change in the plant.
+///
+/// A half cycle is counted each time the error crosses the band of `±amplitude` from
+/// one side to the other, the band acting as an hysteresis against measurement noise.
+/// The loop is considered oscillating after enough consecutive half cycles, each
+/// shorter than a given number of steps.
+#[derive(Clone, Copy, Debug)]
+pub struct Oscillation<T> {
+    /// Smallest swing of the error counted as a half cycle.
+    amplitude: T,
+    /// Number of consecutive half cycles after which the loop is oscillating.
+    half_cycles: u32,
+    /// Longest duration of a half cycle, in steps.
+    max_half_period: u32,
+
+    /// Side of the band the error was last seen on, `None` until it leaves the band.
+    positive: Option<bool>,
+    /// Number of consecutive half cycles counted.
+    count: u32,
+    /// Number of steps since the error last left the band on the other side.
+    steps: u32,
+    /// Duration of the last half cycle, in steps.
+    half_period: Option<u32>,
+}
+
+impl<T: FloatCore> Oscillation<T> {
+    /// Creates a new `Oscillation` detector flagging `half_cycles` consecutive swings
+    /// of the error beyond `±amplitude`, each lasting at most `max_half_period` steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amplitude` is negative or `half_cycles` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Oscillation;
+    ///
+    /// let mut detector = Oscillation::new(0.5, 4, 20);
+    /// // A decaying response does not raise the flag.
+    /// for &error in &[2.0, -1.0, 0.4, -0.2, 0.1, 0.0] {
+    ///     assert!(!detector.step(error));
+    /// }
+    ///
+    /// // A sustained one does.
+    /// let square = (0..40).map(|k| if k % 10 < 5 { 1.0 } else { -1.0 });
+    /// assert!(square.map(|error| detector.step(error)).last().unwrap());
+    /// assert_eq!(detector.half_period(), Some(5));
+    /// ```
+    pub fn new(amplitude: T, half_cycles: u32, max_half_period: u32) -> Self {
+        assert!(amplitude >= T::zero() && half_cycles > 0);
+        Self {
+            amplitude,
+            half_cycles,
+            max_half_period,
+            positive: None,
+            count: 0,
+            steps: 0,
+            half_period: None,
+        }
+    }
+
+    /// Returns whether the loop is considered oscillating.
+    pub fn is_oscillating(&self) -> bool {
+        self.count >= self.half_cycles
+    }
+
+    /// Returns the duration of the last half cycle in steps, half of the oscillation
+    /// period.
+    pub fn half_period(&self) -> Option<u32> {
+        self.half_period
+    }
+
+    /// Clears the internal state and lowers the flag.
+    pub fn reset(&mut self) {
+        self.positive = None;
+        self.count = 0;
+        self.steps = 0;
+        self.half_period = None;
+    }
+
+    /// Monitors the control `error` of a single step and returns whether the loop is
+    /// considered oscillating.
+    pub fn step(&mut self, error: T) -> bool {
+        self.steps = self.steps.saturating_add(1);
+        if self.steps > self.max_half_period {
+            self.count = 0;
+        }
+
+        let side = if error > self.amplitude {
+            Some(true)
+        } else if error < -self.amplitude {
+            Some(false)
+        } else {
+            None
+        };
+        if let Some(positive) = side {
+            if self.positive == Some(!positive) {
+                if self.steps <= self.max_half_period {
+                    self.count = self.count.saturating_add(1);
+                }
+                self.half_period = Some(self.steps);
+            }
+            if self.positive != Some(positive) {
+                self.steps = 0;
+            }
+            self.positive = Some(positive);
+        }
+
+        self.is_oscillating()
+    }
+}
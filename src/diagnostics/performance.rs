@@ -0,0 +1,131 @@
+use num_traits::float::FloatCore;
+
+/// Integral performance criteria of a control loop, the lower the better.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Criteria<T> {
+    /// Integral of the absolute error.
+    pub iae: T,
+    /// Integral of the squared error.
+    pub ise: T,
+    /// Integral of the time-weighted absolute error.
+    pub itae: T,
+}
+
+/// Implementation of an accumulator of integral performance criteria, which compares
+/// tunings quantitatively by how much error they leave over time.
+///
+/// The criteria are accumulated since creation or the last reset, or over consecutive
+/// windows of a fixed number of steps.
+#[derive(Clone, Copy, Debug)]
+pub struct Performance<T> {
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: T,
+    /// Number of steps per window, `None` to accumulate until reset.
+    window: Option<u32>,
+    /// Number of steps accumulated in the current window.
+    steps: u32,
+
+    /// Criteria accumulated in the current window.
+    current: Criteria<T>,
+    /// Criteria of the last completed window.
+    completed: Option<Criteria<T>>,
+}
+
+impl<T: FloatCore> Performance<T> {
+    /// Creates a new `Performance` accumulating the criteria until it is reset.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sampling_time` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Performance;
+    ///
+    /// let mut performance = Performance::new(0.5);
+    /// for error in [2.0, -1.0, 0.0] {
+    ///     performance.step(error);
+    /// }
+    /// let criteria = performance.criteria();
+    /// assert_eq!(criteria.iae, 1.5);
+    /// assert_eq!(criteria.ise, 2.5);
+    /// assert_eq!(criteria.itae, 0.25);
+    /// ```
+    pub fn new(sampling_time: T) -> Self {
+        assert!(sampling_time > T::zero());
+        Self { sampling_time, window: None, steps: 0, current: zero(), completed: None }
+    }
+
+    /// Creates a new `Performance` accumulating the criteria over consecutive windows
+    /// of `window` steps, the time weighting restarting with each window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sampling_time` is not positive or `window` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Performance;
+    ///
+    /// let mut performance = Performance::with_window(1.0, 2);
+    /// for error in [1.0, 1.0, 3.0] {
+    ///     performance.step(error);
+    /// }
+    /// assert_eq!(performance.last_window().unwrap().iae, 2.0);
+    /// assert_eq!(performance.criteria().iae, 3.0);
+    /// ```
+    pub fn with_window(sampling_time: T, window: u32) -> Self {
+        assert!(window > 0);
+        Self { window: Some(window), ..Self::new(sampling_time) }
+    }
+
+    /// Returns the criteria accumulated since creation, the last reset or the start of
+    /// the current window.
+    pub fn criteria(&self) -> Criteria<T> {
+        self.current
+    }
+
+    /// Returns the criteria of the last completed window, `None` until one completes
+    /// or when not accumulating over windows.
+    pub fn last_window(&self) -> Option<Criteria<T>> {
+        self.completed
+    }
+
+    /// Returns the number of steps accumulated in the current criteria.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Clears the accumulated criteria and the last completed window.
+    pub fn reset(&mut self) {
+        self.steps = 0;
+        self.current = zero();
+        self.completed = None;
+    }
+
+    /// Accumulates the control `error` of a single step.
+    pub fn step(&mut self, error: T) {
+        if self.window == Some(self.steps) {
+            self.completed = Some(self.current);
+            self.steps = 0;
+            self.current = zero();
+        }
+
+        let time = T::from(self.steps).unwrap_or_else(T::infinity) * self.sampling_time;
+        let error = error.abs();
+        self.current.iae = self.current.iae + error * self.sampling_time;
+        self.current.ise = self.current.ise + error * error * self.sampling_time;
+        self.current.itae = self.current.itae + time * error * self.sampling_time;
+        self.steps = self.steps.saturating_add(1);
+    }
+}
+
+/// Returns criteria with nothing accumulated.
+fn zero<T: FloatCore>() -> Criteria<T> {
+    Criteria { iae: T::zero(), ise: T::zero(), itae: T::zero() }
+}
@@ -0,0 +1,162 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Summary of the values taken by a signal.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Summary<T> {
+    /// Mean of the values.
+    pub mean: T,
+    /// Population variance of the values.
+    pub variance: T,
+    /// Smallest value.
+    pub min: T,
+    /// Largest value.
+    pub max: T,
+}
+
+/// Snapshot of the statistics of a control loop.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Report<T> {
+    /// Statistics of the control error.
+    pub error: Summary<T>,
+    /// Statistics of the controller output.
+    pub output: Summary<T>,
+    /// Percentage of the steps for which the output was saturated.
+    pub saturated: T,
+    /// Number of steps accounted for.
+    pub steps: u32,
+}
+
+/// Implementation of running statistics of a control loop, cheap enough to be kept
+/// for every loop of a device and reported for health monitoring.
+#[derive(Clone, Copy, Debug)]
+pub struct Statistics<T> {
+    /// Running statistics of the control error.
+    error: Running<T>,
+    /// Running statistics of the controller output.
+    output: Running<T>,
+    /// Number of steps for which the output was saturated.
+    saturated: u32,
+    /// Number of steps accounted for.
+    steps: u32,
+}
+
+impl<T: FloatCore> Statistics<T> {
+    /// Creates a new `Statistics` with no step accounted for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Statistics;
+    /// use control::pid::{Saturation, PID};
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// pid.bound_output(0.0..=5.0);
+    /// let mut statistics = Statistics::new();
+    /// for measurement in [0.0, 6.0, 8.0, 10.0] {
+    ///     let output = pid.step(measurement);
+    ///     let saturated = pid.saturation() != Saturation::Unsaturated;
+    ///     statistics.step(pid.setpoint - measurement, output, saturated);
+    /// }
+    ///
+    /// let report = statistics.report();
+    /// assert_eq!(report.error.mean, 4.0);
+    /// assert_eq!(report.output.max, 5.0);
+    /// assert_eq!(report.saturated, 25.0);
+    /// ```
+    pub fn new() -> Self {
+        Self { error: Running::new(), output: Running::new(), saturated: 0, steps: 0 }
+    }
+
+    /// Discards all the steps accounted for.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Accounts for a single step of the loop, given its control `error`, the
+    /// controller `output` and whether that output was `saturated`. Steps beyond
+    /// `u32::MAX` are not accounted for.
+    pub fn step(&mut self, error: T, output: T, saturated: bool) {
+        if self.steps == u32::MAX {
+            return;
+        }
+        self.steps += 1;
+        if saturated {
+            self.saturated += 1;
+        }
+        self.error.step(error, self.steps);
+        self.output.step(output, self.steps);
+    }
+
+    /// Returns a snapshot of the statistics. All of them are zero if no step was
+    /// accounted for.
+    pub fn report(&self) -> Report<T> {
+        let saturated = if self.steps == 0 {
+            T::zero()
+        } else {
+            constant::hundred::<T>() * count(self.saturated) / count(self.steps)
+        };
+
+        Report {
+            error: self.error.summary(self.steps),
+            output: self.output.summary(self.steps),
+            saturated,
+            steps: self.steps,
+        }
+    }
+}
+
+impl<T: FloatCore> Default for Statistics<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Running mean, variance and extrema of a signal, using Welford's algorithm.
+#[derive(Clone, Copy, Debug)]
+struct Running<T> {
+    /// Mean of the values so far.
+    mean: T,
+    /// Sum of the squared differences to the mean.
+    m2: T,
+    /// Smallest value so far.
+    min: T,
+    /// Largest value so far.
+    max: T,
+}
+
+impl<T: FloatCore> Running<T> {
+    fn new() -> Self {
+        Self { mean: T::zero(), m2: T::zero(), min: T::infinity(), max: T::neg_infinity() }
+    }
+
+    /// Adds the `steps`-th value of the signal.
+    fn step(&mut self, value: T, steps: u32) {
+        let steps = count(steps);
+        let delta = value - self.mean;
+        self.mean = self.mean + delta / steps;
+        self.m2 = self.m2 + delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    fn summary(&self, steps: u32) -> Summary<T> {
+        if steps == 0 {
+            return Summary {
+                mean: T::zero(),
+                variance: T::zero(),
+                min: T::zero(),
+                max: T::zero(),
+            };
+        }
+        Summary { mean: self.mean, variance: self.m2 / count(steps), min: self.min, max: self.max }
+    }
+}
+
+/// Converts a step count to a number, infinite if the type cannot hold it.
+fn count<T: FloatCore>(steps: u32) -> T {
+    T::from(steps).unwrap_or_else(T::infinity)
+}
@@ -0,0 +1,82 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Metrics of a recorded closed-loop step response.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StepResponse<T> {
+    /// Time taken to go from 10% to 90% of the step, `None` if 90% was never reached.
+    pub rise_time: Option<T>,
+    /// Largest excursion beyond the setpoint, in percent of the step. Zero if the
+    /// response never went beyond the setpoint.
+    pub overshoot: T,
+    /// Time after which the response stays within the tolerance band around the
+    /// setpoint, `None` if the last sample is outside of it.
+    pub settling_time: Option<T>,
+    /// Difference between the setpoint and the last sample.
+    pub steady_state_error: T,
+}
+
+/// Analyzes a recorded closed-loop step response from the setpoint change, at the
+/// first sample, to the `setpoint`.
+///
+/// `sampling_time` is the time difference in seconds between two consecutive samples,
+/// which is also the resolution of the returned times. `tolerance` is the half width
+/// of the settling band, as a fraction of the step, such as `0.02` for the common 2%
+/// band.
+///
+/// Returns `None` if there are no samples or the first one is already at the setpoint.
+///
+/// # Examples
+///
+/// ```
+/// use control::diagnostics::step_response;
+///
+/// let samples = [0.0_f64, 0.3, 0.7, 1.0, 1.2, 1.1, 0.97, 1.01, 1.0, 1.0];
+/// let metrics = step_response(&samples, 0.5, 1.0, 0.05).unwrap();
+/// assert_eq!(metrics.rise_time, Some(1.0));
+/// assert!((metrics.overshoot - 20.0).abs() < 1e-9);
+/// assert_eq!(metrics.settling_time, Some(3.0));
+/// assert_eq!(metrics.steady_state_error, 0.0);
+/// ```
+pub fn step_response<T: FloatCore>(
+    samples: &[T],
+    sampling_time: T,
+    setpoint: T,
+    tolerance: T,
+) -> Option<StepResponse<T>> {
+    let first = *samples.first()?;
+    let last = *samples.last()?;
+    let step = setpoint - first;
+    if step == T::zero() {
+        return None;
+    }
+
+    // Progress of each sample towards the setpoint, zero at the start and one at the
+    // setpoint, whatever the direction of the step.
+    let progress = |sample: T| (sample - first) / step;
+    let time = |index: usize| T::from(index).unwrap_or_else(T::infinity) * sampling_time;
+
+    let ten = constant::ten::<T>();
+    let tenth = T::one() / ten;
+    let nine_tenths = (ten - T::one()) / ten;
+    let start = samples.iter().position(|&sample| progress(sample) >= tenth);
+    let end = samples.iter().position(|&sample| progress(sample) >= nine_tenths);
+    let rise_time = match (start, end) {
+        (Some(start), Some(end)) => Some(time(end - start)),
+        _ => None,
+    };
+
+    let peak = samples.iter().fold(T::zero(), |peak, &sample| peak.max(progress(sample)));
+    let overshoot = (peak - T::one()).max(T::zero()) * constant::hundred::<T>();
+
+    let outside = |sample: T| (progress(sample) - T::one()).abs() > tolerance;
+    let settling_time = match samples.iter().rposition(|&sample| outside(sample)) {
+        Some(index) if index + 1 == samples.len() => None,
+        Some(index) => Some(time(index + 1)),
+        None => Some(T::zero()),
+    };
+
+    Some(StepResponse { rise_time, overshoot, settling_time, steady_state_error: setpoint - last })
+}
@@ -0,0 +1,127 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a valve stiction diagnostic, which watches the controller output
+/// and the measurement for the pattern of a sticky valve: the output ramps while the
+/// measurement stays flat, until the valve breaks away and the measurement jumps.
+///
+/// The stiction index is the share of the output travel during which the measurement
+/// did not respond, close to zero for a healthy valve and close to one for a sticky
+/// one. It is best suited to fast loops such as flow, where the measurement follows
+/// the valve without much lag.
+#[derive(Clone, Copy, Debug)]
+pub struct Stiction<T> {
+    /// Largest change of the measurement considered as noise.
+    band: T,
+    /// Weight of the newest travel, between zero and one.
+    alpha: T,
+
+    /// Output and measurement of the previous step, `None` until the first step.
+    previous: Option<(T, T)>,
+    /// Output and measurement when the measurement last moved.
+    anchor: (T, T),
+    /// Output travel during which the measurement did not respond.
+    stuck: T,
+    /// Total output travel.
+    travel: T,
+    /// Average output travel needed for the measurement to move, `None` until it
+    /// first moves.
+    breakaway: Option<T>,
+}
+
+impl<T: FloatCore> Stiction<T> {
+    /// Creates a new `Stiction` diagnostic ignoring measurement changes within
+    /// `±band`, `alpha` being the weight of the newest steps in the index, between
+    /// zero and one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `band` is negative or `alpha` is not in the `(0, 1]` interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Stiction;
+    ///
+    /// let mut diagnostic = Stiction::new(0.1, 0.05);
+    /// // The output ramps up and down by 0.5 per step, the valve only moves once it
+    /// // is 2 away from its position.
+    /// let mut position = 0.0_f64;
+    /// for k in 0..200 {
+    ///     let output = 0.5 * (k % 80).min(80 - k % 80) as f64;
+    ///     if (output - position).abs() >= 2.0 {
+    ///         position = output;
+    ///     }
+    ///     diagnostic.step(output, 2.0 * position);
+    /// }
+    /// assert!(diagnostic.index() > 0.7);
+    /// assert_eq!(diagnostic.breakaway(), Some(2.0));
+    /// ```
+    pub fn new(band: T, alpha: T) -> Self {
+        assert!(band >= T::zero());
+        assert!(alpha > T::zero() && alpha <= T::one());
+        Self {
+            band,
+            alpha,
+            previous: None,
+            anchor: (T::zero(), T::zero()),
+            stuck: T::zero(),
+            travel: T::zero(),
+            breakaway: None,
+        }
+    }
+
+    /// Returns the stiction index, between zero and one.
+    pub fn index(&self) -> T {
+        if self.travel > T::zero() {
+            self.stuck / self.travel
+        } else {
+            T::zero()
+        }
+    }
+
+    /// Returns the average output travel needed for the measurement to move, an
+    /// estimate of the dead band plus stick band of the valve. `None` until the
+    /// measurement first moves.
+    pub fn breakaway(&self) -> Option<T> {
+        self.breakaway
+    }
+
+    /// Clears the internal state.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.stuck = T::zero();
+        self.travel = T::zero();
+        self.breakaway = None;
+    }
+
+    /// Monitors the controller `output` and the `measurement` of a single step.
+    pub fn step(&mut self, output: T, measurement: T) {
+        let (previous_output, _) = match self.previous {
+            Some(previous) => previous,
+            None => {
+                self.previous = Some((output, measurement));
+                self.anchor = (output, measurement);
+                return;
+            }
+        };
+        self.previous = Some((output, measurement));
+
+        let step = (output - previous_output).abs();
+        let forget = T::one() - self.alpha;
+        self.travel = forget * self.travel + self.alpha * step;
+
+        let (anchor_output, anchor_measurement) = self.anchor;
+        if (measurement - anchor_measurement).abs() > self.band {
+            // The valve moved, the output travel since it last moved broke it away.
+            let breakaway = (output - anchor_output).abs();
+            self.breakaway = Some(match self.breakaway {
+                Some(average) => forget * average + self.alpha * breakaway,
+                None => breakaway,
+            });
+            self.stuck = forget * self.stuck;
+            self.anchor = (output, measurement);
+        } else {
+            self.stuck = forget * self.stuck + self.alpha * step;
+        }
+    }
+}
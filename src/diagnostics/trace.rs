@@ -0,0 +1,90 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::{StepObserver, StepRecord};
+
+/// Implementation of a trace of the last `N` steps of a controller, kept in a ring
+/// buffer so it can be dumped over a debug link after a fault.
+#[derive(Clone, Copy, Debug)]
+pub struct Trace<T, const N: usize> {
+    /// Recorded steps, the oldest one at `index` once the buffer is full.
+    records: [Option<StepRecord<T>>; N],
+    /// Position of the next record in the buffer.
+    index: usize,
+}
+
+impl<T: FloatCore, const N: usize> Trace<T, N> {
+    /// Creates a new, empty, `Trace`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::diagnostics::Trace;
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// let mut trace = Trace::<f32, 2>::new();
+    /// for measurement in [1.0, 2.0, 3.0] {
+    ///     pid.step(measurement);
+    ///     trace.push(pid.record());
+    /// }
+    ///
+    /// let measurements: Vec<f32> = trace.iter().map(|record| record.measurement).collect();
+    /// assert_eq!(measurements, [2.0, 3.0]);
+    /// ```
+    pub fn new() -> Self {
+        assert!(N > 0);
+        Self { records: [None; N], index: 0 }
+    }
+
+    /// Returns the number of recorded steps.
+    pub fn len(&self) -> usize {
+        match self.records[self.index] {
+            Some(_) => N,
+            None => self.index,
+        }
+    }
+
+    /// Returns whether no step was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records[0].is_none()
+    }
+
+    /// Returns the last recorded step.
+    pub fn latest(&self) -> Option<&StepRecord<T>> {
+        self.records[(self.index + N - 1) % N].as_ref()
+    }
+
+    /// Discards all the recorded steps.
+    pub fn clear(&mut self) {
+        self.records = [None; N];
+        self.index = 0;
+    }
+
+    /// Records a step, discarding the oldest one if the trace is full.
+    pub fn push(&mut self, record: StepRecord<T>) {
+        self.records[self.index] = Some(record);
+        self.index = (self.index + 1) % N;
+    }
+
+    /// Returns an iterator over the recorded steps, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &StepRecord<T>> {
+        let (newest, oldest) = self.records.split_at(self.index);
+        oldest.iter().chain(newest).flatten()
+    }
+}
+
+impl<T: FloatCore, const N: usize> Default for Trace<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FloatCore, const N: usize> StepObserver<T> for Trace<T, N> {
+    fn on_step(&mut self, record: &StepRecord<T>) {
+        self.push(*record);
+    }
+}
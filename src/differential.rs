@@ -0,0 +1,96 @@
+use num_traits::float::FloatCore;
+
+use crate::controller::Controller;
+
+/// How the controlled variable is obtained from two measurements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Combination {
+    /// The first measurement minus the second, such as the pressure drop across a
+    /// filter.
+    Difference,
+    /// The first measurement divided by the second, such as the mixing ratio of two
+    /// flows.
+    Ratio,
+}
+
+/// Implementation of a differential input, where the controlled variable is the
+/// difference or the ratio of two measurements from separate sensors.
+///
+/// Both raw measurements of the last step are kept alongside the variable, so the
+/// diagnostics can tell a drifting sensor from a real change of the process.
+#[derive(Clone, Copy, Debug)]
+pub struct Differential<C, T> {
+    /// Controller stepping on the controlled variable.
+    pub controller: C,
+
+    /// How the measurements are combined.
+    combination: Combination,
+    /// First measurement of the last step.
+    first: T,
+    /// Second measurement of the last step.
+    second: T,
+    /// Controlled variable of the last step.
+    variable: T,
+}
+
+impl<C: Controller<T>, T: FloatCore> Differential<C, T> {
+    /// Creates a new `Differential` stepping `controller` on the `combination` of two
+    /// measurements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::differential::{Combination, Differential};
+    /// use control::pid::PID;
+    ///
+    /// // Keep 20 mbar across a filter, measured by sensors on both sides.
+    /// let pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.1, 20.0);
+    /// let mut fan = Differential::new(pid, Combination::Difference);
+    /// assert_eq!(fan.step(1030.0, 1015.0), 10.0);
+    /// assert_eq!(fan.measurements(), (1030.0, 1015.0));
+    /// assert_eq!(fan.variable(), 15.0);
+    /// ```
+    pub fn new(controller: C, combination: Combination) -> Self {
+        Self { controller, combination, first: T::zero(), second: T::zero(), variable: T::zero() }
+    }
+
+    /// Returns how the measurements are combined.
+    pub fn combination(&self) -> Combination {
+        self.combination
+    }
+
+    /// Returns the two measurements of the last step.
+    pub fn measurements(&self) -> (T, T) {
+        (self.first, self.second)
+    }
+
+    /// Returns the controlled variable of the last step.
+    pub fn variable(&self) -> T {
+        self.variable
+    }
+
+    /// Performs a single step of the control loop on the combination of `first` and
+    /// `second` and returns the output.
+    ///
+    /// For a ratio, a zero `second` measurement keeps the variable of the previous
+    /// step instead of feeding an infinite one to the controller.
+    pub fn step(&mut self, first: T, second: T) -> T {
+        self.first = first;
+        self.second = second;
+        self.variable = match self.combination {
+            Combination::Difference => first - second,
+            Combination::Ratio if second == T::zero() => self.variable,
+            Combination::Ratio => first / second,
+        };
+        self.controller.step(self.variable)
+    }
+
+    /// Clears the controller state and the measurements.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+        self.first = T::zero();
+        self.second = T::zero();
+        self.variable = T::zero();
+    }
+}
@@ -0,0 +1,12 @@
+//! Feedforward blocks, whose output is meant to be passed to
+//! [`PID::step_with_feedforward`](crate::pid::PID::step_with_feedforward).
+
+mod friction;
+mod gravity;
+mod lead_lag;
+mod motion;
+
+pub use self::friction::Friction;
+pub use self::gravity::{Gravity, Load};
+pub use self::lead_lag::LeadLag;
+pub use self::motion::Motion;
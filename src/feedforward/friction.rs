@@ -0,0 +1,78 @@
+use num_traits::float::{Float, FloatCore};
+
+/// Implementation of a friction feedforward model, which computes the torque needed
+/// to overcome Coulomb, viscous and, optionally, Stribeck friction at the commanded
+/// velocity.
+///
+/// The sign of the velocity is smoothed over a small band around zero, so the
+/// compensation does not chatter when the commanded velocity crosses zero.
+#[derive(Clone, Copy, Debug)]
+pub struct Friction<T> {
+    /// Coulomb friction torque.
+    coulomb: T,
+    /// Viscous friction coefficient, torque per unit of velocity.
+    viscous: T,
+    /// Static friction torque and Stribeck velocity.
+    stribeck: Option<(T, T)>,
+    /// Velocity below which the sign of the velocity is smoothed.
+    band: T,
+}
+
+impl<T: Float + FloatCore> Friction<T> {
+    /// Creates a new `Friction` model with a Coulomb torque of `coulomb` and a viscous
+    /// coefficient of `viscous`, smoothing the sign of the velocity for velocities
+    /// below `band`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `band` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::feedforward::Friction;
+    ///
+    /// let friction = Friction::new(0.5, 0.01, 0.1);
+    /// assert_eq!(friction.step(-10.0), -0.6);
+    /// // Half of the Coulomb torque at half of the smoothing band.
+    /// assert_eq!(friction.step(0.05), 0.2505);
+    /// ```
+    pub fn new(coulomb: T, viscous: T, band: T) -> Self {
+        assert!(band >= T::zero());
+        Self { coulomb, viscous, stribeck: None, band }
+    }
+
+    /// Adds a Stribeck term, where the friction torque rises to `breakaway` at rest
+    /// and decays towards the Coulomb torque with a characteristic velocity of
+    /// `velocity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `velocity` is not positive.
+    pub fn stribeck(&mut self, breakaway: T, velocity: T) -> &mut Self {
+        assert!(velocity > T::zero());
+        self.stribeck = Some((breakaway, velocity));
+        self
+    }
+
+    /// Computes the compensation torque for the commanded `velocity`.
+    pub fn step(&self, velocity: T) -> T {
+        let sign = if Float::abs(velocity) < self.band {
+            velocity / self.band
+        } else if velocity == T::zero() {
+            T::zero()
+        } else {
+            Float::signum(velocity)
+        };
+
+        let static_torque = match self.stribeck {
+            Some((breakaway, scale)) => {
+                let ratio = velocity / scale;
+                self.coulomb + (breakaway - self.coulomb) * Float::exp(-ratio * ratio)
+            }
+            None => self.coulomb,
+        };
+
+        sign * static_torque + self.viscous * velocity
+    }
+}
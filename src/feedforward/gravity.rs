@@ -0,0 +1,71 @@
+use num_traits::float::FloatCore;
+
+use crate::lookup::Table;
+
+/// A model of a position dependent load, such as gravity acting on an arm or a spring
+/// acting on a lift.
+///
+/// It is implemented for closures of the position and for breakpoint tables.
+pub trait Load<T> {
+    /// Returns the output needed to hold the load at `position`.
+    fn load(&self, position: T) -> T;
+}
+
+impl<T, F: Fn(T) -> T> Load<T> for F {
+    fn load(&self, position: T) -> T {
+        self(position)
+    }
+}
+
+impl<T: FloatCore, const N: usize> Load<T> for Table<T, N> {
+    fn load(&self, position: T) -> T {
+        self.interpolate(position)
+    }
+}
+
+/// Implementation of a position dependent feedforward, which cancels a known load so
+/// the controller only has to handle the residual dynamics.
+///
+/// Passing the result to
+/// [`PID::step_with_feedforward`](crate::pid::PID::step_with_feedforward) applies it
+/// before the output is clamped.
+#[derive(Clone, Copy, Debug)]
+pub struct Gravity<M> {
+    /// Model of the load.
+    model: M,
+}
+
+impl<M> Gravity<M> {
+    /// Creates a new `Gravity` feedforward from a model of the load.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::feedforward::Gravity;
+    /// use control::lookup::Table;
+    ///
+    /// // Holding torque of an arm, from a closure of its angle in radians.
+    /// let arm = Gravity::new(|angle: f64| 2.5 * angle.cos());
+    /// assert_eq!(arm.step(0.0), 2.5);
+    ///
+    /// // Holding force of a spring loaded lift, from a measured table.
+    /// let lift = Gravity::new(Table::new([0.0, 0.1, 0.2], [4.0, 6.0, 9.0]));
+    /// assert_eq!(lift.step(0.15), 7.5);
+    /// ```
+    pub fn new(model: M) -> Self {
+        Self { model }
+    }
+
+    /// Returns the model of the load.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Computes the feedforward term for the measured `position`.
+    pub fn step<T>(&self, position: T) -> T
+    where
+        M: Load<T>,
+    {
+        self.model.load(position)
+    }
+}
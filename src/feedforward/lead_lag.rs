@@ -0,0 +1,130 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of a feedforward block acting on a measured disturbance, with a
+/// static gain and lead-lag dynamics
+///
+/// ```text
+///            lead * s + 1
+/// H(s) = k * ------------
+///             lag * s + 1
+/// ```
+///
+/// discretized with the bilinear transform.
+#[derive(Clone, Copy, Debug)]
+pub struct LeadLag<T> {
+    /// Static gain.
+    k: T,
+    /// Lead time constant in seconds.
+    lead: T,
+    /// Lag time constant in seconds.
+    lag: T,
+    /// Time difference in seconds between two consecutive step operations.
+    sampling_time: T,
+
+    /// Coefficients of the discretized transfer function, normalized so that `a0` is one.
+    b0: T,
+    b1: T,
+    a1: T,
+
+    /// Input of the previous step.
+    input: T,
+    /// Output of the previous step, `None` until the first sample arrives.
+    output: Option<T>,
+}
+
+impl<T: FloatCore> LeadLag<T> {
+    /// Creates a new `LeadLag` with a static gain of `k`, a lead time constant of
+    /// `lead` seconds and a lag time constant of `lag` seconds.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lead` or `lag` are negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::feedforward::LeadLag;
+    /// use control::pid::PID;
+    ///
+    /// // Compensate a drop of the inlet temperature before the outlet notices it.
+    /// let mut inlet = LeadLag::new(-0.8, 5.0, 20.0, 1.0);
+    /// let mut pid = PID::new(2.0, 0.1, 0.0, 0.0, 1.0, 70.0);
+    ///
+    /// let feedforward = inlet.step(15.0);
+    /// let output = pid.step_with_feedforward(70.0, feedforward);
+    /// assert_eq!(output, -12.0);
+    /// ```
+    pub fn new(k: T, lead: T, lag: T, sampling_time: T) -> Self {
+        let mut block = Self {
+            k,
+            lead,
+            lag,
+            sampling_time,
+
+            b0: T::zero(),
+            b1: T::zero(),
+            a1: T::zero(),
+
+            input: T::zero(),
+            output: None,
+        };
+        block.update();
+        block
+    }
+
+    /// Changes the static gain, keeping the block state.
+    pub fn set_gain(&mut self, k: T) -> &mut Self {
+        self.k = k;
+        self.update();
+        self
+    }
+
+    /// Changes the lead and lag time constants, keeping the block state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lead` or `lag` are negative.
+    pub fn set_time_constants(&mut self, lead: T, lag: T) -> &mut Self {
+        self.lead = lead;
+        self.lag = lag;
+        self.update();
+        self
+    }
+
+    /// Clears the block state, the next sample is taken as a steady state.
+    pub fn reset(&mut self) {
+        self.input = T::zero();
+        self.output = None;
+    }
+
+    /// Computes the feedforward term for a new disturbance measurement.
+    pub fn step(&mut self, input: T) -> T {
+        let output = match self.output {
+            Some(previous) => self.b0 * input + self.b1 * self.input - self.a1 * previous,
+            // Start in steady state to avoid a bump on the first sample.
+            None => self.k * input,
+        };
+        self.input = input;
+        self.output = Some(output);
+        output
+    }
+
+    /// Recomputes the coefficients of the discretized transfer function.
+    fn update(&mut self) {
+        assert!(self.lead >= T::zero() && self.lag >= T::zero());
+
+        let two = constant::two::<T>();
+        let lead = two * self.lead / self.sampling_time;
+        let lag = two * self.lag / self.sampling_time;
+        let a0 = lag + T::one();
+
+        self.b0 = self.k * (lead + T::one()) / a0;
+        self.b1 = self.k * (T::one() - lead) / a0;
+        self.a1 = (T::one() - lag) / a0;
+    }
+}
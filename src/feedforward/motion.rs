@@ -0,0 +1,52 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of velocity and acceleration feedforward for motion loops, which
+/// computes `kv * velocity + ka * acceleration` from the reference trajectory.
+///
+/// Passing the result to
+/// [`PID::step_with_feedforward`](crate::pid::PID::step_with_feedforward) keeps it
+/// within the output bounds and accounted for by the anti-windup.
+#[derive(Clone, Copy, Debug)]
+pub struct Motion<T> {
+    /// Velocity feedforward gain.
+    kv: T,
+    /// Acceleration feedforward gain.
+    ka: T,
+}
+
+impl<T: FloatCore> Motion<T> {
+    /// Creates a new `Motion` feedforward with a velocity gain of `kv` and an
+    /// acceleration gain of `ka`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::feedforward::Motion;
+    /// use control::pid::PID;
+    ///
+    /// let motion = Motion::new(0.2, 0.01);
+    /// let mut pid = PID::new(5.0, 0.0, 0.0, 0.0, 0.001, 1.0);
+    /// pid.bound_output(-12.0..=12.0);
+    ///
+    /// // Reference position, velocity and acceleration from a motion profile.
+    /// let (position, velocity, acceleration) = (1.0, 40.0, 200.0);
+    /// pid.setpoint = position;
+    /// let output = pid.step_with_feedforward(1.0, motion.step(velocity, acceleration));
+    /// assert_eq!(output, 10.0);
+    /// ```
+    pub fn new(kv: T, ka: T) -> Self {
+        Self { kv, ka }
+    }
+
+    /// Changes the feedforward gains.
+    pub fn set_gains(&mut self, kv: T, ka: T) -> &mut Self {
+        self.kv = kv;
+        self.ka = ka;
+        self
+    }
+
+    /// Computes the feedforward term for the reference `velocity` and `acceleration`.
+    pub fn step(&self, velocity: T, acceleration: T) -> T {
+        self.kv * velocity + self.ka * acceleration
+    }
+}
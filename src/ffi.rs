@@ -0,0 +1,211 @@
+//! A C API to the [`PID`] controller, so existing C firmware can adopt it one loop at
+//! a time.
+//!
+//! Controllers are handed out as opaque pointers and use `float` values. The
+//! signatures only use C types, so a header can be generated with `cbindgen`.
+
+use alloc::boxed::Box;
+
+use crate::pid::{Saturation, PID};
+
+/// Opaque handle to a controller created by [`control_pid_new`].
+pub struct ControlPid {
+    /// Wrapped controller.
+    controller: PID<f32>,
+}
+
+/// Snapshot of the last step of a controller, see [`StepRecord`](crate::pid::StepRecord).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ControlPidSnapshot {
+    /// Setpoint of the step.
+    pub setpoint: f32,
+    /// Measurement of the step.
+    pub measurement: f32,
+    /// Proportional term.
+    pub proportional: f32,
+    /// Integral term.
+    pub integral: f32,
+    /// Derivative term.
+    pub derivative: f32,
+    /// Feedforward term.
+    pub feedforward: f32,
+    /// Controller output.
+    pub output: f32,
+    /// Whether the output was clamped: -1 to its lower bound, 1 to its upper bound, 0
+    /// otherwise.
+    pub saturation: i8,
+    /// Whether the controller was in manual mode.
+    pub manual: bool,
+}
+
+/// Creates a new controller, see [`PID::new`], to be freed with [`control_pid_free`].
+///
+/// Invalid arguments panic, which aborts since a panic cannot unwind into C.
+///
+/// # Examples
+///
+/// ```
+/// use control::ffi::*;
+///
+/// unsafe {
+///     let pid = control_pid_new(2.0, 0.0, 0.0, 0.0, 0.01, 5.0);
+///     control_pid_bound_output(pid, 0.0, 4.0);
+///     assert_eq!(control_pid_step(pid, 0.0), 4.0);
+///
+///     control_pid_set_gains(pid, 0.5, 0.0, 0.0);
+///     assert_eq!(control_pid_step(pid, 1.0), 2.0);
+///
+///     let snapshot = control_pid_snapshot(pid);
+///     assert_eq!((snapshot.measurement, snapshot.saturation), (1.0, 0));
+///     control_pid_free(pid);
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn control_pid_new(
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    tau: f32,
+    sampling_time: f32,
+    setpoint: f32,
+) -> *mut ControlPid {
+    let controller = PID::new(kp, ki, kd, tau, sampling_time, setpoint);
+    Box::into_raw(Box::new(ControlPid { controller }))
+}
+
+/// Frees a controller. Does nothing if `pid` is null.
+///
+/// # Safety
+///
+/// `pid` must be null or a handle returned by [`control_pid_new`] not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_free(pid: *mut ControlPid) {
+    if !pid.is_null() {
+        drop(Box::from_raw(pid));
+    }
+}
+
+/// Performs a single step of the control loop and returns the output, see
+/// [`PID::step`].
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_step(pid: *mut ControlPid, measurement: f32) -> f32 {
+    (*pid).controller.step(measurement)
+}
+
+/// Performs a single step of the control loop with a feedforward term and returns the
+/// output, see [`PID::step_with_feedforward`].
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_step_with_feedforward(
+    pid: *mut ControlPid,
+    measurement: f32,
+    feedforward: f32,
+) -> f32 {
+    (*pid).controller.step_with_feedforward(measurement, feedforward)
+}
+
+/// Changes the setpoint.
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_set_setpoint(pid: *mut ControlPid, setpoint: f32) {
+    (*pid).controller.setpoint = setpoint;
+}
+
+/// Changes the gains, see [`PID::set_gains`].
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_set_gains(pid: *mut ControlPid, kp: f32, ki: f32, kd: f32) {
+    (*pid).controller.set_gains(kp, ki, kd);
+}
+
+/// Restricts the output to the `[min, max]` interval, see [`PID::bound_output`].
+///
+/// Panics, which aborts since a panic cannot unwind into C, if `min` > `max`.
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_bound_output(pid: *mut ControlPid, min: f32, max: f32) {
+    (*pid).controller.bound_output(min..=max);
+}
+
+/// Switches the controller to manual mode with the given output, see
+/// [`PID::set_manual`].
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_set_manual(pid: *mut ControlPid, output: f32) {
+    (*pid).controller.set_manual(output);
+}
+
+/// Switches the controller back to automatic mode.
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_set_automatic(pid: *mut ControlPid) {
+    (*pid).controller.set_automatic();
+}
+
+/// Clears the controller state, see [`PID::reset`].
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_reset(pid: *mut ControlPid) {
+    (*pid).controller.reset();
+}
+
+/// Returns a snapshot of the last step.
+///
+/// # Safety
+///
+/// `pid` must be a handle returned by [`control_pid_new`] not freed yet, and not used
+/// concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn control_pid_snapshot(pid: *const ControlPid) -> ControlPidSnapshot {
+    let record = (*pid).controller.record();
+    let saturation = match record.saturation {
+        Saturation::Lower => -1,
+        Saturation::Unsaturated => 0,
+        Saturation::Upper => 1,
+    };
+    ControlPidSnapshot {
+        setpoint: record.setpoint,
+        measurement: record.measurement,
+        proportional: record.proportional,
+        integral: record.integral,
+        derivative: record.derivative,
+        feedforward: record.feedforward,
+        output: record.output,
+        saturation,
+        manual: record.manual,
+    }
+}
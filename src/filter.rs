@@ -0,0 +1,29 @@
+//! Filters for measurement conditioning and for building custom compensators.
+
+mod alpha_beta;
+mod biquad;
+mod decimator;
+mod delay;
+mod exponential;
+mod median;
+mod moving_average;
+mod notch;
+mod outlier;
+mod savitzky_golay;
+mod slew;
+mod square_root;
+
+pub use self::alpha_beta::AlphaBeta;
+pub use self::biquad::Biquad;
+pub use self::decimator::Decimator;
+pub use self::delay::Delay;
+pub use self::exponential::Exponential;
+pub use self::median::Median;
+pub use self::moving_average::MovingAverage;
+pub use self::notch::Notch;
+pub use self::outlier::OutlierGate;
+pub use self::savitzky_golay::SavitzkyGolay;
+pub use self::slew::SlewLimiter;
+pub use self::square_root::SquareRoot;
+
+pub(crate) use self::exponential::pole;
@@ -0,0 +1,99 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of an [alpha-beta filter], which tracks the position and velocity
+/// of a signal from noisy position measurements.
+///
+/// [alpha-beta filter]: https://en.wikipedia.org/wiki/Alpha_beta_filter
+#[derive(Clone, Copy, Debug)]
+pub struct AlphaBeta<T> {
+    /// Position correction gain.
+    alpha: T,
+    /// Velocity correction gain.
+    beta: T,
+    /// Time difference in seconds between two consecutive step operations.
+    sampling_time: T,
+
+    /// Position estimate.
+    position: T,
+    /// Velocity estimate, in units per second.
+    velocity: T,
+    /// Whether the first measurement has been received.
+    initialized: bool,
+}
+
+impl<T: FloatCore> AlphaBeta<T> {
+    /// Creates a new `AlphaBeta` with a position gain of `alpha` and a velocity gain
+    /// of `beta`.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gains do not result in a stable filter, that is, unless
+    /// `0 < alpha <= 1` and `0 < beta < 4 - 2 * alpha`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::AlphaBeta;
+    /// use control::pid::PID;
+    ///
+    /// let mut tracker = AlphaBeta::new(0.5, 0.1, 0.01);
+    /// let mut pid = PID::new(1.0, 0.1, 0.05, 0.0, 0.01, 10.0);
+    ///
+    /// let position = tracker.step(2.0);
+    /// let output = pid.step_with_rate(position, tracker.velocity());
+    /// assert!(output > 0.0);
+    /// ```
+    pub fn new(alpha: T, beta: T, sampling_time: T) -> Self {
+        let two = constant::two::<T>();
+        assert!(alpha > T::zero() && alpha <= T::one());
+        assert!(beta > T::zero() && beta < two * (two - alpha));
+
+        Self {
+            alpha,
+            beta,
+            sampling_time,
+
+            position: T::zero(),
+            velocity: T::zero(),
+            initialized: false,
+        }
+    }
+
+    /// Returns the current position estimate.
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    /// Returns the current velocity estimate, in units per second.
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    /// Clears the estimates, the next measurement is taken as the position.
+    pub fn reset(&mut self) {
+        self.position = T::zero();
+        self.velocity = T::zero();
+        self.initialized = false;
+    }
+
+    /// Updates the estimates with a new measurement and returns the position estimate.
+    pub fn step(&mut self, measurement: T) -> T {
+        if !self.initialized {
+            self.position = measurement;
+            self.initialized = true;
+            return self.position;
+        }
+
+        let predicted = self.position + self.velocity * self.sampling_time;
+        let residual = measurement - predicted;
+
+        self.position = predicted + self.alpha * residual;
+        self.velocity = self.velocity + self.beta * residual / self.sampling_time;
+        self.position
+    }
+}
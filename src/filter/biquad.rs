@@ -0,0 +1,176 @@
+use num_traits::float::{Float, FloatCore};
+
+use crate::constant;
+
+/// Implementation of a second-order IIR filter in transposed direct form II.
+#[derive(Clone, Copy, Debug)]
+pub struct Biquad<T> {
+    /// Feedforward coefficients.
+    b0: T,
+    b1: T,
+    b2: T,
+    /// Feedback coefficients, normalized so that `a0` is one.
+    a1: T,
+    a2: T,
+
+    /// Internal state of the filter.
+    s1: T,
+    s2: T,
+}
+
+impl<T: FloatCore> Biquad<T> {
+    /// Creates a new `Biquad` from its transfer function coefficients
+    ///
+    /// ```text
+    ///        b0 + b1*z^-1 + b2*z^-2
+    /// H(z) = ----------------------
+    ///        a0 + a1*z^-1 + a2*z^-2
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a0` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Biquad;
+    ///
+    /// // A pure one sample delay.
+    /// let mut delay = Biquad::new(0.0, 1.0, 0.0, 1.0, 0.0, 0.0);
+    /// assert_eq!(delay.step(1.0), 0.0);
+    /// assert_eq!(delay.step(0.0), 1.0);
+    /// ```
+    pub fn new(b0: T, b1: T, b2: T, a0: T, a1: T, a2: T) -> Self {
+        let mut filter = Self {
+            b0: T::zero(),
+            b1: T::zero(),
+            b2: T::zero(),
+            a1: T::zero(),
+            a2: T::zero(),
+
+            s1: T::zero(),
+            s2: T::zero(),
+        };
+        filter.set_coefficients(b0, b1, b2, a0, a1, a2);
+        filter
+    }
+
+    /// Replaces the transfer function coefficients while keeping the internal state,
+    /// which allows the filter to be retuned at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a0` is zero.
+    pub fn set_coefficients(&mut self, b0: T, b1: T, b2: T, a0: T, a1: T, a2: T) -> &mut Self {
+        assert!(a0 != T::zero());
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+        self
+    }
+
+    /// Clears the internal state of the filter.
+    pub fn reset(&mut self) {
+        self.s1 = T::zero();
+        self.s2 = T::zero();
+    }
+
+    /// Filters a single input sample.
+    pub fn step(&mut self, input: T) -> T {
+        let output = self.b0 * input + self.s1;
+        self.s1 = self.b1 * input - self.a1 * output + self.s2;
+        self.s2 = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+impl<T: Float + FloatCore> Biquad<T> {
+    /// Creates a Butterworth low-pass filter with a cutoff frequency of `cutoff` hertz.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Biquad;
+    ///
+    /// let mut filter = Biquad::low_pass(10.0, 0.001);
+    /// let mut output = 0.0;
+    /// for _ in 0..1000 {
+    ///     output = filter.step(1.0);
+    /// }
+    /// assert!((output - 1.0_f64).abs() < 1e-9);
+    /// ```
+    pub fn low_pass(cutoff: T, sampling_time: T) -> Self {
+        Self::low_pass_with_q(cutoff, sampling_time, butterworth_q())
+    }
+
+    /// Creates a low-pass filter with a cutoff frequency of `cutoff` hertz and a
+    /// quality factor of `q`.
+    pub fn low_pass_with_q(cutoff: T, sampling_time: T, q: T) -> Self {
+        let (alpha, cos) = intermediates(cutoff, sampling_time, q);
+        let one = T::one();
+        let two = one + one;
+        let b1 = one - cos;
+        let b0 = b1 / two;
+        Self::new(b0, b1, b0, one + alpha, -two * cos, one - alpha)
+    }
+
+    /// Creates a Butterworth high-pass filter with a cutoff frequency of `cutoff` hertz.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    pub fn high_pass(cutoff: T, sampling_time: T) -> Self {
+        Self::high_pass_with_q(cutoff, sampling_time, butterworth_q())
+    }
+
+    /// Creates a high-pass filter with a cutoff frequency of `cutoff` hertz and a
+    /// quality factor of `q`.
+    pub fn high_pass_with_q(cutoff: T, sampling_time: T, q: T) -> Self {
+        let (alpha, cos) = intermediates(cutoff, sampling_time, q);
+        let one = T::one();
+        let two = one + one;
+        let b1 = -(one + cos);
+        let b0 = -b1 / two;
+        Self::new(b0, b1, b0, one + alpha, -two * cos, one - alpha)
+    }
+
+    /// Creates a band-pass filter centered at `center` hertz, with unity gain at the
+    /// center frequency and a Butterworth quality factor.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    pub fn band_pass(center: T, sampling_time: T) -> Self {
+        Self::band_pass_with_q(center, sampling_time, butterworth_q())
+    }
+
+    /// Creates a band-pass filter centered at `center` hertz, with unity gain at the
+    /// center frequency and a quality factor of `q`.
+    pub fn band_pass_with_q(center: T, sampling_time: T, q: T) -> Self {
+        let (alpha, cos) = intermediates(center, sampling_time, q);
+        let one = T::one();
+        let two = one + one;
+        Self::new(alpha, T::zero(), -alpha, one + alpha, -two * cos, one - alpha)
+    }
+}
+
+/// Quality factor of a second-order Butterworth filter, `1 / sqrt(2)`.
+fn butterworth_q<T: Float>() -> T {
+    T::from(core::f64::consts::FRAC_1_SQRT_2).expect("Unable to cast from 1/sqrt(2)")
+}
+
+/// Computes the `alpha` and `cos(w0)` intermediate values shared by the filter designs
+/// of the [Audio EQ Cookbook].
+///
+/// [Audio EQ Cookbook]: https://www.w3.org/TR/audio-eq-cookbook/
+pub(crate) fn intermediates<T: Float>(frequency: T, sampling_time: T, q: T) -> (T, T) {
+    let two = constant::two::<T>();
+    let pi = T::from(core::f64::consts::PI).expect("Unable to cast from pi");
+
+    let w0 = two * pi * frequency * sampling_time;
+    (w0.sin() / (two * q), w0.cos())
+}
@@ -0,0 +1,97 @@
+use num_traits::float::{Float, FloatCore};
+
+use super::biquad::Biquad;
+use crate::constant;
+
+/// Implementation of a decimator, which low-pass filters a fast signal and passes on
+/// one every `ratio` samples, so noise above the slower rate does not alias into it.
+///
+/// The anti-aliasing filter is a fourth order Butterworth low-pass, built from two
+/// second-order sections.
+#[derive(Clone, Copy, Debug)]
+pub struct Decimator<T> {
+    /// Anti-aliasing sections.
+    sections: [Biquad<T>; 2],
+    /// Number of input samples per output sample.
+    ratio: u32,
+    /// Number of input samples since the last output sample.
+    count: u32,
+}
+
+impl<T: Float + FloatCore> Decimator<T> {
+    /// Creates a new `Decimator` passing on one every `ratio` samples, with the
+    /// anti-aliasing cutoff at half of the output Nyquist frequency.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// input samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Decimator;
+    ///
+    /// // Sampling at 1 kHz, controlling at 20 Hz.
+    /// let mut decimator = Decimator::new(50, 0.001);
+    /// let outputs = (0..100).filter_map(|_| decimator.step(1.0)).count();
+    /// assert_eq!(outputs, 2);
+    /// ```
+    pub fn new(ratio: u32, sampling_time: T) -> Self {
+        assert!(ratio > 0);
+        let four = constant::four::<T>();
+        let ratio_t = T::from(ratio).expect("Unable to cast the ratio");
+        Self::with_cutoff(ratio, sampling_time, T::one() / (four * ratio_t * sampling_time))
+    }
+
+    /// Creates a new `Decimator` passing on one every `ratio` samples, with the
+    /// anti-aliasing cutoff at `cutoff` hertz.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// input samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    pub fn with_cutoff(ratio: u32, sampling_time: T, cutoff: T) -> Self {
+        assert!(ratio > 0);
+        // Quality factors of the sections of a fourth order Butterworth filter.
+        let q1 = T::from(0.541_196_1_f64).expect("Unable to cast quality factor");
+        let q2 = T::from(1.306_563_f64).expect("Unable to cast quality factor");
+
+        Self {
+            sections: [
+                Biquad::low_pass_with_q(cutoff, sampling_time, q1),
+                Biquad::low_pass_with_q(cutoff, sampling_time, q2),
+            ],
+            ratio,
+            count: 0,
+        }
+    }
+}
+
+impl<T: FloatCore> Decimator<T> {
+    /// Clears the filter state and restarts the sample counting.
+    pub fn reset(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.reset();
+        }
+        self.count = 0;
+    }
+
+    /// Filters a single input sample, returning the filtered signal once every `ratio`
+    /// samples.
+    pub fn step(&mut self, input: T) -> Option<T> {
+        let output = self.sections.iter_mut().fold(input, |signal, section| section.step(signal));
+
+        self.count += 1;
+        if self.count == self.ratio {
+            self.count = 0;
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
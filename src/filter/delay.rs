@@ -0,0 +1,59 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a delay line, which outputs its input `N` samples later, the
+/// model of a pure transport delay such as a conveyor or a long pipe.
+#[derive(Clone, Copy, Debug)]
+pub struct Delay<T, const N: usize> {
+    /// Last `N` input samples.
+    window: [T; N],
+    /// Position of the oldest sample in the window.
+    index: usize,
+}
+
+impl<T: FloatCore, const N: usize> Delay<T, N> {
+    /// Creates a new `Delay` of `N` samples, initially filled with zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Delay;
+    ///
+    /// let mut delay = Delay::<f32, 2>::new();
+    /// assert_eq!(delay.step(1.0), 0.0);
+    /// assert_eq!(delay.step(2.0), 0.0);
+    /// assert_eq!(delay.step(3.0), 1.0);
+    /// ```
+    pub fn new() -> Self {
+        Self { window: [T::zero(); N], index: 0 }
+    }
+
+    /// Fills the delay line with `value`, as if it had been the input for the last
+    /// `N` samples.
+    pub fn fill(&mut self, value: T) {
+        self.window = [value; N];
+    }
+
+    /// Clears the delay line, filling it with zeros.
+    pub fn reset(&mut self) {
+        self.window = [T::zero(); N];
+        self.index = 0;
+    }
+
+    /// Takes a single input sample and returns the one received `N` samples ago.
+    pub fn step(&mut self, input: T) -> T {
+        match self.window.get_mut(self.index) {
+            Some(oldest) => {
+                let output = core::mem::replace(oldest, input);
+                self.index = (self.index + 1) % N;
+                output
+            }
+            None => input,
+        }
+    }
+}
+
+impl<T: FloatCore, const N: usize> Default for Delay<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
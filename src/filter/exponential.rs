@@ -0,0 +1,106 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of a single-pole exponential smoothing filter.
+#[derive(Clone, Copy, Debug)]
+pub struct Exponential<T> {
+    /// Smoothing factor, the weight of a new sample.
+    alpha: T,
+    /// Output of the previous step, `None` until the first sample arrives.
+    output: Option<T>,
+}
+
+impl<T: FloatCore> Exponential<T> {
+    /// Creates a new `Exponential` with a smoothing factor of `alpha`.
+    ///
+    /// An `alpha` of one disables the filter, smaller values result in heavier
+    /// smoothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in the `(0, 1]` interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Exponential;
+    ///
+    /// let mut filter = Exponential::new(0.5);
+    /// assert_eq!(filter.step(2.0), 2.0);
+    /// assert_eq!(filter.step(4.0), 3.0);
+    /// ```
+    pub fn new(alpha: T) -> Self {
+        assert!(alpha > T::zero() && alpha <= T::one());
+        Self { alpha, output: None }
+    }
+
+    /// Creates a new `Exponential` with a time constant of `tau` seconds.
+    ///
+    /// The pole is placed exactly where the derivative low-pass filter of
+    /// [`PID`](crate::pid::PID) places it for the same `tau`. Time constants shorter
+    /// than half of the sampling time disable the filter.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tau` is negative or `sampling_time` is not positive.
+    pub fn from_time_constant(tau: T, sampling_time: T) -> Self {
+        assert!(tau >= T::zero() && sampling_time > T::zero());
+        Self::new(T::one() - pole(tau, sampling_time).max(T::zero()))
+    }
+
+    /// Creates a new `Exponential` with a cutoff frequency of `cutoff` hertz.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cutoff` or `sampling_time` are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Exponential;
+    ///
+    /// let mut filter = Exponential::from_cutoff(5.0, 0.01);
+    /// filter.step(0.0);
+    /// assert!(filter.step(1.0) < 1.0);
+    /// ```
+    pub fn from_cutoff(cutoff: T, sampling_time: T) -> Self {
+        assert!(cutoff > T::zero());
+        let two = constant::two::<T>();
+        let pi = T::from(core::f64::consts::PI).expect("Unable to cast from pi");
+        Self::from_time_constant(T::one() / (two * pi * cutoff), sampling_time)
+    }
+
+    /// Returns the smoothing factor.
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    /// Clears the internal state, the next sample is passed through unfiltered.
+    pub fn reset(&mut self) {
+        self.output = None;
+    }
+
+    /// Filters a single input sample.
+    pub fn step(&mut self, input: T) -> T {
+        let output = match self.output {
+            Some(previous) => previous + self.alpha * (input - previous),
+            None => input,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+/// Computes the pole of a first-order low-pass filter with a time constant of `tau`
+/// seconds, discretized with the bilinear transform.
+pub(crate) fn pole<T: FloatCore>(tau: T, sampling_time: T) -> T {
+    let two = constant::two::<T>();
+    (two * tau - sampling_time) / (two * tau + sampling_time)
+}
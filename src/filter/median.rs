@@ -0,0 +1,86 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of a median filter over the last `N` samples, useful to remove
+/// isolated spikes from a signal.
+///
+/// The filter sorts a copy of the window on every step, it is intended for small
+/// windows of 3, 5 or 7 samples.
+#[derive(Clone, Copy, Debug)]
+pub struct Median<T, const N: usize> {
+    /// Last `N` input samples.
+    window: [T; N],
+    /// Position of the oldest sample in the window.
+    index: usize,
+    /// Number of valid samples in the window.
+    len: usize,
+}
+
+impl<T: FloatCore, const N: usize> Median<T, N> {
+    /// Creates a new, empty, `Median`.
+    ///
+    /// Until the window is full the output is the median of the samples received
+    /// so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or even.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Median;
+    ///
+    /// let mut median = Median::<f32, 3>::new();
+    /// median.step(1.0);
+    /// median.step(1.0);
+    /// // A single sample spike is removed.
+    /// assert_eq!(median.step(100.0), 1.0);
+    /// ```
+    pub fn new() -> Self {
+        assert!(N % 2 == 1);
+        Self { window: [T::zero(); N], index: 0, len: 0 }
+    }
+
+    /// Clears the window.
+    pub fn reset(&mut self) {
+        self.window = [T::zero(); N];
+        self.index = 0;
+        self.len = 0;
+    }
+
+    /// Adds a sample to the window and returns the updated median.
+    pub fn step(&mut self, input: T) -> T {
+        self.window[self.index] = input;
+        self.index = (self.index + 1) % N;
+        if self.len < N {
+            self.len += 1;
+        }
+
+        // Insertion sort, fast for the small windows this filter is meant for.
+        let mut sorted = self.window;
+        let sorted = &mut sorted[..self.len];
+        for i in 1..sorted.len() {
+            let mut j = i;
+            while j > 0 && sorted[j - 1] > sorted[j] {
+                sorted.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[middle]
+        } else {
+            let two = constant::two::<T>();
+            (sorted[middle - 1] + sorted[middle]) / two
+        }
+    }
+}
+
+impl<T: FloatCore, const N: usize> Default for Median<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,76 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a moving average filter over the last `N` samples.
+#[derive(Clone, Copy, Debug)]
+pub struct MovingAverage<T, const N: usize> {
+    /// Last `N` input samples.
+    window: [T; N],
+    /// Sum of the samples in the window.
+    sum: T,
+    /// Position of the oldest sample in the window.
+    index: usize,
+    /// Number of valid samples in the window.
+    len: usize,
+    /// Number of valid samples in the window, as a number.
+    count: T,
+}
+
+impl<T: FloatCore, const N: usize> MovingAverage<T, N> {
+    /// Creates a new, empty, `MovingAverage`.
+    ///
+    /// Until the window is full the output is the average of the samples received
+    /// so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::MovingAverage;
+    ///
+    /// let mut average = MovingAverage::<f32, 4>::new();
+    /// assert_eq!(average.step(4.0), 4.0);
+    /// assert_eq!(average.step(2.0), 3.0);
+    /// ```
+    pub fn new() -> Self {
+        assert!(N > 0);
+        Self { window: [T::zero(); N], sum: T::zero(), index: 0, len: 0, count: T::zero() }
+    }
+
+    /// Clears the window.
+    pub fn reset(&mut self) {
+        self.window = [T::zero(); N];
+        self.sum = T::zero();
+        self.index = 0;
+        self.len = 0;
+        self.count = T::zero();
+    }
+
+    /// Adds a sample to the window and returns the updated average.
+    pub fn step(&mut self, input: T) -> T {
+        if self.len == N {
+            self.sum = self.sum - self.window[self.index];
+        } else {
+            self.len += 1;
+            self.count = self.count + T::one();
+        }
+        self.window[self.index] = input;
+        self.sum = self.sum + input;
+        self.index = (self.index + 1) % N;
+
+        // Recompute the sum once per window to stop rounding errors from accumulating.
+        if self.index == 0 {
+            self.sum = self.window.iter().fold(T::zero(), |sum, &x| sum + x);
+        }
+
+        self.sum / self.count
+    }
+}
+
+impl<T: FloatCore, const N: usize> Default for MovingAverage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,145 @@
+use num_traits::float::{Float, FloatCore};
+
+use super::biquad::{self, Biquad};
+
+/// Implementation of a notch filter, useful to suppress mechanical resonances.
+#[derive(Clone, Copy, Debug)]
+pub struct Notch<T> {
+    /// Underlying second-order section.
+    filter: Biquad<T>,
+
+    /// Center frequency in hertz.
+    frequency: T,
+    /// Width of the rejected band in hertz.
+    bandwidth: T,
+    /// Gain at the center frequency.
+    depth: T,
+    /// Time difference in seconds between two consecutive step operations.
+    sampling_time: T,
+}
+
+impl<T: Float + FloatCore> Notch<T> {
+    /// Creates a new `Notch` centered at `frequency` hertz rejecting a band `bandwidth`
+    /// hertz wide.
+    ///
+    /// `depth` is the gain of the filter at the center frequency, zero removes the
+    /// center frequency completely while one leaves the signal untouched.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bandwidth` is not positive or `depth` is not in the `[0, 1]` interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::Notch;
+    ///
+    /// // Attenuate a 50 Hz resonance by 20 dB.
+    /// let mut notch = Notch::new(50.0, 10.0, 0.1, 0.001);
+    /// let filtered = notch.step(1.0);
+    /// assert!(filtered <= 1.0);
+    /// ```
+    pub fn new(frequency: T, bandwidth: T, depth: T, sampling_time: T) -> Self {
+        let zero = T::zero();
+        let mut notch = Self {
+            filter: Biquad::new(zero, zero, zero, T::one(), zero, zero),
+            frequency,
+            bandwidth,
+            depth,
+            sampling_time,
+        };
+        notch.update();
+        notch
+    }
+
+    /// Moves the center frequency to `frequency` hertz, keeping the filter state.
+    pub fn set_frequency(&mut self, frequency: T) -> &mut Self {
+        self.frequency = frequency;
+        self.update();
+        self
+    }
+
+    /// Changes the width of the rejected band to `bandwidth` hertz, keeping the
+    /// filter state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bandwidth` is not positive.
+    pub fn set_bandwidth(&mut self, bandwidth: T) -> &mut Self {
+        self.bandwidth = bandwidth;
+        self.update();
+        self
+    }
+
+    /// Changes the width of the rejected band through its quality factor, the ratio
+    /// between the center frequency and the bandwidth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not positive.
+    pub fn set_q(&mut self, q: T) -> &mut Self {
+        assert!(q > T::zero());
+        self.set_bandwidth(self.frequency / q)
+    }
+
+    /// Changes the gain at the center frequency, keeping the filter state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is not in the `[0, 1]` interval.
+    pub fn set_depth(&mut self, depth: T) -> &mut Self {
+        self.depth = depth;
+        self.update();
+        self
+    }
+
+    /// Returns the center frequency in hertz.
+    pub fn frequency(&self) -> T {
+        self.frequency
+    }
+
+    /// Returns the width of the rejected band in hertz.
+    pub fn bandwidth(&self) -> T {
+        self.bandwidth
+    }
+
+    /// Returns the gain at the center frequency.
+    pub fn depth(&self) -> T {
+        self.depth
+    }
+
+    /// Clears the internal state of the filter.
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+
+    /// Filters a single input sample.
+    pub fn step(&mut self, input: T) -> T {
+        self.filter.step(input)
+    }
+
+    /// Recomputes the coefficients of the underlying section.
+    fn update(&mut self) {
+        assert!(self.bandwidth > T::zero());
+        assert!(self.depth >= T::zero() && self.depth <= T::one());
+
+        let one = T::one();
+        let two = one + one;
+        let q = self.frequency / self.bandwidth;
+        let (alpha, cos) = biquad::intermediates(self.frequency, self.sampling_time, q);
+
+        // Subtracting a scaled band-pass from the input leaves a gain of exactly
+        // `depth` at the center frequency.
+        self.filter.set_coefficients(
+            one + alpha * self.depth,
+            -two * cos,
+            one - alpha * self.depth,
+            one + alpha,
+            -two * cos,
+            one - alpha,
+        );
+    }
+}
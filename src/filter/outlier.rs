@@ -0,0 +1,133 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a statistical gate that rejects measurements further than a
+/// number of standard deviations away from the running mean.
+///
+/// The mean and variance are tracked with exponential weighting, so the gate adapts
+/// to slow changes of the signal. A genuine fast transient is rejected at first, but
+/// once `max_rejections` consecutive samples have been rejected the gate accepts the
+/// signal again and restarts its statistics from there.
+#[derive(Clone, Copy, Debug)]
+pub struct OutlierGate<T> {
+    /// Number of standard deviations beyond which a sample is rejected.
+    k: T,
+    /// Weight of a new sample in the running statistics.
+    alpha: T,
+    /// Number of consecutive rejections after which the signal is accepted again.
+    max_rejections: u32,
+
+    /// Running mean of the accepted samples.
+    mean: T,
+    /// Running variance of the accepted samples.
+    variance: T,
+    /// Number of samples in the running statistics, only counted until the weighting
+    /// takes over.
+    samples: T,
+    /// Number of consecutive rejected samples.
+    rejections: u32,
+    /// Last accepted sample.
+    last: T,
+}
+
+impl<T: FloatCore> OutlierGate<T> {
+    /// Creates a new `OutlierGate` rejecting samples more than `k` standard deviations
+    /// away from the mean.
+    ///
+    /// `alpha` is the weight of a new sample in the running mean and variance, roughly
+    /// the inverse of the number of samples they remember. The gate only starts
+    /// rejecting samples after the statistics have seen `1 / alpha` samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive or `alpha` is not in the `(0, 1]` interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::OutlierGate;
+    ///
+    /// let mut gate = OutlierGate::new(3.0, 0.1, 5);
+    /// for k in 0..50 {
+    ///     gate.step(20.0 + (k % 3) as f32 * 0.1);
+    /// }
+    /// assert_eq!(gate.step(80.0), 20.1);
+    /// assert!(gate.rejected());
+    /// ```
+    pub fn new(k: T, alpha: T, max_rejections: u32) -> Self {
+        assert!(k > T::zero());
+        assert!(alpha > T::zero() && alpha <= T::one());
+
+        Self {
+            k,
+            alpha,
+            max_rejections,
+
+            mean: T::zero(),
+            variance: T::zero(),
+            samples: T::zero(),
+            rejections: 0,
+            last: T::zero(),
+        }
+    }
+
+    /// Returns the running mean of the accepted samples.
+    pub fn mean(&self) -> T {
+        self.mean
+    }
+
+    /// Returns the running variance of the accepted samples.
+    pub fn variance(&self) -> T {
+        self.variance
+    }
+
+    /// Returns whether the last sample was rejected.
+    pub fn rejected(&self) -> bool {
+        self.rejections > 0
+    }
+
+    /// Clears the running statistics.
+    pub fn reset(&mut self) {
+        self.mean = T::zero();
+        self.variance = T::zero();
+        self.samples = T::zero();
+        self.rejections = 0;
+        self.last = T::zero();
+    }
+
+    /// Gates a single input sample. Rejected samples are substituted by the last
+    /// accepted one.
+    pub fn step(&mut self, input: T) -> T {
+        let deviation = input - self.mean;
+
+        if self.samples * self.alpha >= T::one()
+            && deviation * deviation > self.k * self.k * self.variance
+        {
+            if self.rejections < self.max_rejections {
+                self.rejections += 1;
+                return self.last;
+            }
+            // The signal has really moved, start over from the new level.
+            self.samples = T::zero();
+            self.variance = T::zero();
+        }
+
+        self.rejections = 0;
+        self.accept(input);
+        input
+    }
+
+    /// Adds an accepted sample to the running statistics.
+    fn accept(&mut self, input: T) {
+        if self.samples * self.alpha < T::one() {
+            self.samples = self.samples + T::one();
+        }
+        self.last = input;
+
+        // Plain averaging until enough samples have been seen for the weighting.
+        let alpha = self.alpha.max(T::one() / self.samples);
+
+        let deviation = input - self.mean;
+        self.mean = self.mean + alpha * deviation;
+        self.variance = (T::one() - alpha) * (self.variance + alpha * deviation * deviation);
+    }
+}
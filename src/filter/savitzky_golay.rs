@@ -0,0 +1,107 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a [Savitzky–Golay] differentiator, which estimates the rate of
+/// change of a signal by fitting a low-order polynomial to the last `N` samples.
+///
+/// Compared to the difference between two consecutive samples, the estimate is far
+/// less sensitive to noise and quantization, at the cost of some delay.
+///
+/// [Savitzky–Golay]: https://en.wikipedia.org/wiki/Savitzky%E2%80%93Golay_filter
+#[derive(Clone, Copy, Debug)]
+pub struct SavitzkyGolay<T, const N: usize> {
+    /// Convolution weights, the first weight multiplies the oldest sample.
+    weights: [T; N],
+    /// Last `N` input samples.
+    window: [T; N],
+    /// Position of the oldest sample in the window.
+    index: usize,
+    /// Number of valid samples in the window.
+    len: usize,
+}
+
+impl<T: FloatCore, const N: usize> SavitzkyGolay<T, N> {
+    /// Creates a new `SavitzkyGolay` fitting a polynomial of degree `order`, either one
+    /// or two, and evaluating its derivative at the most recent sample.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// step operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is not one or two, or if `N` is not larger than `order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::SavitzkyGolay;
+    ///
+    /// let mut rate = SavitzkyGolay::<f64, 5>::new(2, 0.1);
+    /// let mut estimate = 0.0;
+    /// for k in 0..5 {
+    ///     estimate = rate.step(3.0 * k as f64 * 0.1);
+    /// }
+    /// assert!((estimate - 3.0).abs() < 1e-9);
+    /// ```
+    pub fn new(order: usize, sampling_time: T) -> Self {
+        assert!(order == 1 || order == 2);
+        assert!(N > order);
+
+        // Positions of the samples relative to the most recent one, which sits at zero.
+        let position = |k: usize| {
+            T::from(k).expect("Unable to cast sample index")
+                - T::from(N - 1).expect("Unable to cast window length")
+        };
+
+        // Power sums of the sample positions.
+        let mut sums = [T::zero(); 5];
+        for k in 0..N {
+            let x = position(k);
+            let mut power = T::one();
+            for sum in sums.iter_mut() {
+                *sum = *sum + power;
+                power = power * x;
+            }
+        }
+        let [s0, s1, s2, s3, s4] = sums;
+
+        // Row of the least squares solution that yields the first order coefficient.
+        let (c0, c1, c2, det) = if order == 1 {
+            (-s1, s0, T::zero(), s0 * s2 - s1 * s1)
+        } else {
+            let det =
+                s0 * (s2 * s4 - s3 * s3) - s1 * (s1 * s4 - s2 * s3) + s2 * (s1 * s3 - s2 * s2);
+            (s2 * s3 - s1 * s4, s0 * s4 - s2 * s2, s1 * s2 - s0 * s3, det)
+        };
+
+        let mut weights = [T::zero(); N];
+        for (k, weight) in weights.iter_mut().enumerate() {
+            let x = position(k);
+            *weight = (c0 + c1 * x + c2 * x * x) / (det * sampling_time);
+        }
+
+        Self { weights, window: [T::zero(); N], index: 0, len: 0 }
+    }
+
+    /// Clears the window.
+    pub fn reset(&mut self) {
+        self.window = [T::zero(); N];
+        self.index = 0;
+        self.len = 0;
+    }
+
+    /// Adds a sample to the window and returns the estimated rate of change, in units
+    /// per second. The estimate is zero until the window is full.
+    pub fn step(&mut self, input: T) -> T {
+        self.window[self.index] = input;
+        self.index = (self.index + 1) % N;
+        if self.len < N {
+            self.len += 1;
+            if self.len < N {
+                return T::zero();
+            }
+        }
+
+        // The oldest sample is at `index` once the window is full.
+        (0..N).fold(T::zero(), |rate, k| rate + self.weights[k] * self.window[(self.index + k) % N])
+    }
+}
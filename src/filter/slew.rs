@@ -0,0 +1,110 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a rate of change limiter for measurements, which replaces
+/// physically impossible jumps with the largest plausible change.
+#[derive(Clone, Copy, Debug)]
+pub struct SlewLimiter<T> {
+    /// Largest accepted change between two consecutive samples.
+    max_step: T,
+
+    /// Output of the previous step, `None` until the first sample arrives.
+    output: Option<T>,
+    /// Whether the last sample had to be limited.
+    limited: bool,
+    /// Number of samples limited since creation or the last reset.
+    count: u32,
+}
+
+impl<T: FloatCore> SlewLimiter<T> {
+    /// Creates a new `SlewLimiter` that allows the signal to change by at most
+    /// `max_step` units between two consecutive samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_step` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::SlewLimiter;
+    ///
+    /// let mut limiter = SlewLimiter::new(1.0);
+    /// assert_eq!(limiter.step(20.0), 20.0);
+    /// assert_eq!(limiter.step(95.0), 21.0);
+    /// assert!(limiter.limited());
+    /// ```
+    pub fn new(max_step: T) -> Self {
+        assert!(max_step >= T::zero());
+        Self { max_step, output: None, limited: false, count: 0 }
+    }
+
+    /// Changes the largest accepted change between two consecutive samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_step` is negative.
+    pub fn set_max_step(&mut self, max_step: T) -> &mut Self {
+        assert!(max_step >= T::zero());
+        self.max_step = max_step;
+        self
+    }
+
+    /// Returns whether the last sample had to be limited.
+    pub fn limited(&self) -> bool {
+        self.limited
+    }
+
+    /// Returns the number of samples limited since creation or the last reset.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Clears the internal state, the next sample is passed through unchanged.
+    pub fn reset(&mut self) {
+        self.output = None;
+        self.limited = false;
+        self.count = 0;
+    }
+
+    /// Limits a single input sample.
+    ///
+    /// A sample that is not finite is rejected: the previous output is held and the
+    /// sample counts as limited. Before the first finite sample it is passed through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::SlewLimiter;
+    ///
+    /// let mut limiter = SlewLimiter::new(1.0);
+    /// limiter.step(20.0);
+    /// assert_eq!(limiter.step(f64::NAN), 20.0);
+    /// assert!(limiter.limited());
+    /// assert_eq!(limiter.step(20.5), 20.5);
+    /// ```
+    pub fn step(&mut self, input: T) -> T {
+        if !input.is_finite() {
+            if let Some(previous) = self.output {
+                self.limited = true;
+                self.count = self.count.saturating_add(1);
+                return previous;
+            }
+            self.limited = false;
+            return input;
+        }
+
+        let output = match self.output {
+            Some(previous) => {
+                num_traits::clamp(input, previous - self.max_step, previous + self.max_step)
+            }
+            None => input,
+        };
+
+        self.limited = output != input;
+        if self.limited {
+            self.count = self.count.saturating_add(1);
+        }
+        self.output = Some(output);
+        output
+    }
+}
@@ -0,0 +1,103 @@
+use num_traits::float::{Float, FloatCore};
+
+use crate::constant;
+
+/// Implementation of the square-root extraction of a differential-pressure signal into
+/// flow units, for flow loops measured across an orifice plate, a venturi or a pitot
+/// tube.
+///
+/// The flow is proportional to the square root of the differential pressure, whose
+/// slope is infinite at zero: near zero flow the extraction amplifies the noise of the
+/// pressure signal without bound. Differential pressures below the low cutoff read as
+/// no flow, and below the linear band the extraction can be replaced by a straight
+/// line of the same value at the end of the band, bounding the amplification. Filters
+/// averaging the noise should come before the extraction, as averaging after it biases
+/// the flow low.
+#[derive(Clone, Copy, Debug)]
+pub struct SquareRoot<T> {
+    /// Flow per square root of differential pressure.
+    scale: T,
+    /// Differential pressure below which the flow reads zero.
+    cutoff: T,
+    /// Differential pressure below which the extraction is linear.
+    linear: T,
+}
+
+impl<T: Float + FloatCore> SquareRoot<T> {
+    /// Creates a new `SquareRoot` reading a flow of `flow_span` at a differential
+    /// pressure of `pressure_span`, without low cutoff or linear band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pressure_span` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::filter::SquareRoot;
+    ///
+    /// // 100 mbar across the orifice at 40 m³/h.
+    /// let mut extraction = SquareRoot::new(100.0, 40.0);
+    /// assert_eq!(extraction.flow(25.0), 20.0);
+    ///
+    /// // Below 1% of the pressure span the flow reads zero, up to 4% it is linear.
+    /// extraction.set_cutoff(1.0).set_linear_band(4.0);
+    /// assert_eq!(extraction.flow(0.5), 0.0);
+    /// assert_eq!(extraction.flow(2.0), 4.0);
+    /// assert_eq!(extraction.flow(4.0), 8.0);
+    /// assert_eq!(extraction.max_gain(), 2.0);
+    /// ```
+    pub fn new(pressure_span: T, flow_span: T) -> Self {
+        assert!(pressure_span > T::zero());
+        let zero = T::zero();
+        Self { scale: flow_span / Float::sqrt(pressure_span), cutoff: zero, linear: zero }
+    }
+
+    /// Changes the differential pressure below which the flow reads zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cutoff` is negative.
+    pub fn set_cutoff(&mut self, cutoff: T) -> &mut Self {
+        assert!(cutoff >= T::zero());
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Changes the differential pressure below which the extraction is linear, zero to
+    /// extract the square root down to the cutoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `band` is negative.
+    pub fn set_linear_band(&mut self, band: T) -> &mut Self {
+        assert!(band >= T::zero());
+        self.linear = band;
+        self
+    }
+
+    /// Returns the largest gain from differential pressure to flow, at the end of the
+    /// linear band or of the cutoff, infinite if both are zero.
+    pub fn max_gain(&self) -> T {
+        let two = constant::two::<T>();
+        let knee = FloatCore::max(self.linear, self.cutoff);
+        if knee > self.linear {
+            self.scale / (two * Float::sqrt(knee))
+        } else {
+            self.scale / Float::sqrt(knee)
+        }
+    }
+
+    /// Returns the flow for the differential pressure `pressure`, zero for a negative
+    /// one.
+    pub fn flow(&self, pressure: T) -> T {
+        let zero = T::zero();
+        if pressure < self.cutoff || pressure <= zero {
+            zero
+        } else if pressure < self.linear {
+            self.scale * pressure / Float::sqrt(self.linear)
+        } else {
+            self.scale * Float::sqrt(pressure)
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Flight control of multirotors: angular rate loops per axis, attitude loops on top
+//! of them and the mixing of the resulting torques into motor commands.
+//!
+//! Angles are in radians and rates in radians per second, positive for a roll to the
+//! right, a pitch nose up and a yaw to the right, clockwise seen from above.
+
+use num_traits::float::FloatCore;
+
+use crate::constant;
+use crate::pid::PID;
+
+/// A value for each of the roll, pitch and yaw axes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Axes<T> {
+    /// Roll axis, along the nose.
+    pub roll: T,
+    /// Pitch axis, along the wings.
+    pub pitch: T,
+    /// Yaw axis, vertical.
+    pub yaw: T,
+}
+
+impl<T> Axes<T> {
+    /// Creates a new `Axes` from its three values.
+    pub fn new(roll: T, pitch: T, yaw: T) -> Self {
+        Self { roll, pitch, yaw }
+    }
+
+    /// Applies `f` to the value of each axis.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> Axes<U> {
+        Axes { roll: f(self.roll), pitch: f(self.pitch), yaw: f(self.yaw) }
+    }
+}
+
+/// What the stick inputs of a [`FlightController`] command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mode {
+    /// Angular rates on all axes, for acrobatic flight.
+    Rate,
+    /// Roll and pitch angles, the vehicle levelling itself when the sticks are
+    /// centered, and a yaw rate.
+    Angle,
+}
+
+/// Implementation of the stabilization of a multirotor, an angular rate PID per axis
+/// driving its torque, below an attitude P loop per axis in [`Mode::Angle`].
+///
+/// The derivative terms of the rate loops are low-pass filtered, as gyroscopes pick
+/// up the vibrations of the motors. The integral terms are cleared on every change of
+/// mode and held at zero while on the ground, so they do not wind up against the
+/// ground before take off.
+#[derive(Clone, Copy, Debug)]
+pub struct FlightController<T> {
+    /// Angular rate controllers, their outputs being the torque commands.
+    pub rate: Axes<PID<T>>,
+    /// Attitude controllers, their outputs being the rate setpoints. Only roll and
+    /// pitch are used.
+    pub attitude: Axes<PID<T>>,
+
+    /// What the setpoints command.
+    mode: Mode,
+    /// Whether the integral terms are held at zero.
+    grounded: bool,
+}
+
+impl<T: FloatCore> FlightController<T> {
+    /// Creates a new `FlightController` in [`Mode::Rate`] and on the ground, from the
+    /// `(kp, ki, kd)` gains of the rate loops, the gains of the attitude loops and the
+    /// cutoff frequency in hertz of the derivative filters.
+    ///
+    /// The torque commands are bounded to `[-1, 1]`, a third of it for the integral
+    /// terms, and the rate setpoints of the attitude loops to `max_rate`. Both loops
+    /// step every `sampling_time` seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::flight::{Axes, FlightController, Mixer, Mode, QuadX};
+    ///
+    /// let rate = Axes::new((0.05, 0.1, 0.001), (0.05, 0.1, 0.001), (0.1, 0.2, 0.0));
+    /// let attitude = Axes::new(6.0, 6.0, 0.0);
+    /// let mut controller = FlightController::new(rate, attitude, 100.0, 10.0, 0.001);
+    /// controller.set_mode(Mode::Angle).set_grounded(false);
+    ///
+    /// // Level the vehicle while it is rolled 0.1 rad to the right.
+    /// let attitude = Axes::new(0.1, 0.0, 0.0);
+    /// let torque = controller.step(Axes::default(), attitude, Axes::default());
+    /// assert!(torque.roll < 0.0);
+    ///
+    /// let [front_left, front_right, rear_right, rear_left] = QuadX.mix(0.5, torque);
+    /// assert!(front_right > front_left && rear_right > rear_left);
+    /// ```
+    pub fn new(
+        rate: Axes<(T, T, T)>,
+        attitude: Axes<T>,
+        derivative_cutoff: T,
+        max_rate: T,
+        sampling_time: T,
+    ) -> Self {
+        let pi = T::from(core::f64::consts::PI).expect("Unable to cast from pi");
+        let tau = T::one() / (constant::two::<T>() * pi * derivative_cutoff);
+        let zero = T::zero();
+        let one = T::one();
+        let three = one + constant::two::<T>();
+
+        let rate = rate.map(|(kp, ki, kd)| {
+            let mut pid = PID::new(kp, ki, kd, tau, sampling_time, zero);
+            pid.bound_output(-one..=one).bound_integral(-one / three..=one / three);
+            pid
+        });
+        let attitude = attitude.map(|kp| {
+            let mut pid = PID::new(kp, zero, zero, zero, sampling_time, zero);
+            pid.bound_output(-max_rate..=max_rate);
+            pid
+        });
+        Self { rate, attitude, mode: Mode::Rate, grounded: true }
+    }
+
+    /// Returns what the setpoints command.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Changes what the setpoints command, clearing the integral terms if it changes.
+    pub fn set_mode(&mut self, mode: Mode) -> &mut Self {
+        if mode != self.mode {
+            self.reset_integrals();
+        }
+        self.mode = mode;
+        self
+    }
+
+    /// Indicates whether the vehicle is on the ground, such as disarmed or at idle
+    /// throttle, holding the integral terms at zero while it is.
+    pub fn set_grounded(&mut self, grounded: bool) -> &mut Self {
+        self.grounded = grounded;
+        self
+    }
+
+    /// Clears the integral terms of every loop.
+    pub fn reset_integrals(&mut self) {
+        for pid in [&mut self.rate.roll, &mut self.rate.pitch, &mut self.rate.yaw] {
+            let mut state = pid.state();
+            state.integral = T::zero();
+            pid.set_state(state);
+        }
+    }
+
+    /// Clears the state of every loop.
+    pub fn reset(&mut self) {
+        for pid in [&mut self.rate.roll, &mut self.rate.pitch, &mut self.rate.yaw] {
+            pid.reset();
+        }
+        for pid in [&mut self.attitude.roll, &mut self.attitude.pitch, &mut self.attitude.yaw] {
+            pid.reset();
+        }
+    }
+
+    /// Performs a single step of the loops from the stick `setpoint`, the estimated
+    /// `attitude` and the gyroscope `rates`, and returns the torque commands. It should
+    /// be called exactly once every sampling time.
+    ///
+    /// In [`Mode::Angle`] the roll and pitch setpoints are angles, otherwise every
+    /// setpoint is a rate. The yaw attitude is not used.
+    pub fn step(&mut self, setpoint: Axes<T>, attitude: Axes<T>, rates: Axes<T>) -> Axes<T> {
+        let (roll, pitch) = match self.mode {
+            Mode::Rate => (setpoint.roll, setpoint.pitch),
+            Mode::Angle => {
+                self.attitude.roll.setpoint = setpoint.roll;
+                self.attitude.pitch.setpoint = setpoint.pitch;
+                (self.attitude.roll.step(attitude.roll), self.attitude.pitch.step(attitude.pitch))
+            }
+        };
+        self.rate.roll.setpoint = roll;
+        self.rate.pitch.setpoint = pitch;
+        self.rate.yaw.setpoint = setpoint.yaw;
+
+        if self.grounded {
+            self.reset_integrals();
+        }
+        Axes {
+            roll: self.rate.roll.step(rates.roll),
+            pitch: self.rate.pitch.step(rates.pitch),
+            yaw: self.rate.yaw.step(rates.yaw),
+        }
+    }
+}
+
+/// Turns a collective throttle and the torque commands of a [`FlightController`] into
+/// motor commands, for a given frame geometry.
+pub trait Mixer<T> {
+    /// Commands of the motors, such as an array with one per motor.
+    type Output;
+
+    /// Returns the motor commands mixing `throttle` and `torque`.
+    fn mix(&self, throttle: T, torque: Axes<T>) -> Self::Output;
+}
+
+/// Mixer of a quadcopter in X configuration, the front right and rear left propellers
+/// spinning counter-clockwise seen from above.
+///
+/// Its commands are in the `[0, 1]` interval, in the order front left, front right,
+/// rear right and rear left. When a command would leave the interval, the throttle is
+/// shifted to keep the full torque if it fits, and the commands are clamped otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuadX;
+
+impl<T: FloatCore> Mixer<T> for QuadX {
+    type Output = [T; 4];
+
+    fn mix(&self, throttle: T, torque: Axes<T>) -> [T; 4] {
+        let Axes { roll, pitch, yaw } = torque;
+        let motors = [
+            throttle + roll + pitch - yaw,
+            throttle - roll + pitch + yaw,
+            throttle - roll - pitch - yaw,
+            throttle + roll - pitch + yaw,
+        ];
+        let low = motors.iter().fold(T::infinity(), |low, &motor| low.min(motor));
+        let high = motors.iter().fold(T::neg_infinity(), |high, &motor| high.max(motor));
+        let shift = if high - low > T::one() {
+            T::zero()
+        } else if low < T::zero() {
+            -low
+        } else if high > T::one() {
+            T::one() - high
+        } else {
+            T::zero()
+        };
+        motors.map(|motor| num_traits::clamp(motor + shift, T::zero(), T::one()))
+    }
+}
@@ -0,0 +1,156 @@
+//! Implementations of the `ufmt` traits, printing numbers with three decimals as
+//! `ufmt` does not format floating point numbers itself.
+
+use num_traits::float::FloatCore;
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+use crate::diagnostics::{Criteria, Report, StepResponse, Summary};
+use crate::pid::{Saturation, StepRecord, PID};
+
+/// A number printed with three decimals.
+struct Fixed<T>(T);
+
+impl<T: FloatCore> uDisplay for Fixed<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let value = self.0.to_f32().unwrap_or(f32::NAN);
+        if value.is_nan() {
+            return f.write_str("NaN");
+        }
+        if value < 0.0 {
+            f.write_str("-")?;
+        }
+        let value = FloatCore::abs(value);
+        if value.is_infinite() || value >= u32::MAX as f32 {
+            return f.write_str("inf");
+        }
+
+        let mut integer = value as u32;
+        let mut fraction = FloatCore::round((value - integer as f32) * 1000.0) as u32;
+        if fraction == 1000 {
+            integer += 1;
+            fraction = 0;
+        }
+        ufmt::uwrite!(f, "{}.", integer)?;
+        if fraction < 100 {
+            f.write_str("0")?;
+        }
+        if fraction < 10 {
+            f.write_str("0")?;
+        }
+        ufmt::uwrite!(f, "{}", fraction)
+    }
+}
+
+impl<T: FloatCore> uDebug for Fixed<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(self, f)
+    }
+}
+
+impl uDisplay for Saturation {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(match self {
+            Saturation::Unsaturated => "Unsaturated",
+            Saturation::Lower => "Lower",
+            Saturation::Upper => "Upper",
+        })
+    }
+}
+
+impl uDebug for Saturation {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(self, f)
+    }
+}
+
+impl<T: FloatCore> uDebug for PID<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("PID")?
+            .field("setpoint", &Fixed(self.setpoint))?
+            .field("output", &Fixed(self.output()))?
+            .field("manual", &self.is_manual())?
+            .field("saturation", &self.saturation())?
+            .field("rate_limit", &self.rate_limit())?
+            .finish()
+    }
+}
+
+impl<T: FloatCore> uDebug for StepRecord<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("StepRecord")?
+            .field("setpoint", &Fixed(self.setpoint))?
+            .field("measurement", &Fixed(self.measurement))?
+            .field("proportional", &Fixed(self.proportional))?
+            .field("integral", &Fixed(self.integral))?
+            .field("derivative", &Fixed(self.derivative))?
+            .field("feedforward", &Fixed(self.feedforward))?
+            .field("output", &Fixed(self.output))?
+            .field("saturation", &self.saturation)?
+            .field("rate_limit", &self.rate_limit)?
+            .field("manual", &self.manual)?
+            .finish()
+    }
+}
+
+impl<T: FloatCore> uDisplay for StepRecord<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        ufmt::uwrite!(
+            f,
+            "SP={} PV={} OUT={}",
+            Fixed(self.setpoint),
+            Fixed(self.measurement),
+            Fixed(self.output)
+        )?;
+        if self.manual {
+            f.write_str(" MAN")?;
+        }
+        match self.saturation {
+            Saturation::Unsaturated => Ok(()),
+            Saturation::Lower => f.write_str(" LO"),
+            Saturation::Upper => f.write_str(" HI"),
+        }
+    }
+}
+
+impl<T: FloatCore> uDebug for Criteria<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("Criteria")?
+            .field("iae", &Fixed(self.iae))?
+            .field("ise", &Fixed(self.ise))?
+            .field("itae", &Fixed(self.itae))?
+            .finish()
+    }
+}
+
+impl<T: FloatCore> uDebug for Summary<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("Summary")?
+            .field("mean", &Fixed(self.mean))?
+            .field("variance", &Fixed(self.variance))?
+            .field("min", &Fixed(self.min))?
+            .field("max", &Fixed(self.max))?
+            .finish()
+    }
+}
+
+impl<T: FloatCore> uDebug for Report<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("Report")?
+            .field("error", &self.error)?
+            .field("output", &self.output)?
+            .field("saturated", &Fixed(self.saturated))?
+            .field("steps", &self.steps)?
+            .finish()
+    }
+}
+
+impl<T: FloatCore> uDebug for StepResponse<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("StepResponse")?
+            .field("rise_time", &self.rise_time.map(Fixed))?
+            .field("overshoot", &Fixed(self.overshoot))?
+            .field("settling_time", &self.settling_time.map(Fixed))?
+            .field("steady_state_error", &Fixed(self.steady_state_error))?
+            .finish()
+    }
+}
@@ -0,0 +1,172 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+use crate::lookup::Table;
+use crate::pid::PID;
+
+/// Implementation of a heading hold for rovers and boats, steering towards a target
+/// heading along the shortest way around.
+///
+/// Headings are in radians, the error being wrapped to `[-π, π]` so that a target
+/// just past north is reached by a small turn. The derivative term acts on the rate
+/// of turn, so changing the target does not kick the steering.
+///
+/// The steering is more effective the faster the vehicle goes, so the gains are
+/// scaled by a factor interpolated from a table of speeds, usually decreasing with
+/// speed. The steering output, such as a rudder angle, can be bounded and rate
+/// limited through the controller.
+#[derive(Clone, Copy, Debug)]
+pub struct Heading<T, const N: usize> {
+    /// Controller of the heading error, its setpoint staying at zero.
+    pub controller: PID<T>,
+
+    /// Proportional, integral and derivative gains at a scale of one.
+    gains: (T, T, T),
+    /// Scale of the gains for each speed.
+    schedule: Table<T, N>,
+    /// Scale of the gains of the last step.
+    scale: T,
+    /// Heading to hold.
+    target: T,
+    /// Heading error of the last step.
+    error: T,
+    /// Heading of the previous step, `None` until the first step.
+    previous: Option<T>,
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: T,
+    /// A full turn, in radians.
+    turn: T,
+}
+
+impl<T: FloatCore, const N: usize> Heading<T, N> {
+    /// Creates a new `Heading` with the gains `kp`, `ki` and `kd` scaled by the
+    /// factors of `schedule` for the speed of each step. The target heading starts at
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::f64::consts::PI;
+    /// use control::heading::Heading;
+    /// use control::lookup::Table;
+    ///
+    /// // Full gains at 1 m/s, a quarter of them from 4 m/s.
+    /// let schedule = Table::new([1.0, 4.0], [1.0, 0.25]);
+    /// let mut heading = Heading::new(2.0, 0.0, 0.0, 0.1, schedule);
+    /// heading.bound_steering(0.5).limit_steering_rate(2.0);
+    ///
+    /// // Heading 350°, target 10°: a 20° turn to the right, not 340° to the left.
+    /// heading.set_target(10.0_f64.to_radians());
+    /// let rudder = heading.step(350.0_f64.to_radians(), 1.0);
+    /// assert!((heading.error() - 20.0_f64.to_radians()).abs() < 1e-12);
+    /// assert_eq!(rudder, 0.2);
+    ///
+    /// // At 4 m/s the same error steers a quarter as much.
+    /// heading.controller.reset();
+    /// let rudder = heading.step(350.0_f64.to_radians(), 4.0);
+    /// assert!((rudder - 0.25 * 2.0 * 20.0_f64.to_radians()).abs() < 1e-12);
+    /// ```
+    pub fn new(kp: T, ki: T, kd: T, sampling_time: T, schedule: Table<T, N>) -> Self {
+        let zero = T::zero();
+        let pi = T::from(core::f64::consts::PI).expect("Unable to cast from pi");
+        Self {
+            controller: PID::new(kp, ki, kd, zero, sampling_time, zero),
+            gains: (kp, ki, kd),
+            schedule,
+            scale: T::one(),
+            target: zero,
+            error: zero,
+            previous: None,
+            sampling_time,
+            turn: constant::two::<T>() * pi,
+        }
+    }
+
+    /// Changes the heading to hold.
+    pub fn set_target(&mut self, heading: T) -> &mut Self {
+        self.target = heading;
+        self
+    }
+
+    /// Returns the heading to hold.
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    /// Changes the gains at a scale of one.
+    pub fn set_gains(&mut self, kp: T, ki: T, kd: T) -> &mut Self {
+        self.gains = (kp, ki, kd);
+        let scale = self.scale;
+        self.controller.set_gains(kp * scale, ki * scale, kd * scale);
+        self
+    }
+
+    /// Restricts the steering output to `[-max, max]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is negative.
+    pub fn bound_steering(&mut self, max: T) -> &mut Self {
+        self.controller.bound_output(-max..=max);
+        self
+    }
+
+    /// Limits the rate of change of the steering output to `rate` units per second in
+    /// both directions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not positive.
+    pub fn limit_steering_rate(&mut self, rate: T) -> &mut Self {
+        self.controller.limit_rate(rate, rate);
+        self
+    }
+
+    /// Returns the heading error of the last step, positive when the target is to the
+    /// right.
+    pub fn error(&self) -> T {
+        self.error
+    }
+
+    /// Returns the scale of the gains of the last step.
+    pub fn scale(&self) -> T {
+        self.scale
+    }
+
+    /// Clears the controller state and the previous heading.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+        self.error = T::zero();
+        self.previous = None;
+    }
+
+    /// Performs a single step of the heading hold from the measured `heading` and the
+    /// vehicle `speed`, and returns the steering output. It should be called exactly
+    /// once every sampling time.
+    ///
+    /// The gains are only changed when the scale for the speed differs from the one
+    /// of the previous step.
+    pub fn step(&mut self, heading: T, speed: T) -> T {
+        let scale = self.schedule.interpolate(speed);
+        if scale != self.scale {
+            self.scale = scale;
+            let (kp, ki, kd) = self.gains;
+            self.controller.set_gains(kp * scale, ki * scale, kd * scale);
+        }
+
+        let turn = match self.previous {
+            Some(previous) => wrap(heading - previous, self.turn),
+            None => T::zero(),
+        };
+        self.previous = Some(heading);
+
+        self.error = wrap(self.target - heading, self.turn);
+        // The measurement is the opposite of the error, so it moves with the heading.
+        self.controller.step_with_rate(-self.error, turn / self.sampling_time)
+    }
+}
+
+/// Wraps an angle in radians to `[-π, π]`, given a full `turn`.
+fn wrap<T: FloatCore>(angle: T, turn: T) -> T {
+    angle - turn * (angle / turn).round()
+}
@@ -0,0 +1,148 @@
+//! Tuning and control of integrating processes, such as levels and positions, whose
+//! output keeps moving as long as their input is not zero.
+//!
+//! Rules derived from first-order models with dead time tune these processes poorly,
+//! as their time constant is in effect infinite. The rules here use the integrating
+//! gain of the process instead, the slope of its output per unit of input.
+
+use num_traits::float::FloatCore;
+
+use crate::constant;
+use crate::pid::PID;
+
+/// Rule computing the gains of a PI controller for an integrating process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rule {
+    /// Skogestad's SIMC rule, with an integral time of four times the closed-loop time
+    /// constant plus the dead time, which rejects load disturbances well.
+    Simc,
+    /// Lambda tuning, with a closed-loop time constant `λ` and a critically damped
+    /// response to load disturbances, which suits averaging level control.
+    Lambda,
+}
+
+/// Returns the proportional and integral gains of a PI controller for an integrating
+/// process of `gain` units per second per unit of input and of dead time `dead_time`,
+/// with a closed-loop time constant of `closed_loop_time` seconds.
+///
+/// The closed-loop time constant trades speed against robustness, setting it to the
+/// dead time is a good start. The gain of the process is negative for a reverse
+/// acting one, such as a tank level driven by its outlet valve, and so are the
+/// returned gains.
+///
+/// # Panics
+///
+/// Panics if `gain` is zero, `dead_time` is negative or `closed_loop_time` is not
+/// positive.
+///
+/// # Examples
+///
+/// ```
+/// use control::integrating::{self, Rule};
+///
+/// let (kp, ki) = integrating::tune(Rule::Simc, 0.5, 1.0, 1.0);
+/// assert_eq!((kp, ki), (1.0, 0.125));
+/// ```
+pub fn tune<T: FloatCore>(rule: Rule, gain: T, dead_time: T, closed_loop_time: T) -> (T, T) {
+    assert!(gain != T::zero() && dead_time >= T::zero() && closed_loop_time > T::zero());
+    let two = constant::two::<T>();
+    let total = closed_loop_time + dead_time;
+
+    let (kp, integral_time) = match rule {
+        Rule::Simc => (T::one() / (gain * total), constant::four::<T>() * total),
+        Rule::Lambda => {
+            let integral_time = two * closed_loop_time + dead_time;
+            (integral_time / (gain * total * total), integral_time)
+        }
+    };
+    (kp, kp / integral_time)
+}
+
+/// Implementation of a PI controller for integrating processes, with setpoint
+/// weighting.
+///
+/// The proportional term acts on `weight * setpoint - measurement` while the integral
+/// term acts on the full error. A weight below one keeps the integral tuned for
+/// disturbance rejection from overshooting on setpoint changes, which integrating
+/// processes do with any PI controller. The controller always keeps a proportional
+/// term, as a pure integral controller of an integrating process oscillates.
+#[derive(Clone, Copy, Debug)]
+pub struct Integrating<T> {
+    /// Underlying controller.
+    pub controller: PID<T>,
+
+    /// Setpoint weight of the proportional term.
+    weight: T,
+}
+
+impl<T: FloatCore> Integrating<T> {
+    /// Creates a new `Integrating` controller tuned by `rule` for an integrating
+    /// process, see [`tune`], with a setpoint weight of one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gain` is zero, `dead_time` is negative, `closed_loop_time` is not
+    /// positive or `sampling_time` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::integrating::{Integrating, Rule};
+    /// use control::simulation::{Integrator, Sample, Simulation};
+    ///
+    /// let peak = |weight| {
+    ///     let mut tank = Integrating::new(Rule::Simc, 0.1, 0.0, 5.0, 0.1, 0.0);
+    ///     tank.set_setpoint_weight(weight);
+    ///     let mut simulation = Simulation::new(tank, Integrator::new(0.1, 0.1));
+    ///     let mut trajectory = [Sample::default(); 1000];
+    ///     simulation.run(&mut trajectory, |_| 1.0, |_| 0.0);
+    ///     trajectory.iter().fold(0.0_f64, |peak, sample| peak.max(sample.measurement))
+    /// };
+    ///
+    /// assert!(peak(1.0) > 1.1);
+    /// assert!(peak(0.5) < 1.05);
+    /// ```
+    pub fn new(
+        rule: Rule,
+        gain: T,
+        dead_time: T,
+        closed_loop_time: T,
+        sampling_time: T,
+        setpoint: T,
+    ) -> Self {
+        let (kp, ki) = tune(rule, gain, dead_time, closed_loop_time);
+        let controller = PID::new(kp, ki, T::zero(), T::zero(), sampling_time, setpoint);
+        Self { controller, weight: T::one() }
+    }
+
+    /// Changes the setpoint weight of the proportional term.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is not between zero and one.
+    pub fn set_setpoint_weight(&mut self, weight: T) -> &mut Self {
+        assert!(weight >= T::zero() && weight <= T::one());
+        self.weight = weight;
+        self
+    }
+
+    /// Returns the setpoint weight of the proportional term.
+    pub fn setpoint_weight(&self) -> T {
+        self.weight
+    }
+
+    /// Clears the controller state.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+    }
+
+    /// Performs a single step of the control loop and returns the output. It should be
+    /// called exactly once every sampling time.
+    pub fn step(&mut self, measurement: T) -> T {
+        let (kp, _, _) = self.controller.gains();
+        // The part of the setpoint left out of the proportional term.
+        let feedforward = -kp * (T::one() - self.weight) * self.controller.setpoint;
+        self.controller.step_with_feedforward(measurement, feedforward)
+    }
+}
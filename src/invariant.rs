@@ -0,0 +1,162 @@
+//! Invariants of the controller, checked on its transitions so they can be verified
+//! again with other numeric types or configurations, for instance from a property
+//! test feeding the random [`Inputs`].
+
+use num_traits::float::FloatCore;
+
+use crate::constant;
+use crate::pid::{Input, State, PID};
+use crate::simulation::NoiseSource;
+
+/// An invariant of the controller that a transition broke.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// The output is outside the output bounds.
+    Output,
+    /// The integral term is outside the integral bounds.
+    Integral,
+    /// The derivative term grew beyond what the bounded inputs allow.
+    Derivative,
+    /// A state variable became infinite or not a number.
+    NotFinite,
+}
+
+/// Performs the transition of `controller` from `state` on `input` and checks that
+/// the output and the integral term of the next state are within their bounds,
+/// returning the next state.
+///
+/// It assumes `state` is within the bounds, as any state produced by the controller
+/// from a finite input is.
+///
+/// # Examples
+///
+/// ```
+/// use control::invariant::{check_transition, Violation};
+/// use control::pid::{Input, PID};
+///
+/// let mut pid = PID::new(10.0, 5.0, 0.0, 0.0, 0.1, 50.0);
+/// pid.bound_output(0.0..=100.0).bound_integral(-20.0..=20.0);
+///
+/// let input = Input { measurement: -1e6, rate: None, feedforward: 0.0 };
+/// assert!(check_transition(&pid, pid.state(), input).is_ok());
+///
+/// let input = Input { measurement: f64::NAN, rate: None, feedforward: 0.0 };
+/// assert_eq!(check_transition(&pid, pid.state(), input), Err(Violation::Output));
+/// ```
+pub fn check_transition<T: FloatCore>(
+    controller: &PID<T>,
+    state: State<T>,
+    input: Input<T>,
+) -> Result<State<T>, Violation> {
+    let (next, output) = controller.transition(state, input);
+    let (omin, omax) = controller.output_bounds();
+    if !(output >= omin && output <= omax) {
+        return Err(Violation::Output);
+    }
+    let (imin, imax) = controller.integral_bounds();
+    if !(next.integral >= imin && next.integral <= imax) {
+        return Err(Violation::Integral);
+    }
+    Ok(next)
+}
+
+/// Steps `controller` from its current state through every input, which must not
+/// exceed `limit` in magnitude, checking the transitions with [`check_transition`]
+/// and that the state stays bounded. Returns the index of the first offending input
+/// and the invariant it broke.
+///
+/// The state must stay finite, and when the derivative filter is stable its term
+/// must stay within the bound the inputs allow, with a relative slack of 2⁻¹⁰ for
+/// rounding. The integral term is only bounded by its own bounds, if any.
+///
+/// # Examples
+///
+/// ```
+/// use control::invariant::{check_bounded, Inputs};
+/// use control::pid::PID;
+///
+/// let mut pid = PID::new(2.0_f32, 1.0, 0.5, 0.05, 0.01, 0.0);
+/// pid.bound_output(-10.0..=10.0).bound_integral(-5.0..=5.0).limit_rate(100.0, 100.0);
+/// for seed in 1..20 {
+///     let inputs = Inputs::new(seed, 1000.0).take(500);
+///     assert_eq!(check_bounded(&mut pid, inputs, 1000.0), Ok(()));
+/// }
+/// ```
+pub fn check_bounded<T, I>(
+    controller: &mut PID<T>,
+    inputs: I,
+    limit: T,
+) -> Result<(), (usize, Violation)>
+where
+    T: FloatCore,
+    I: IntoIterator<Item = Input<T>>,
+{
+    let (_, _, d, t) = controller.coefficients();
+    let (_, _, kd) = controller.gains();
+    let state = controller.state();
+    let measurement = limit.max(state.measurement.unwrap_or_else(T::zero).abs());
+    // A step reaches at most a fraction |t| of the previous term plus the largest
+    // measurement difference, so the term never exceeds the fixed point of that sum.
+    let bound = if t.abs() < T::one() {
+        let filtered = constant::two::<T>() * measurement * d.abs() / (T::one() - t.abs());
+        let slack = (0..10).fold(T::one(), |slack, _| slack * constant::half::<T>());
+        Some(filtered.max(kd.abs() * limit).max(state.derivative.abs()) * (T::one() + slack))
+    } else {
+        None
+    };
+
+    for (index, input) in inputs.into_iter().enumerate() {
+        let state = check_transition(controller, controller.state(), input)
+            .map_err(|violation| (index, violation))?;
+        let values = [
+            state.error,
+            state.integral,
+            state.derivative,
+            state.feedforward,
+            state.measurement.unwrap_or_else(T::zero),
+            state.output,
+        ];
+        if !values.iter().all(|value| value.is_finite()) {
+            return Err((index, Violation::NotFinite));
+        }
+        if bound.is_some_and(|bound| state.derivative.abs() > bound) {
+            return Err((index, Violation::Derivative));
+        }
+        controller.set_state(state);
+    }
+    Ok(())
+}
+
+/// Endless iterator over pseudo-random controller inputs no larger than a limit in
+/// magnitude, half of them supplying a rate of change of the measurement.
+#[derive(Clone, Copy, Debug)]
+pub struct Inputs<T> {
+    /// Source of the inputs.
+    noise: NoiseSource,
+    /// Largest magnitude of the inputs.
+    limit: T,
+}
+
+impl<T: FloatCore> Inputs<T> {
+    /// Creates a new `Inputs` from a `seed`, the same seed always producing the same
+    /// inputs, with measurements, rates and feedforward terms in the
+    /// `[-limit, limit]` interval.
+    pub fn new(seed: u32, limit: T) -> Self {
+        Self { noise: NoiseSource::new(seed), limit }
+    }
+}
+
+impl<T: FloatCore> Iterator for Inputs<T> {
+    type Item = Input<T>;
+
+    fn next(&mut self) -> Option<Input<T>> {
+        let measurement = self.noise.uniform(self.limit);
+        let rate = if self.noise.next_u32() & 1 == 0 {
+            Some(self.noise.uniform(self.limit))
+        } else {
+            None
+        };
+        let feedforward = self.noise.uniform(self.limit);
+        Some(Input { measurement, rate, feedforward })
+    }
+}
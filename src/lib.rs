@@ -2,4 +2,77 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Logs a debug message through the `log` facade, if the `log` feature is enabled.
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+    }};
+}
+
+/// Logs a trace message through the `log` facade, if the `log` feature is enabled.
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+    }};
+}
+
+pub mod bank;
+pub mod canopen;
+pub mod cascade;
+pub mod command;
+pub mod compensation;
+pub mod config;
+mod constant;
+pub mod controller;
+pub mod diagnostics;
+pub mod differential;
+pub mod feedforward;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filter;
+pub mod flight;
+pub mod foc;
+#[cfg(feature = "ufmt")]
+mod format;
+pub mod heading;
+pub mod integrating;
+pub mod invariant;
+pub mod lookup;
+pub mod mid_range;
+pub mod mimo;
+#[cfg(feature = "modbus")]
+pub mod modbus;
+pub mod multirate;
+pub mod output;
 pub mod pid;
+pub mod prelude;
+#[cfg(feature = "pyo3")]
+pub mod python;
+#[cfg(feature = "alloc")]
+pub mod registry;
+pub mod remote;
+pub mod runner;
+pub mod scalar;
+pub mod selector;
+pub mod servo;
+#[cfg(feature = "critical-section")]
+pub mod shared;
+pub mod simulation;
+pub mod stability;
+pub mod staging;
+pub mod swap;
+pub mod telemetry;
+#[cfg(feature = "uom")]
+pub mod units;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod zones;
+
+pub use crate::pid::{Saturation, PID};
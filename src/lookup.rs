@@ -0,0 +1,85 @@
+//! Breakpoint tables with linear interpolation.
+
+use num_traits::float::FloatCore;
+
+/// Implementation of a table of `N` breakpoints, linearly interpolated between them
+/// and held constant beyond the first and last ones.
+#[derive(Clone, Copy, Debug)]
+pub struct Table<T, const N: usize> {
+    /// Input value of each breakpoint, strictly increasing.
+    inputs: [T; N],
+    /// Output value of each breakpoint.
+    outputs: [T; N],
+}
+
+impl<T: FloatCore, const N: usize> Table<T, N> {
+    /// Creates a new `Table` from its breakpoint `inputs` and `outputs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or the inputs are not strictly increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::lookup::Table;
+    ///
+    /// let table = Table::new([0.0, 1.0, 3.0], [0.0, 10.0, 30.0]);
+    /// assert_eq!(table.interpolate(0.5), 5.0);
+    /// assert_eq!(table.interpolate(2.0), 20.0);
+    /// assert_eq!(table.interpolate(5.0), 30.0);
+    /// ```
+    pub fn new(inputs: [T; N], outputs: [T; N]) -> Self {
+        assert!(N > 0);
+        assert!(inputs.windows(2).all(|pair| pair[0] < pair[1]));
+        Self { inputs, outputs }
+    }
+
+    /// Returns the breakpoint inputs.
+    pub fn inputs(&self) -> &[T; N] {
+        &self.inputs
+    }
+
+    /// Returns the breakpoint outputs.
+    pub fn outputs(&self) -> &[T; N] {
+        &self.outputs
+    }
+
+    /// Returns the output of the table for `input`, or not a number if `input` is not
+    /// a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::lookup::Table;
+    ///
+    /// let table = Table::new([1.0], [4.0]);
+    /// assert_eq!(table.interpolate(0.0), 4.0);
+    /// assert!(table.interpolate(f32::NAN).is_nan());
+    /// ```
+    pub fn interpolate(&self, input: T) -> T {
+        interpolate(&self.inputs, &self.outputs, input)
+    }
+}
+
+/// Linearly interpolates the output for `input` given breakpoints with increasing
+/// inputs, holding the first and last outputs beyond them. An input that is not a
+/// number gives not a number.
+pub(crate) fn interpolate<T: FloatCore>(inputs: &[T], outputs: &[T], input: T) -> T {
+    if input.is_nan() {
+        return T::nan();
+    }
+    let last = inputs.len() - 1;
+    if last == 0 || input <= inputs[0] {
+        return outputs[0];
+    }
+    if input >= inputs[last] {
+        return outputs[last];
+    }
+
+    // First breakpoint beyond the input, never the first one given the checks above.
+    let upper = inputs.iter().position(|&x| x > input).unwrap_or(last);
+    let lower = upper - 1;
+    let fraction = (input - inputs[lower]) / (inputs[upper] - inputs[lower]);
+    outputs[lower] + (outputs[upper] - outputs[lower]) * fraction
+}
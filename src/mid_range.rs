@@ -0,0 +1,105 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Implementation of mid-ranging control, where two actuators act on the same process.
+///
+/// The `fast` controller drives a fast but small actuator to control the process,
+/// while the `slow` controller drives a large actuator to keep the fast actuator near
+/// the middle of its range, where it has room to act in both directions.
+///
+/// The slow controller sees the fast actuator command as its measurement and the
+/// mid-range value as its setpoint. As an increase of the slow actuator lets the fast
+/// actuator close, the slow controller must be reverse acting, with negative gains.
+///
+/// Each controller holds its integral term while its own actuator is saturated, so
+/// neither winds up while the other one does the work. While the fast actuator is
+/// saturated the slow controller keeps integrating, which is what brings the fast
+/// actuator back towards the middle. The integral inhibition of both controllers is
+/// managed by this type.
+#[derive(Clone, Copy, Debug)]
+pub struct MidRange<T> {
+    /// Controller driving the fast actuator from the process measurement.
+    pub fast: PID<T>,
+    /// Controller driving the slow actuator from the fast actuator command.
+    pub slow: PID<T>,
+}
+
+impl<T: FloatCore> MidRange<T> {
+    /// Creates a new `MidRange` keeping the output of `fast` around `mid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::mid_range::MidRange;
+    /// use control::pid::PID;
+    ///
+    /// let mut fast = PID::new(1.0, 1.0, 0.0, 0.0, 0.1, 7.0);
+    /// fast.bound_output(0.0..=100.0);
+    /// let mut slow = PID::new(-0.05, -0.01, 0.0, 0.0, 0.1, 0.0);
+    /// slow.bound_output(0.0..=100.0);
+    ///
+    /// let mut control = MidRange::new(fast, slow, 50.0);
+    /// let (small_valve, large_valve) = control.step(6.0);
+    /// assert!(small_valve > 0.0 && large_valve == 0.0);
+    /// ```
+    pub fn new(fast: PID<T>, mut slow: PID<T>, mid: T) -> Self {
+        slow.setpoint = mid;
+        Self { fast, slow }
+    }
+
+    /// Changes the position around which the fast actuator is kept.
+    pub fn set_mid(&mut self, mid: T) -> &mut Self {
+        self.slow.setpoint = mid;
+        self
+    }
+
+    /// Clears the state of both controllers.
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+    }
+
+    /// Performs a single step of both loops and returns the fast and slow actuator
+    /// commands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::mid_range::MidRange;
+    /// use control::pid::PID;
+    /// use control::simulation::{FirstOrder, Plant};
+    ///
+    /// let mut fast = PID::new(1.0, 2.0, 0.0, 0.0, 0.1, 120.0);
+    /// fast.bound_output(0.0..=100.0);
+    /// let mut slow = PID::new(-0.05, -0.05, 0.0, 0.0, 0.1, 0.0);
+    /// slow.bound_output(0.0..=100.0);
+    /// let mut control = MidRange::new(fast, slow, 50.0);
+    ///
+    /// // A demand of 120 is more than the small valve alone can deliver.
+    /// let mut process = FirstOrder::new(1.0, 1.0, 0.1);
+    /// let (mut small_valve, mut saturated) = (0.0, false);
+    /// for _ in 0..3000 {
+    ///     let (small, large) = control.step(process.output());
+    ///     process.step(small + large);
+    ///     saturated |= small == 100.0;
+    ///     small_valve = small;
+    /// }
+    /// // The small valve saturated, then the large one took over and brought it back
+    /// // to mid-range.
+    /// assert!(saturated);
+    /// assert!((process.output() - 120.0_f64).abs() < 0.1);
+    /// assert!((small_valve - 50.0).abs() < 1.0);
+    /// ```
+    pub fn step(&mut self, measurement: T) -> (T, T) {
+        // Hold each integral term while its own actuator is saturated in the direction
+        // the integral would wind.
+        self.fast.inhibit_integral(self.fast.saturation());
+        let fast = self.fast.step(measurement);
+
+        self.slow.inhibit_integral(self.slow.saturation());
+        let slow = self.slow.step(fast);
+
+        (fast, slow)
+    }
+}
@@ -0,0 +1,155 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Implementation of a multivariable controller made of `N` independent loops and a
+/// static decoupling matrix, for processes where the loops interact.
+///
+/// The outputs of the loops are multiplied by the output decoupling matrix before
+/// being sent to the actuators. Measurements can optionally be combined by an input
+/// decoupling matrix before being fed to the loops.
+#[derive(Clone, Copy, Debug)]
+pub struct Mimo<T, const N: usize> {
+    /// Independent loops, one per decoupled channel.
+    pub controllers: [PID<T>; N],
+
+    /// Matrix applied to the loop outputs, row major.
+    output_decoupling: [[T; N]; N],
+    /// Matrix applied to the measurements, row major.
+    input_decoupling: Option<[[T; N]; N]>,
+}
+
+impl<T: FloatCore, const N: usize> Mimo<T, N> {
+    /// Creates a new `Mimo` from its loops and output decoupling matrix, the actuator
+    /// commands being `decoupling * outputs`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::mimo::Mimo;
+    /// use control::pid::PID;
+    ///
+    /// let temperature = PID::new(2.0, 0.1, 0.0, 0.0, 1.0, 25.0);
+    /// let humidity = PID::new(1.0, 0.05, 0.0, 0.0, 1.0, 60.0);
+    ///
+    /// // The humidifier also cools the chamber, so the heater compensates for it.
+    /// let mut chamber = Mimo::new([temperature, humidity], [[1.0, 0.3], [0.0, 1.0]]);
+    /// let [heater, humidifier] = chamber.step([25.0, 50.0]);
+    /// assert_eq!(heater, 0.3 * humidifier);
+    /// ```
+    pub fn new(controllers: [PID<T>; N], output_decoupling: [[T; N]; N]) -> Self {
+        Self { controllers, output_decoupling, input_decoupling: None }
+    }
+
+    /// Sets the matrix applied to the measurements, the loop inputs being
+    /// `decoupling * measurements`.
+    pub fn decouple_inputs(&mut self, decoupling: [[T; N]; N]) -> &mut Self {
+        self.input_decoupling = Some(decoupling);
+        self
+    }
+
+    /// Replaces the matrix applied to the loop outputs.
+    pub fn decouple_outputs(&mut self, decoupling: [[T; N]; N]) -> &mut Self {
+        self.output_decoupling = decoupling;
+        self
+    }
+
+    /// Clears the state of every loop.
+    pub fn reset(&mut self) {
+        for controller in self.controllers.iter_mut() {
+            controller.reset();
+        }
+    }
+
+    /// Performs a single step of every loop and returns the actuator commands.
+    pub fn step(&mut self, measurements: [T; N]) -> [T; N] {
+        let measurements = match &self.input_decoupling {
+            Some(decoupling) => multiply(decoupling, &measurements),
+            None => measurements,
+        };
+
+        let mut outputs = [T::zero(); N];
+        for ((output, controller), &measurement) in
+            outputs.iter_mut().zip(self.controllers.iter_mut()).zip(measurements.iter())
+        {
+            *output = controller.step(measurement);
+        }
+
+        multiply(&self.output_decoupling, &outputs)
+    }
+}
+
+/// Multiplies a square matrix by a vector.
+fn multiply<T: FloatCore, const N: usize>(matrix: &[[T; N]; N], vector: &[T; N]) -> [T; N] {
+    let mut result = [T::zero(); N];
+    for (value, row) in result.iter_mut().zip(matrix.iter()) {
+        *value = row.iter().zip(vector.iter()).fold(T::zero(), |sum, (&a, &b)| sum + a * b);
+    }
+    result
+}
+
+/// Computes the [relative gain array] of a steady-state gain matrix, where `gain[i][j]`
+/// is the gain from input `j` to output `i`. Returns `None` if the matrix is singular.
+///
+/// Pairing output `i` with input `j` is advisable when the relative gain `[i][j]` is
+/// close to one, and should be avoided when it is negative.
+///
+/// [relative gain array]: https://en.wikipedia.org/wiki/Relative_Gain_Array
+///
+/// # Examples
+///
+/// ```
+/// use control::mimo;
+///
+/// let rga = mimo::rga(&[[2.0_f64, 0.5], [0.4, 1.0]]).unwrap();
+/// assert!((rga[0][0] - 1.1111).abs() < 1e-4);
+/// assert!((rga[0][1] + 0.1111).abs() < 1e-4);
+/// ```
+pub fn rga<T: FloatCore, const N: usize>(gain: &[[T; N]; N]) -> Option<[[T; N]; N]> {
+    let inverse = inverse(gain)?;
+    let mut result = [[T::zero(); N]; N];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = gain[i][j] * inverse[j][i];
+        }
+    }
+    Some(result)
+}
+
+/// Inverts a square matrix by Gauss-Jordan elimination with partial pivoting.
+fn inverse<T: FloatCore, const N: usize>(matrix: &[[T; N]; N]) -> Option<[[T; N]; N]> {
+    let mut left = *matrix;
+    let mut right = [[T::zero(); N]; N];
+    for (i, row) in right.iter_mut().enumerate() {
+        row[i] = T::one();
+    }
+
+    for column in 0..N {
+        let pivot = (column..N).max_by(|&a, &b| {
+            let (a, b) = (left[a][column].abs(), left[b][column].abs());
+            a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+        })?;
+        if left[pivot][column] == T::zero() {
+            return None;
+        }
+        left.swap(column, pivot);
+        right.swap(column, pivot);
+
+        let scale = left[column][column];
+        for k in 0..N {
+            left[column][k] = left[column][k] / scale;
+            right[column][k] = right[column][k] / scale;
+        }
+
+        for row in 0..N {
+            let factor = left[row][column];
+            if row != column && factor != T::zero() {
+                for k in 0..N {
+                    left[row][k] = left[row][k] - factor * left[column][k];
+                    right[row][k] = right[row][k] - factor * right[column][k];
+                }
+            }
+        }
+    }
+    Some(right)
+}
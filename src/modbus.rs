@@ -0,0 +1,277 @@
+//! A canonical Modbus holding register layout for the parameters and live values of
+//! a controller, so PLC and SCADA clients can monitor and tune it.
+//!
+//! # Layout
+//!
+//! | Address | Register        | Access     | Scaling |
+//! |---------|-----------------|------------|---------|
+//! | 0       | [`SETPOINT`]    | read-write | process |
+//! | 1       | [`MEASUREMENT`] | read       | process |
+//! | 2       | [`OUTPUT`]      | read-write | process |
+//! | 3       | [`MODE`]        | read-write |         |
+//! | 4       | [`KP`]          | read-write | gains   |
+//! | 5       | [`KI`]          | read-write | gains   |
+//! | 6       | [`KD`]          | read-write | gains   |
+//! | 7       | [`OUTPUT_MIN`]  | read-write | process |
+//! | 8       | [`OUTPUT_MAX`]  | read-write | process |
+//! | 9       | [`STATUS`]      | read       |         |
+//!
+//! Values are signed 16-bit integers, the real value multiplied by the scale of the
+//! register and rounded, saturating at the ends of the integer range. The output bound
+//! registers reserve the ends of the range for unbounded outputs: `0x8000` is a lower
+//! bound of minus infinity and `0x7FFF` an upper bound of infinity, finite bounds
+//! saturating one step short of them.
+//!
+//! The mode is 0 in automatic and 1 in manual, the output can only be written in
+//! manual mode. Bits 0 and 1 of the status hold the output saturation, as 0 for none,
+//! 1 for the lower bound and 2 for the upper bound, and bit 2 is set in manual mode.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, PID};
+
+/// Address of the setpoint register.
+pub const SETPOINT: u16 = 0;
+/// Address of the measurement register.
+pub const MEASUREMENT: u16 = 1;
+/// Address of the output register.
+pub const OUTPUT: u16 = 2;
+/// Address of the mode register.
+pub const MODE: u16 = 3;
+/// Address of the proportional gain register.
+pub const KP: u16 = 4;
+/// Address of the integral gain register.
+pub const KI: u16 = 5;
+/// Address of the derivative gain register.
+pub const KD: u16 = 6;
+/// Address of the output lower bound register.
+pub const OUTPUT_MIN: u16 = 7;
+/// Address of the output upper bound register.
+pub const OUTPUT_MAX: u16 = 8;
+/// Address of the status register.
+pub const STATUS: u16 = 9;
+
+/// Number of registers of the layout.
+pub const REGISTERS: usize = 10;
+
+/// A Modbus exception to report to the client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exception {
+    /// A register is outside of the layout or is read-only.
+    IllegalDataAddress,
+    /// A written value is not acceptable.
+    IllegalDataValue,
+}
+
+impl Exception {
+    /// Returns the Modbus exception code.
+    pub fn code(self) -> u8 {
+        match self {
+            Exception::IllegalDataAddress => 0x02,
+            Exception::IllegalDataValue => 0x03,
+        }
+    }
+}
+
+/// Implementation of the register map of a [`PID`] controller, converting between
+/// its values and scaled integers.
+#[derive(Clone, Copy, Debug)]
+pub struct RegisterMap<T> {
+    /// Scale of the setpoint, measurement and output registers.
+    process: T,
+    /// Scale of the gain registers.
+    gains: T,
+}
+
+impl<T: FloatCore> RegisterMap<T> {
+    /// Creates a new `RegisterMap` multiplying the process values by `process` and
+    /// the gains by `gains`, for instance 10 for a resolution of 0.1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a scale is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::modbus::{RegisterMap, KP, MEASUREMENT, OUTPUT_MIN, SETPOINT};
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 1.0, 20.0);
+    /// let map = RegisterMap::new(10.0, 100.0);
+    ///
+    /// // Function 16, write multiple registers: a setpoint of 21.5.
+    /// map.write_registers(&mut pid, SETPOINT, &[215]).unwrap();
+    /// pid.step(20.0);
+    ///
+    /// // Function 3, read holding registers.
+    /// let mut registers = [0; 4];
+    /// map.read_registers(&pid, SETPOINT, &mut registers).unwrap();
+    /// assert_eq!(registers, [215, 200, 15, 0]);
+    ///
+    /// map.write_registers(&mut pid, KP, &[250, 10, 0]).unwrap();
+    /// assert_eq!(pid.gains(), (2.5, 0.1, 0.0));
+    /// assert!(map.write_registers(&mut pid, MEASUREMENT, &[0]).is_err());
+    ///
+    /// // Unbounded outputs survive a read-modify-write of the bounds.
+    /// let mut bounds = [0; 2];
+    /// map.read_registers(&pid, OUTPUT_MIN, &mut bounds).unwrap();
+    /// assert_eq!(bounds, [0x8000, 0x7FFF]);
+    /// map.write_registers(&mut pid, OUTPUT_MIN, &bounds).unwrap();
+    /// assert_eq!(pid.output_bounds(), (f32::NEG_INFINITY, f32::INFINITY));
+    /// ```
+    pub fn new(process: T, gains: T) -> Self {
+        assert!(process > T::zero() && gains > T::zero());
+        Self { process, gains }
+    }
+
+    /// Reads the registers starting at `address` into `registers`.
+    pub fn read_registers(
+        &self,
+        controller: &PID<T>,
+        address: u16,
+        registers: &mut [u16],
+    ) -> Result<(), Exception> {
+        let start = usize::from(address);
+        if start + registers.len() > REGISTERS {
+            return Err(Exception::IllegalDataAddress);
+        }
+        for (register, value) in (address..).zip(registers.iter_mut()) {
+            *value = self.read(controller, register);
+        }
+        Ok(())
+    }
+
+    /// Writes `values` to the registers starting at `address`.
+    ///
+    /// All the values are checked before any is applied, so a rejected write leaves the
+    /// controller untouched.
+    pub fn write_registers(
+        &self,
+        controller: &mut PID<T>,
+        address: u16,
+        values: &[u16],
+    ) -> Result<(), Exception> {
+        let start = usize::from(address);
+        if start + values.len() > REGISTERS {
+            return Err(Exception::IllegalDataAddress);
+        }
+
+        let (mut kp, mut ki, mut kd) = controller.gains();
+        let (mut min, mut max) = controller.output_bounds();
+        let mut setpoint = controller.setpoint;
+        let mut manual = controller.is_manual();
+        let mut output = None;
+        for (register, &value) in (address..).zip(values) {
+            match register {
+                SETPOINT => setpoint = decode(value, self.process)?,
+                OUTPUT => output = Some(decode(value, self.process)?),
+                MODE => {
+                    manual = match value {
+                        0 => false,
+                        1 => true,
+                        _ => return Err(Exception::IllegalDataValue),
+                    }
+                }
+                KP => kp = decode(value, self.gains)?,
+                KI => ki = decode(value, self.gains)?,
+                KD => kd = decode(value, self.gains)?,
+                OUTPUT_MIN => min = decode_bound(value, self.process)?,
+                OUTPUT_MAX => max = decode_bound(value, self.process)?,
+                _ => return Err(Exception::IllegalDataAddress),
+            }
+        }
+        if min > max || (output.is_some() && !manual) {
+            return Err(Exception::IllegalDataValue);
+        }
+
+        controller.setpoint = setpoint;
+        if (kp, ki, kd) != controller.gains() {
+            controller.set_gains(kp, ki, kd);
+        }
+        controller.bound_output(min..=max);
+        match output {
+            Some(output) => {
+                controller.set_manual(output);
+            }
+            // Switching to manual mode holds the last output.
+            None if manual && !controller.is_manual() => {
+                let output = controller.output();
+                controller.set_manual(output);
+            }
+            None if !manual => {
+                controller.set_automatic();
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a single register within the layout.
+    fn read(&self, controller: &PID<T>, register: u16) -> u16 {
+        let (kp, ki, kd) = controller.gains();
+        let (min, max) = controller.output_bounds();
+        match register {
+            SETPOINT => encode(controller.setpoint, self.process),
+            MEASUREMENT => encode(controller.record().measurement, self.process),
+            OUTPUT => encode(controller.output(), self.process),
+            MODE => u16::from(controller.is_manual()),
+            KP => encode(kp, self.gains),
+            KI => encode(ki, self.gains),
+            KD => encode(kd, self.gains),
+            OUTPUT_MIN => encode_bound(min, self.process),
+            OUTPUT_MAX => encode_bound(max, self.process),
+            _ => {
+                let saturation = match controller.saturation() {
+                    Saturation::Unsaturated => 0,
+                    Saturation::Lower => 1,
+                    Saturation::Upper => 2,
+                };
+                saturation | u16::from(controller.is_manual()) << 2
+            }
+        }
+    }
+}
+
+/// Converts a value to a register, scaled, rounded and saturated.
+fn encode<T: FloatCore>(value: T, scale: T) -> u16 {
+    saturate(value * scale, i16::MIN, i16::MAX)
+}
+
+/// Converts an output bound to a register, infinite bounds to the ends of the range.
+fn encode_bound<T: FloatCore>(value: T, scale: T) -> u16 {
+    if value == T::neg_infinity() {
+        i16::MIN as u16
+    } else if value == T::infinity() {
+        i16::MAX as u16
+    } else {
+        saturate(value * scale, i16::MIN + 1, i16::MAX - 1)
+    }
+}
+
+/// Rounds a scaled value to a register, saturated between `min` and `max`.
+fn saturate<T: FloatCore>(scaled: T, min: i16, max: i16) -> u16 {
+    let integer = match scaled.round().to_i16() {
+        Some(integer) => integer.clamp(min, max),
+        // Not a number encodes as zero.
+        None if scaled.is_nan() => 0,
+        None if scaled > T::zero() => max,
+        None => min,
+    };
+    integer as u16
+}
+
+/// Converts a register to a value.
+fn decode<T: FloatCore>(register: u16, scale: T) -> Result<T, Exception> {
+    T::from(register as i16).map(|value| value / scale).ok_or(Exception::IllegalDataValue)
+}
+
+/// Converts an output bound register to a value, the ends of the range to infinite
+/// bounds.
+fn decode_bound<T: FloatCore>(register: u16, scale: T) -> Result<T, Exception> {
+    match register as i16 {
+        i16::MIN => Ok(T::neg_infinity()),
+        i16::MAX => Ok(T::infinity()),
+        _ => decode(register, scale),
+    }
+}
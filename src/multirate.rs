@@ -0,0 +1,90 @@
+use num_traits::float::FloatCore;
+
+use crate::output::Interpolator;
+use crate::pid::PID;
+
+pub use crate::output::Hold;
+
+/// Implementation of a controller that runs at `1 / ratio` of the rate it is called at,
+/// useful to run an outer loop from the same periodic task as a faster inner loop.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiRate<T> {
+    /// Controller stepping once every `ratio` calls.
+    pub controller: PID<T>,
+
+    /// Number of calls per controller step.
+    ratio: u32,
+    /// Number of calls since the last controller step.
+    count: u32,
+    /// Output between controller steps.
+    output: Interpolator<T>,
+}
+
+impl<T: FloatCore> MultiRate<T> {
+    /// Creates a new `MultiRate` controller with a proportional gain of `kp`,
+    /// integral gain of `ki` and derivative gain of `kd`, stepping once every `ratio`
+    /// calls.
+    ///
+    /// `tau` represents the time constant of the derivative low-pass filter.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive
+    /// calls, the controller coefficients are computed for its actual sampling time of
+    /// `ratio * sampling_time` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::multirate::{Hold, MultiRate};
+    ///
+    /// // Called at 1 kHz, controlling at 100 Hz.
+    /// let mut outer = MultiRate::new(1.0, 0.5, 0.0, 0.0, 0.001, 10, 1.0);
+    /// outer.set_hold(Hold::Linear);
+    /// let first = outer.step(0.0);
+    /// let second = outer.step(0.0);
+    /// assert!(second > first);
+    /// ```
+    pub fn new(kp: T, ki: T, kd: T, tau: T, sampling_time: T, ratio: u32, setpoint: T) -> Self {
+        assert!(ratio > 0);
+        let period = sampling_time * T::from(ratio).expect("Unable to cast the ratio");
+
+        Self {
+            controller: PID::new(kp, ki, kd, tau, period, setpoint),
+            ratio,
+            count: 0,
+            output: Interpolator::new(Hold::Zero, ratio),
+        }
+    }
+
+    /// Changes how the output evolves between controller steps.
+    pub fn set_hold(&mut self, hold: Hold) -> &mut Self {
+        self.output.set_hold(hold);
+        self
+    }
+
+    /// Returns whether the next call to [`step`](Self::step) steps the controller.
+    pub fn is_due(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Clears the controller state and restarts the step counting.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+        self.count = 0;
+        self.output.reset();
+    }
+
+    /// Steps the controller if it is due and returns the held or interpolated output.
+    /// It should be called exactly once every `sampling_time` seconds.
+    pub fn step(&mut self, measurement: T) -> T {
+        if self.count == 0 {
+            let output = self.controller.step(measurement);
+            self.output.update(output);
+        }
+        self.count = (self.count + 1) % self.ratio;
+        self.output.sample()
+    }
+}
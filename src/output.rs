@@ -0,0 +1,21 @@
+//! Blocks that shape the controller output before it reaches the actuators.
+
+mod backlash;
+mod heat_cool;
+mod hold;
+mod linearization;
+mod pwm;
+mod quantizer;
+mod shaper;
+mod split_range;
+mod valve;
+
+pub use self::backlash::{Backlash, Direction};
+pub use self::heat_cool::HeatCool;
+pub use self::hold::{Hold, Interpolator};
+pub use self::linearization::Linearization;
+pub use self::pwm::Pwm;
+pub use self::quantizer::Quantizer;
+pub use self::shaper::Shaper;
+pub use self::split_range::{Actuator, SplitRange};
+pub use self::valve::{Characteristic, Valve};
@@ -0,0 +1,97 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Direction in which a command last moved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// The command last increased.
+    Increasing,
+    /// The command last decreased.
+    Decreasing,
+}
+
+/// Implementation of a backlash compensation, which adds an extra displacement when
+/// the command reverses direction so the dead band of the actuator is taken up at once.
+///
+/// The compensated output is offset by half of the backlash in the direction of
+/// motion, a reversal therefore jumps the output by the whole backlash.
+#[derive(Clone, Copy, Debug)]
+pub struct Backlash<T> {
+    /// Width of the actuator dead band.
+    width: T,
+
+    /// Command of the previous step, `None` until the first command arrives.
+    command: Option<T>,
+    /// Direction of the last motion, `None` until the command first moves.
+    direction: Option<Direction>,
+}
+
+impl<T: FloatCore> Backlash<T> {
+    /// Creates a new `Backlash` compensating a dead band `width` units wide.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::{Backlash, Direction};
+    ///
+    /// let mut backlash = Backlash::new(0.5);
+    /// backlash.step(10.0);
+    /// assert_eq!(backlash.step(11.0), 11.25);
+    /// assert_eq!(backlash.step(10.5), 10.25);
+    /// assert_eq!(backlash.direction(), Some(Direction::Decreasing));
+    /// ```
+    pub fn new(width: T) -> Self {
+        assert!(width >= T::zero());
+        Self { width, command: None, direction: None }
+    }
+
+    /// Changes the width of the compensated dead band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is negative.
+    pub fn set_width(&mut self, width: T) -> &mut Self {
+        assert!(width >= T::zero());
+        self.width = width;
+        self
+    }
+
+    /// Returns the direction of the last motion, `None` if the command never moved.
+    pub fn direction(&self) -> Option<Direction> {
+        self.direction
+    }
+
+    /// Returns the offset currently added to the command.
+    pub fn offset(&self) -> T {
+        let half = self.width * constant::half::<T>();
+        match self.direction {
+            Some(Direction::Increasing) => half,
+            Some(Direction::Decreasing) => -half,
+            None => T::zero(),
+        }
+    }
+
+    /// Clears the compensation state.
+    pub fn reset(&mut self) {
+        self.command = None;
+        self.direction = None;
+    }
+
+    /// Compensates a single command.
+    pub fn step(&mut self, command: T) -> T {
+        if let Some(previous) = self.command {
+            if command > previous {
+                self.direction = Some(Direction::Increasing);
+            } else if command < previous {
+                self.direction = Some(Direction::Decreasing);
+            }
+        }
+        self.command = Some(command);
+        command + self.offset()
+    }
+}
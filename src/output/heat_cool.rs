@@ -0,0 +1,69 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of a heat/cool output split, which drives a heater from the positive
+/// side of a controller output and a cooler from its negative side.
+///
+/// Each side has its own gain, as heating and cooling rarely act on the process with
+/// the same strength. A positive dead band leaves a region around zero where neither
+/// acts, a negative one makes them overlap.
+#[derive(Clone, Copy, Debug)]
+pub struct HeatCool<T> {
+    /// Gain applied to the heating side.
+    heat_gain: T,
+    /// Gain applied to the cooling side.
+    cool_gain: T,
+    /// Half of the dead band, negative for an overlap.
+    half_band: T,
+}
+
+impl<T: FloatCore> HeatCool<T> {
+    /// Creates a new `HeatCool` with a heating gain of `heat_gain`, a cooling gain of
+    /// `cool_gain` and a dead band `dead_band` units wide centered on zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either gain is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::HeatCool;
+    ///
+    /// let split = HeatCool::new(1.0, 2.0, 10.0);
+    /// assert_eq!(split.split(50.0), (45.0, 0.0));
+    /// assert_eq!(split.split(3.0), (0.0, 0.0));
+    /// assert_eq!(split.split(-20.0), (0.0, 30.0));
+    /// ```
+    pub fn new(heat_gain: T, cool_gain: T, dead_band: T) -> Self {
+        let mut split = Self { heat_gain: T::zero(), cool_gain: T::zero(), half_band: T::zero() };
+        split.set_gains(heat_gain, cool_gain).set_dead_band(dead_band);
+        split
+    }
+
+    /// Changes the gains of the heating and cooling sides.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either gain is negative.
+    pub fn set_gains(&mut self, heat_gain: T, cool_gain: T) -> &mut Self {
+        assert!(heat_gain >= T::zero() && cool_gain >= T::zero());
+        self.heat_gain = heat_gain;
+        self.cool_gain = cool_gain;
+        self
+    }
+
+    /// Changes the width of the dead band, negative for an overlap.
+    pub fn set_dead_band(&mut self, dead_band: T) -> &mut Self {
+        self.half_band = dead_band * constant::half::<T>();
+        self
+    }
+
+    /// Returns the heating and cooling commands for the controller `output`.
+    pub fn split(&self, output: T) -> (T, T) {
+        let heat = (output - self.half_band).max(T::zero()) * self.heat_gain;
+        let cool = (-output - self.half_band).max(T::zero()) * self.cool_gain;
+        (heat, cool)
+    }
+}
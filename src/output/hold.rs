@@ -0,0 +1,93 @@
+use num_traits::float::FloatCore;
+
+/// How a signal evolves between two updates, when it is consumed faster than it is
+/// produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hold {
+    /// The signal is held constant until the next update.
+    Zero,
+    /// The signal ramps linearly from the previous update to the last one, at the cost
+    /// of one update period of delay.
+    Linear,
+    /// The signal is extrapolated with the slope between the last two updates, without
+    /// delay but overshooting when the slope changes.
+    FirstOrder,
+}
+
+/// Implementation of an interpolator, which provides `ratio` samples of a signal for
+/// each of its updates, for instance a commutation loop reading the output of a
+/// slower controller.
+#[derive(Clone, Copy, Debug)]
+pub struct Interpolator<T> {
+    /// How the signal evolves between updates.
+    hold: Hold,
+    /// Number of samples per update.
+    ratio: T,
+    /// Number of samples since the last update, up to `ratio`.
+    count: T,
+
+    /// Value of the update before the last one.
+    previous: T,
+    /// Value of the last update.
+    current: T,
+}
+
+impl<T: FloatCore> Interpolator<T> {
+    /// Creates a new `Interpolator` expecting `ratio` samples per update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::{Hold, Interpolator};
+    ///
+    /// let mut output = Interpolator::new(Hold::FirstOrder, 4);
+    /// output.update(1.0);
+    /// output.update(2.0);
+    /// let samples: Vec<f32> = (0..4).map(|_| output.sample()).collect();
+    /// assert_eq!(samples, [2.0, 2.25, 2.5, 2.75]);
+    /// ```
+    pub fn new(hold: Hold, ratio: u32) -> Self {
+        assert!(ratio > 0);
+        let ratio = T::from(ratio).expect("Unable to cast the ratio");
+        Self { hold, ratio, count: T::zero(), previous: T::zero(), current: T::zero() }
+    }
+
+    /// Changes how the signal evolves between updates.
+    pub fn set_hold(&mut self, hold: Hold) -> &mut Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Clears the signal history.
+    pub fn reset(&mut self) {
+        self.count = T::zero();
+        self.previous = T::zero();
+        self.current = T::zero();
+    }
+
+    /// Provides a new value of the signal.
+    pub fn update(&mut self, value: T) {
+        self.previous = self.current;
+        self.current = value;
+        self.count = T::zero();
+    }
+
+    /// Returns the next sample of the signal. It should be called `ratio` times
+    /// between two updates.
+    pub fn sample(&mut self) -> T {
+        // Samples beyond the expected ones stay where the next update was due.
+        let count = self.count;
+        self.count = (count + T::one()).min(self.ratio);
+
+        let slope = self.current - self.previous;
+        match self.hold {
+            Hold::Zero => self.current,
+            Hold::Linear => self.previous + slope * ((count + T::one()) / self.ratio).min(T::one()),
+            Hold::FirstOrder => self.current + slope * count / self.ratio,
+        }
+    }
+}
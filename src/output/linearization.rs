@@ -0,0 +1,54 @@
+use num_traits::float::FloatCore;
+
+use crate::lookup;
+
+/// Implementation of an actuator linearization, which maps the desired effect in
+/// physical units to the actuator command producing it.
+///
+/// The actuator is described by `N` breakpoints of its curve, the effect measured at
+/// a number of commands, linearly interpolated between them.
+#[derive(Clone, Copy, Debug)]
+pub struct Linearization<T, const N: usize> {
+    /// Command of each breakpoint, strictly increasing.
+    commands: [T; N],
+    /// Effect of each breakpoint, strictly increasing.
+    effects: [T; N],
+}
+
+impl<T: FloatCore, const N: usize> Linearization<T, N> {
+    /// Creates a new `Linearization` from the actuator curve, where `effects[i]` is the
+    /// effect produced by `commands[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or either the commands or the effects are not strictly
+    /// increasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Linearization;
+    ///
+    /// // Fan airflow in m³/h at a number of duty cycles.
+    /// let fan = Linearization::new([0.0, 25.0, 50.0, 75.0, 100.0], [0.0, 10.0, 80.0, 270.0, 640.0]);
+    /// assert_eq!(fan.command(45.0), 37.5);
+    /// assert_eq!(fan.effect(37.5), 45.0);
+    /// ```
+    pub fn new(commands: [T; N], effects: [T; N]) -> Self {
+        assert!(N > 0);
+        assert!(commands.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(effects.windows(2).all(|pair| pair[0] < pair[1]));
+        Self { commands, effects }
+    }
+
+    /// Returns the actuator command that produces `effect`.
+    pub fn command(&self, effect: T) -> T {
+        lookup::interpolate(&self.effects, &self.commands, effect)
+    }
+
+    /// Returns the effect produced by the actuator `command`, the inverse of
+    /// [`command`](Self::command).
+    pub fn effect(&self, command: T) -> T {
+        lookup::interpolate(&self.commands, &self.effects, command)
+    }
+}
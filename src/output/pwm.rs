@@ -0,0 +1,82 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of a PWM duty converter, which turns a controller output into an
+/// integer compare value for a timer, carrying the truncation error over to the next
+/// period so slow loops do not lose resolution.
+#[derive(Clone, Copy, Debug)]
+pub struct Pwm<T> {
+    /// Timer compare value for a fully on output.
+    top: u32,
+    /// Timer compare value for a fully on output, as a number.
+    scale: T,
+    /// Controller output mapped to a duty of zero.
+    min: T,
+    /// Controller output mapped to a duty of `top`.
+    max: T,
+    /// Truncation error carried over from the previous period, in timer counts.
+    error: T,
+}
+
+impl<T: FloatCore> Pwm<T> {
+    /// Creates a new `Pwm` for a timer counting up to `top`, mapping controller
+    /// outputs in the `[min, max]` interval to duties from zero to `top`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` >= `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Pwm;
+    ///
+    /// let mut pwm = Pwm::new(99, 0.0, 100.0);
+    /// // 40.5% of 99 counts is 40.095, delivered as 40 most of the time.
+    /// let duties: Vec<u32> = (0..20).map(|_| pwm.duty(40.5)).collect();
+    /// assert_eq!(duties.iter().sum::<u32>(), 801);
+    /// ```
+    pub fn new(top: u32, min: T, max: T) -> Self {
+        assert!(min < max);
+        let scale = T::from(top).expect("Unable to cast the timer top value");
+        Self { top, scale, min, max, error: T::zero() }
+    }
+
+    /// Returns the timer compare value for a fully on output.
+    pub fn top(&self) -> u32 {
+        self.top
+    }
+
+    /// Discards the carried truncation error.
+    pub fn reset(&mut self) {
+        self.error = T::zero();
+    }
+
+    /// Returns the timer compare value for the next period given the controller
+    /// `output`.
+    ///
+    /// An output that is not a number gives a duty of zero, leaving the carried
+    /// truncation error unchanged, so a failing controller turns the actuator off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Pwm;
+    ///
+    /// let mut pwm = Pwm::new(100, 0.0, 1.0);
+    /// assert_eq!(pwm.duty(f32::NAN), 0);
+    /// assert_eq!(pwm.duty(0.5), 50);
+    /// ```
+    pub fn duty(&mut self, output: T) -> u32 {
+        if output.is_nan() {
+            return 0;
+        }
+        let top = self.scale;
+        let fraction =
+            num_traits::clamp((output - self.min) / (self.max - self.min), T::zero(), T::one());
+
+        let target = num_traits::clamp(fraction * top + self.error, T::zero(), top);
+        let duty = target.floor();
+        self.error = target - duty;
+        duty.to_u32().unwrap_or(self.top)
+    }
+}
@@ -0,0 +1,72 @@
+use num_traits::float::FloatCore;
+
+/// Implementation of an output quantizer, which rounds commands to the resolution of
+/// the actuator while carrying the rounding error over to the next step, so the long
+/// term average matches the requested command.
+#[derive(Clone, Copy, Debug)]
+pub struct Quantizer<T> {
+    /// Smallest step the actuator can make.
+    resolution: T,
+    /// Rounding error carried over from the previous step.
+    error: T,
+}
+
+impl<T: FloatCore> Quantizer<T> {
+    /// Creates a new `Quantizer` rounding commands to multiples of `resolution`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Quantizer;
+    ///
+    /// // A fan with 16 speed steps, asked for a speed between two of them.
+    /// let mut fan = Quantizer::new(1.0);
+    /// let average = (0..4).map(|_| fan.step(7.25)).sum::<f32>() / 4.0;
+    /// assert_eq!(average, 7.25);
+    /// ```
+    pub fn new(resolution: T) -> Self {
+        assert!(resolution > T::zero());
+        Self { resolution, error: T::zero() }
+    }
+
+    /// Returns the rounding error carried over to the next step.
+    pub fn error(&self) -> T {
+        self.error
+    }
+
+    /// Discards the carried rounding error.
+    pub fn reset(&mut self) {
+        self.error = T::zero();
+    }
+
+    /// Quantizes a single command.
+    ///
+    /// A command that is not finite is returned as is, leaving the carried rounding
+    /// error unchanged, so a single failing step does not poison the next ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Quantizer;
+    ///
+    /// let mut valve = Quantizer::new(0.5);
+    /// assert_eq!(valve.step(1.2), 1.0);
+    /// assert!(valve.step(f32::NAN).is_nan());
+    /// assert!(valve.step(f32::INFINITY).is_infinite());
+    /// assert!((valve.error() - 0.2).abs() < 1e-6);
+    /// assert_eq!(valve.step(1.2), 1.5);
+    /// ```
+    pub fn step(&mut self, command: T) -> T {
+        if !command.is_finite() {
+            return command;
+        }
+        let target = command + self.error;
+        let quantized = (target / self.resolution).round() * self.resolution;
+        self.error = target - quantized;
+        quantized
+    }
+}
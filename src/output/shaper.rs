@@ -0,0 +1,113 @@
+use num_traits::float::{Float, FloatCore};
+
+use crate::constant;
+
+/// Implementation of a second-order output shaper, which limits both the rate and the
+/// acceleration of a command so large mechanical actuators are never jerked.
+///
+/// The shaped command brakes ahead of the target so it settles on it without
+/// overshooting.
+#[derive(Clone, Copy, Debug)]
+pub struct Shaper<T> {
+    /// Largest rate of change of the command, in units per second.
+    max_rate: T,
+    /// Largest acceleration of the command, in units per second squared.
+    max_acceleration: T,
+    /// Sampling time in seconds.
+    sampling_time: T,
+
+    /// Output of the previous step, `None` until the first command arrives.
+    output: Option<T>,
+    /// Rate of change of the output during the previous step.
+    rate: T,
+}
+
+impl<T: Float + FloatCore> Shaper<T> {
+    /// Creates a new `Shaper` limiting the command to `max_rate` units per second and
+    /// `max_acceleration` units per second squared.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_rate`, `max_acceleration` or `sampling_time` are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::Shaper;
+    ///
+    /// // An antenna rotator turning at most 10 °/s, accelerating at most 20 °/s².
+    /// let mut rotator = Shaper::new(10.0, 20.0, 0.1);
+    /// rotator.step(0.0);
+    /// let path: Vec<f64> = (0..20).map(|_| rotator.step(5.0)).collect();
+    /// assert!(path.windows(2).all(|w| w[1] - w[0] <= 10.0 * 0.1 + 1e-9));
+    /// assert!(path.iter().all(|&angle| angle <= 5.0 + 1e-9));
+    /// assert!((path[19] - 5.0).abs() < 1e-9);
+    /// ```
+    pub fn new(max_rate: T, max_acceleration: T, sampling_time: T) -> Self {
+        assert!(sampling_time > T::zero());
+        let mut shaper = Self {
+            max_rate: T::zero(),
+            max_acceleration: T::zero(),
+            sampling_time,
+            output: None,
+            rate: T::zero(),
+        };
+        shaper.set_limits(max_rate, max_acceleration);
+        shaper
+    }
+
+    /// Changes the rate and acceleration limits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_rate` or `max_acceleration` are not positive.
+    pub fn set_limits(&mut self, max_rate: T, max_acceleration: T) -> &mut Self {
+        assert!(max_rate > T::zero() && max_acceleration > T::zero());
+        self.max_rate = max_rate;
+        self.max_acceleration = max_acceleration;
+        self
+    }
+
+    /// Returns the rate of change of the output during the last step, in units per
+    /// second.
+    pub fn rate(&self) -> T {
+        self.rate
+    }
+
+    /// Clears the internal state, the next command is passed through unchanged and
+    /// the output starts at rest.
+    pub fn reset(&mut self) {
+        self.output = None;
+        self.rate = T::zero();
+    }
+
+    /// Shapes a single command.
+    pub fn step(&mut self, command: T) -> T {
+        let previous = match self.output {
+            Some(previous) => previous,
+            None => {
+                self.output = Some(command);
+                return command;
+            }
+        };
+
+        let dt = self.sampling_time;
+        let dv = self.max_acceleration * dt;
+        let distance = Float::abs(command - previous);
+
+        // Fastest rate that can still be brought down to zero, one `dv` per step,
+        // by the time the command is reached.
+        let two = constant::two::<T>();
+        let eight = two * two * two;
+        let steps = (Float::sqrt(T::one() + eight * distance / (dv * dt)) - T::one()) / two;
+        let rate = FloatCore::min(FloatCore::min(distance / dt, dv * steps), self.max_rate);
+        let target = if command < previous { -rate } else { rate };
+
+        self.rate = num_traits::clamp(target, self.rate - dv, self.rate + dv);
+        let output = previous + self.rate * dt;
+        self.output = Some(output);
+        output
+    }
+}
@@ -0,0 +1,89 @@
+use num_traits::float::FloatCore;
+
+/// Sub-range of the controller output assigned to one actuator of a [`SplitRange`].
+#[derive(Clone, Copy, Debug)]
+pub struct Actuator<T> {
+    /// Controller output at which the actuator is fully closed.
+    start: T,
+    /// Controller output at which the actuator is fully open.
+    end: T,
+    /// Commands below this fraction of the stroke are replaced by zero.
+    dead_zone: T,
+}
+
+impl<T: FloatCore> Actuator<T> {
+    /// Creates a new `Actuator` that is fully closed when the controller output is
+    /// `start` and fully open when it is `end`. A `start` larger than `end` results in
+    /// an actuator that opens as the controller output decreases.
+    ///
+    /// Commands below `dead_zone`, a fraction of the stroke, are replaced by zero so
+    /// the actuator is not driven inside its dead band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` equals `end` or `dead_zone` is not in the `[0, 1)` interval.
+    pub fn new(start: T, end: T, dead_zone: T) -> Self {
+        assert!(start != end);
+        assert!(dead_zone >= T::zero() && dead_zone < T::one());
+        Self { start, end, dead_zone }
+    }
+
+    /// Returns the command, as a fraction of the stroke, for the controller output.
+    pub fn command(&self, output: T) -> T {
+        let fraction =
+            num_traits::clamp((output - self.start) / (self.end - self.start), T::zero(), T::one());
+        if fraction < self.dead_zone {
+            T::zero()
+        } else {
+            fraction
+        }
+    }
+}
+
+/// Maps a single controller output onto `N` actuators, each driven over its own
+/// sub-range of the output.
+///
+/// # Examples
+///
+/// ```
+/// use control::output::{Actuator, SplitRange};
+///
+/// // 0-50% opens the cooling valve as the output decreases, 50-100% the heating valve.
+/// let split = SplitRange::new([Actuator::new(50.0, 0.0, 0.0), Actuator::new(50.0, 100.0, 0.05)]);
+///
+/// assert_eq!(split.map(25.0), [0.5, 0.0]);
+/// assert_eq!(split.map(51.0), [0.0, 0.0]);
+/// assert_eq!(split.map(75.0), [0.0, 0.5]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SplitRange<T, const N: usize> {
+    /// Sub-range of each actuator.
+    actuators: [Actuator<T>; N],
+}
+
+impl<T: FloatCore, const N: usize> SplitRange<T, N> {
+    /// Creates a new `SplitRange` with the given actuator sub-ranges.
+    pub fn new(actuators: [Actuator<T>; N]) -> Self {
+        Self { actuators }
+    }
+
+    /// Returns the sub-ranges of the actuators.
+    pub fn actuators(&self) -> &[Actuator<T>; N] {
+        &self.actuators
+    }
+
+    /// Returns the sub-ranges of the actuators for modification.
+    pub fn actuators_mut(&mut self) -> &mut [Actuator<T>; N] {
+        &mut self.actuators
+    }
+
+    /// Returns the command of every actuator, as a fraction of its stroke, for the
+    /// controller output.
+    pub fn map(&self, output: T) -> [T; N] {
+        let mut commands = [T::zero(); N];
+        for (command, actuator) in commands.iter_mut().zip(self.actuators.iter()) {
+            *command = actuator.command(output);
+        }
+        commands
+    }
+}
@@ -0,0 +1,95 @@
+use num_traits::float::{Float, FloatCore};
+
+/// Inherent flow characteristic of a control valve, the flow as a fraction of its
+/// maximum for a given fraction of the valve stroke.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Characteristic<T> {
+    /// The flow is proportional to the stroke.
+    Linear,
+    /// The flow is the square root of the stroke, most of the flow change happens at
+    /// the beginning of the stroke.
+    QuickOpening,
+    /// Each increment of the stroke changes the flow by the same percentage, the flow
+    /// being `rangeability ^ (stroke - 1)`.
+    EqualPercentage(T),
+}
+
+/// Implementation of a valve characteristic compensation, which maps the desired flow
+/// to the stroke producing it, so a flow loop keeps the same effective gain over the
+/// whole stroke.
+///
+/// Flow and stroke are both expressed as fractions in the `[0, 1]` interval.
+#[derive(Clone, Copy, Debug)]
+pub struct Valve<T> {
+    /// Characteristic being compensated.
+    characteristic: Characteristic<T>,
+}
+
+impl<T: Float + FloatCore> Valve<T> {
+    /// Creates a new `Valve` compensating the given characteristic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rangeability of an equal percentage characteristic is not larger
+    /// than one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::output::{Characteristic, Valve};
+    ///
+    /// let valve = Valve::new(Characteristic::EqualPercentage(50.0_f64));
+    /// assert_eq!(valve.stroke(1.0), 1.0);
+    /// assert!(valve.stroke(0.02).abs() < 1e-12);
+    /// ```
+    pub fn new(characteristic: Characteristic<T>) -> Self {
+        let mut valve = Self { characteristic: Characteristic::Linear };
+        valve.set_characteristic(characteristic);
+        valve
+    }
+
+    /// Changes the characteristic being compensated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rangeability of an equal percentage characteristic is not larger
+    /// than one.
+    pub fn set_characteristic(&mut self, characteristic: Characteristic<T>) -> &mut Self {
+        if let Characteristic::EqualPercentage(rangeability) = characteristic {
+            assert!(rangeability > T::one());
+        }
+        self.characteristic = characteristic;
+        self
+    }
+
+    /// Returns the characteristic being compensated.
+    pub fn characteristic(&self) -> Characteristic<T> {
+        self.characteristic
+    }
+
+    /// Returns the stroke that produces `flow`.
+    pub fn stroke(&self, flow: T) -> T {
+        let flow = num_traits::clamp(flow, T::zero(), T::one());
+        match self.characteristic {
+            Characteristic::Linear => flow,
+            Characteristic::QuickOpening => flow * flow,
+            Characteristic::EqualPercentage(rangeability) => {
+                // Flows below the minimum controllable one are out of reach.
+                let stroke = T::one() + Float::ln(flow) / Float::ln(rangeability);
+                FloatCore::max(stroke, T::zero())
+            }
+        }
+    }
+
+    /// Returns the flow produced by `stroke`, the inverse of [`stroke`](Self::stroke).
+    pub fn flow(&self, stroke: T) -> T {
+        let stroke = num_traits::clamp(stroke, T::zero(), T::one());
+        match self.characteristic {
+            Characteristic::Linear => stroke,
+            Characteristic::QuickOpening => Float::sqrt(stroke),
+            Characteristic::EqualPercentage(rangeability) => {
+                Float::powf(rangeability, stroke - T::one())
+            }
+        }
+    }
+}
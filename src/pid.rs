@@ -1,18 +1,20 @@
+use core::fmt;
+use core::ops::{Bound, RangeBounds};
+
 use num_traits::float::FloatCore;
 
+use crate::constant;
+use crate::filter;
+
 /// Implementation of a proportional–integral–derivative controller.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PID<T> {
     /// Desired setpoint.
     pub setpoint: T,
 
-    /// Error value from the previous update.
-    error: T,
-    /// Integral value from the previous update.
-    integral: T,
-    /// Derivative value from the previous update.
-    derivative: T,
-    /// Previous measurement value.
-    measurement: T,
+    /// State carried from one step to the next.
+    state: State<T>,
 
     /// Proportional coefficient.
     p: T,
@@ -22,16 +24,278 @@ pub struct PID<T> {
     d: T,
     /// Coefficient for the derivative low-pass filter.
     t: T,
+    /// Integral gain, as given by the user.
+    ki: T,
+    /// Derivative gain, applied directly when the caller supplies the rate of change.
+    kd: T,
 
     /// Lower bound of the integral term.
     imin: T,
     /// Upper bound of the integral term.
     imax: T,
+    /// Part of the integral term kept from one step to the next, one for a classic
+    /// integral.
+    fade: T,
 
     /// Lower bound of the controller output.
     omin: T,
     /// Upper bound of the controller output.
     omax: T,
+    /// Width of the smooth transition towards the output bounds, zero for a hard clamp.
+    knee: T,
+    /// Constant offset added to the controller output.
+    bias: T,
+    /// Whether a larger control effort corresponds to a smaller output.
+    inverted: bool,
+    /// Largest increase of the controller output per step.
+    rise: T,
+    /// Largest decrease of the controller output per step.
+    fall: T,
+    /// Time difference in seconds between two consecutive step operations.
+    sampling_time: T,
+
+    /// Output imposed by the user while in manual mode.
+    manual: Option<T>,
+    /// Direction in which the integral term is not allowed to move.
+    inhibit: Saturation,
+    /// Number of consecutive steps the error must keep its sign before the integral
+    /// term moves.
+    persistence: u32,
+}
+
+/// Indicates whether a value had to be clamped to one of its bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Saturation {
+    /// The value is within its bounds.
+    Unsaturated,
+    /// The value was clamped to its lower bound.
+    Lower,
+    /// The value was clamped to its upper bound.
+    Upper,
+}
+
+/// The state a [`PID`] carries from one step to the next, everything else being its
+/// configuration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct State<T> {
+    /// Error value from the previous step.
+    pub error: T,
+    /// Integral value from the previous step.
+    pub integral: T,
+    /// Derivative value from the previous step.
+    pub derivative: T,
+    /// Feedforward value from the previous step.
+    pub feedforward: T,
+    /// Previous measurement value, `None` before the first step.
+    pub measurement: Option<T>,
+    /// Control effort of the previous step, before it is mirrored by an inverted
+    /// output.
+    pub output: T,
+    /// Whether the output of the previous step was clamped.
+    pub saturation: Saturation,
+    /// Number of consecutive steps the output was clamped in the same direction.
+    pub saturation_steps: u32,
+    /// Whether the integral term of the previous step was clamped.
+    pub integral_saturation: Saturation,
+    /// Number of consecutive steps the integral term was clamped in the same
+    /// direction.
+    pub integral_saturation_steps: u32,
+    /// Whether the output of the previous step was held back by a rate limit.
+    pub rate_limit: Saturation,
+    /// Number of consecutive steps the error kept the same sign, zero while it is zero.
+    pub sign_steps: u32,
+}
+
+impl<T: FloatCore> Default for State<T> {
+    fn default() -> Self {
+        Self {
+            error: T::zero(),
+            integral: T::zero(),
+            derivative: T::zero(),
+            feedforward: T::zero(),
+            measurement: None,
+            output: T::zero(),
+            saturation: Saturation::Unsaturated,
+            saturation_steps: 0,
+            integral_saturation: Saturation::Unsaturated,
+            integral_saturation_steps: 0,
+            rate_limit: Saturation::Unsaturated,
+            sign_steps: 0,
+        }
+    }
+}
+
+/// The input of a single step of a [`PID`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Input<T> {
+    /// Measurement of the process variable.
+    pub measurement: T,
+    /// Rate of change of the measurement, in units per second, replacing its filtered
+    /// difference as with [`PID::step_with_rate`].
+    pub rate: Option<T>,
+    /// Feedforward term added to the output before it is clamped.
+    pub feedforward: T,
+}
+
+/// Why the parameters given to [`PID::try_new`] cannot make a working controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParameterError {
+    /// A gain, the time constant, the sampling time or the setpoint is infinite or not
+    /// a number.
+    NotFinite,
+    /// The sampling time is zero or negative.
+    SamplingTime,
+    /// The time constant of the derivative filter is negative, which makes its pole
+    /// diverge or leave the unit circle.
+    Tau,
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParameterError::NotFinite => "a PID parameter is infinite or not a number",
+            ParameterError::SamplingTime => "the sampling time of a PID must be positive",
+            ParameterError::Tau => {
+                "the derivative filter time constant of a PID must not be negative"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParameterError {}
+
+/// A configuration of a [`PID`] that works but is likely not what was meant,
+/// reported by [`PID::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Warning {
+    /// The derivative filter time constant is shorter than half the sampling time, so
+    /// the filter pole is negative and the derivative term rings instead of being
+    /// smoothed.
+    DerivativeFilter,
+    /// The derivative time is shorter than the sampling time, so the derivative term
+    /// mostly amplifies measurement noise.
+    DerivativeTime,
+    /// The integral time is shorter than the sampling time, so the integral term
+    /// outweighs the proportional term within a single step.
+    IntegralTime,
+    /// The integral term and the output are both unbounded, so nothing prevents
+    /// integral windup.
+    Windup,
+    /// The integral bounds lie entirely outside the output bounds, so the integral
+    /// term alone saturates the output.
+    IntegralBounds,
+    /// The output bounds are equal, so the output never moves.
+    OutputRange,
+}
+
+/// Every warning, in the order of their bits in [`Warnings`].
+const WARNINGS: [Warning; 6] = [
+    Warning::DerivativeFilter,
+    Warning::DerivativeTime,
+    Warning::IntegralTime,
+    Warning::Windup,
+    Warning::IntegralBounds,
+    Warning::OutputRange,
+];
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Warning::DerivativeFilter => "tau < dt/2: derivative filter is ineffective",
+            Warning::DerivativeTime => "kd/kp < dt: derivative term mostly amplifies noise",
+            Warning::IntegralTime => "kp/ki < dt: integral term dominates every step",
+            Warning::Windup => "integral and output are unbounded: nothing prevents windup",
+            Warning::IntegralBounds => "integral limits lie outside the output limits",
+            Warning::OutputRange => "output limits are equal: output never moves",
+        };
+        f.write_str(message)
+    }
+}
+
+/// The set of warnings returned by [`PID::validate`], iterating over them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Warnings {
+    /// One bit per warning, indexed as in `WARNINGS`.
+    bits: u8,
+}
+
+impl Warnings {
+    /// Returns whether there is no warning.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns whether `warning` is in the set.
+    pub fn contains(&self, warning: Warning) -> bool {
+        self.bits & (1 << warning as u8) != 0
+    }
+
+    /// Adds `warning` to the set.
+    fn insert(&mut self, warning: Warning) {
+        self.bits |= 1 << warning as u8;
+    }
+}
+
+impl Iterator for Warnings {
+    type Item = Warning;
+
+    fn next(&mut self) -> Option<Warning> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = self.bits.trailing_zeros() as usize;
+        self.bits &= self.bits - 1;
+        Some(WARNINGS[index])
+    }
+}
+
+/// Snapshot of a single step of a [`PID`] controller.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StepRecord<T> {
+    /// Setpoint of the step.
+    pub setpoint: T,
+    /// Measurement of the step.
+    pub measurement: T,
+    /// Proportional term.
+    pub proportional: T,
+    /// Integral term.
+    pub integral: T,
+    /// Derivative term.
+    pub derivative: T,
+    /// Feedforward term.
+    pub feedforward: T,
+    /// Controller output.
+    pub output: T,
+    /// Whether the output had to be clamped.
+    pub saturation: Saturation,
+    /// Whether the output was held back by a rate limit.
+    pub rate_limit: Saturation,
+    /// Whether the controller was in manual mode.
+    pub manual: bool,
+}
+
+/// A receiver of the [`StepRecord`] of every step of an [`Observed`] controller, for
+/// instance to stream telemetry to a debug link, a queue or a logger.
+///
+/// It is implemented for closures taking a record and for
+/// [`Trace`](crate::diagnostics::Trace).
+pub trait StepObserver<T> {
+    /// Called after each step of the controller with its record.
+    fn on_step(&mut self, record: &StepRecord<T>);
+}
+
+impl<T, F: FnMut(&StepRecord<T>)> StepObserver<T> for F {
+    fn on_step(&mut self, record: &StepRecord<T>) {
+        self(record)
+    }
 }
 
 impl<T: FloatCore> PID<T> {
@@ -46,85 +310,1036 @@ impl<T: FloatCore> PID<T> {
     /// # Examples
     ///
     /// ```
+    /// use control::pid::PID;
+    ///
+    /// // A derivative filter pole of zero, the derivative is a plain difference.
+    /// let mut pid = PID::new(2.0, 1.0, 0.5, 0.25, 0.5, 3.0);
     ///
+    /// // The first measurement does not kick the derivative term.
+    /// assert_eq!(pid.step(1.0), 4.5);
+    /// let record = pid.record();
+    /// assert_eq!((record.proportional, record.integral, record.derivative), (4.0, 0.5, 0.0));
+    ///
+    /// // The integral term adds the trapezoid from the previous error and the
+    /// // derivative term follows the change of the measurement.
+    /// assert_eq!(pid.step(2.0), 2.25);
+    /// let record = pid.record();
+    /// assert_eq!((record.proportional, record.integral, record.derivative), (2.0, 1.25, -1.0));
     /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     pub fn new(kp: T, ki: T, kd: T, tau: T, sampling_time: T, setpoint: T) -> Self {
-        let two = T::from(2.0_f32).expect("Unable to cast from 2.0");
-        let half = T::from(0.5_f32).expect("Unable to cast from 0.5");
+        let half = constant::half::<T>();
+        let t = filter::pole(tau, sampling_time);
 
         Self {
             setpoint,
 
-            error: T::zero(),
-            integral: T::zero(),
-            derivative: T::zero(),
-            measurement: T::zero(),
+            state: State::default(),
 
             p: kp,
             i: half * ki * sampling_time,
-            d: -two * kd,
-            t: (two * tau - sampling_time) / (two * tau + sampling_time),
+            d: -kd * (T::one() - t) / sampling_time,
+            t,
+            ki,
+            kd,
 
             imin: T::neg_infinity(),
             imax: T::infinity(),
+            fade: T::one(),
 
             omin: T::neg_infinity(),
             omax: T::infinity(),
+            knee: T::zero(),
+            bias: T::zero(),
+            inverted: false,
+            rise: T::infinity(),
+            fall: T::infinity(),
+            sampling_time,
+
+            manual: None,
+            inhibit: Saturation::Unsaturated,
+            persistence: 0,
+        }
+    }
+
+    /// Creates a new `PID` like [`new`](Self::new), after checking the parameters
+    /// can make a working controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::{ParameterError, PID};
+    ///
+    /// assert!(PID::try_new(1.0, 0.5, 0.1, 0.01, 0.001, 0.0).is_ok());
+    ///
+    /// let error = PID::try_new(1.0, 0.5, 0.1, 0.01, 0.0, 0.0).unwrap_err();
+    /// assert_eq!(error, ParameterError::SamplingTime);
+    /// assert_eq!(error.to_string(), "the sampling time of a PID must be positive");
+    ///
+    /// let error = PID::try_new(1.0, 0.5, 0.1, -0.0005, 0.001, 0.0).unwrap_err();
+    /// assert_eq!(error, ParameterError::Tau);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn try_new(
+        kp: T,
+        ki: T,
+        kd: T,
+        tau: T,
+        sampling_time: T,
+        setpoint: T,
+    ) -> Result<Self, ParameterError> {
+        let parameters = [kp, ki, kd, tau, sampling_time, setpoint];
+        if !parameters.iter().all(|parameter| parameter.is_finite()) {
+            return Err(ParameterError::NotFinite);
+        }
+        if sampling_time <= T::zero() {
+            return Err(ParameterError::SamplingTime);
+        }
+        if tau < T::zero() {
+            return Err(ParameterError::Tau);
         }
+        let pid = Self::new(kp, ki, kd, tau, sampling_time, setpoint);
+        // Gains near the largest representable values can still overflow once
+        // discretized.
+        if !(pid.i.is_finite() && pid.d.is_finite()) {
+            return Err(ParameterError::NotFinite);
+        }
+        Ok(pid)
+    }
+
+    /// Creates a new `PID` with a starting point for a slow heater loop, whose
+    /// temperature approaches its final value with a time constant of about
+    /// `time_constant` seconds, to refine once the loop runs.
+    ///
+    /// The output is the heater power in percent, bounded to the `[0, 100]` interval
+    /// as is the integral term, so it cannot wind up while the heater is off or at full
+    /// power. The gains assume a process gain of one degree per percent of power and a
+    /// dead time of a tenth of the time constant, for a loop reaching the setpoint
+    /// with little overshoot at the pace of the process. The derivative term is light and
+    /// filtered, not to amplify the quantization of temperature sensors. The setpoint
+    /// starts at zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::simulation::{FirstOrder, Sample, Simulation};
+    ///
+    /// let pid = PID::for_thermal_process(1.0, 120.0);
+    /// let oven = FirstOrder::new(1.0, 120.0, 1.0);
+    /// let mut simulation = Simulation::new(pid, oven);
+    ///
+    /// let mut trajectory = [Sample::default(); 1200];
+    /// simulation.run(&mut trajectory, |_| 60.0, |_| 0.0);
+    /// let peak = trajectory.iter().fold(0.0_f64, |peak, sample| peak.max(sample.measurement));
+    /// assert!(peak < 63.0);
+    /// assert!((trajectory[1199].measurement - 60.0).abs() < 0.5);
+    /// ```
+    pub fn for_thermal_process(sampling_time: T, time_constant: T) -> Self {
+        let dead_time = time_constant / constant::ten::<T>();
+        // Skogestad's rules for a first-order process with dead time, with the closed
+        // loop as fast as the dead time allows.
+        let kp = time_constant / (constant::two::<T>() * dead_time);
+        let integral_time =
+            time_constant.min(constant::four::<T>() * constant::two::<T>() * dead_time);
+        let derivative_time = dead_time * constant::half::<T>();
+        let tau = (derivative_time / constant::ten::<T>()).max(sampling_time);
+        let zero = T::zero();
+
+        let mut pid =
+            Self::new(kp, kp / integral_time, kp * derivative_time, tau, sampling_time, zero);
+        let hundred = constant::hundred::<T>();
+        pid.bound_output(zero..=hundred).bound_integral(zero..=hundred);
+        pid
+    }
+
+    /// Changes the proportional, integral and derivative gains, keeping the time
+    /// constant of the derivative low-pass filter.
+    ///
+    /// The integral term is kept as is, so changing the integral gain does not bump
+    /// the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// pid.set_gains(2.0, 0.0, 0.0);
+    /// assert_eq!(pid.step(8.0), 4.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn set_gains(&mut self, kp: T, ki: T, kd: T) -> &mut Self {
+        debug!("PID gains changed to kp={} ki={} kd={}", float(kp), float(ki), float(kd));
+        let half = constant::half::<T>();
+        self.p = kp;
+        self.i = half * ki * self.sampling_time;
+        self.d = -kd * (T::one() - self.t) / self.sampling_time;
+        self.ki = ki;
+        self.kd = kd;
+        self
+    }
+
+    /// Returns the proportional, integral and derivative gains.
+    pub fn gains(&self) -> (T, T, T) {
+        (self.p, self.ki, self.kd)
+    }
+
+    /// Returns the discretized proportional, integral and derivative coefficients and
+    /// the pole of the derivative filter.
+    pub(crate) fn coefficients(&self) -> (T, T, T, T) {
+        (self.p, self.i, self.d, self.t)
+    }
+
+    /// Cross-checks the sampling time against the derivative filter, the gains and the
+    /// bounds, returning the configurations that are likely mistakes so commissioning
+    /// tools can surface them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::{Warning, PID};
+    ///
+    /// let mut pid = PID::new(1.0, 0.5, 0.2, 0.001, 0.01, 0.0);
+    /// pid.bound_output(0.0..=100.0);
+    /// assert_eq!(pid.validate().collect::<Vec<_>>(), [Warning::DerivativeFilter]);
+    /// assert_eq!(
+    ///     Warning::DerivativeFilter.to_string(),
+    ///     "tau < dt/2: derivative filter is ineffective"
+    /// );
+    ///
+    /// let pid = PID::new(1.0, 0.5, 0.2, 0.02, 0.01, 0.0);
+    /// assert!(pid.validate().contains(Warning::Windup));
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn validate(&self) -> Warnings {
+        let mut warnings = Warnings::default();
+        let kp = self.p.abs();
+        if self.kd != T::zero() {
+            if self.t < T::zero() {
+                warnings.insert(Warning::DerivativeFilter);
+            }
+            if kp != T::zero() && self.kd.abs() < kp * self.sampling_time {
+                warnings.insert(Warning::DerivativeTime);
+            }
+        }
+        if self.ki != T::zero() {
+            if kp != T::zero() && kp < self.ki.abs() * self.sampling_time {
+                warnings.insert(Warning::IntegralTime);
+            }
+            let unbounded = |min: T, max: T| min.is_infinite() && max.is_infinite();
+            if unbounded(self.imin, self.imax) && unbounded(self.omin, self.omax) {
+                warnings.insert(Warning::Windup);
+            }
+        }
+        if self.imin > self.omax || self.imax < self.omin {
+            warnings.insert(Warning::IntegralBounds);
+        }
+        if self.omin == self.omax {
+            warnings.insert(Warning::OutputRange);
+        }
+        warnings
     }
 
     /// Indicates that the integral term should be restricted to a certain interval.
     /// Useful to prevent [integral windup].
     ///
+    /// Any kind of range is accepted, an unbounded side leaves the integral term free
+    /// in that direction. Exclusive ends are treated as inclusive ones.
+    ///
     /// [integral windup]: https://en.wikipedia.org/wiki/PID_controller#Integral_windup
     ///
     /// # Panics
     ///
-    /// Panics if `min` > `max`.
+    /// Panics if the lower bound is larger than the upper bound, unless the `no-panic`
+    /// feature is enabled, in which case the bounds are left unchanged.
     ///
     /// # Examples
     ///
     /// ```
+    /// use control::pid::PID;
     ///
+    /// let mut pid = PID::new(1.0, 0.5, 0.0, 0.0, 0.1, 20.0);
+    /// pid.bound_integral(-10.0..=10.0);
+    /// // Only keep the integral term from going negative.
+    /// pid.bound_integral(0.0..);
     /// ```
-    pub fn bound_integral(&mut self, min: T, max: T) -> &mut Self {
-        assert!(min <= max);
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn bound_integral<R: RangeBounds<T>>(&mut self, range: R) -> &mut Self {
+        let (min, max) = bounds(range);
+        if !precondition(min <= max) {
+            return self;
+        }
         self.imin = min;
         self.imax = max;
         self
     }
 
+    /// Returns the lower and upper bounds of the integral term, infinite when
+    /// unbounded.
+    pub fn integral_bounds(&self) -> (T, T) {
+        (self.imin, self.imax)
+    }
+
     /// Indicates that the controller output should be restricted to a certain interval.
     ///
+    /// Any kind of range is accepted, an unbounded side leaves the output free in that
+    /// direction. Exclusive ends are treated as inclusive ones.
+    ///
     /// # Panics
     ///
-    /// Panics if `min` > `max`.
+    /// Panics if the lower bound is larger than the upper bound, unless the `no-panic`
+    /// feature is enabled, in which case the bounds are left unchanged.
     ///
     /// # Examples
     ///
     /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.5, 0.0, 0.0, 0.1, 20.0);
+    /// pid.bound_output(0.0..=100.0);
+    /// assert_eq!(pid.step(-200.0), 100.0);
     ///
+    /// pid.bound_output(..=50.0);
+    /// assert_eq!(pid.step(-200.0), 50.0);
     /// ```
-    pub fn bound_output(&mut self, min: T, max: T) -> &mut Self {
-        assert!(min <= max);
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn bound_output<R: RangeBounds<T>>(&mut self, range: R) -> &mut Self {
+        let (min, max) = bounds(range);
+        if !precondition(min <= max) {
+            return self;
+        }
         self.omin = min;
         self.omax = max;
         self
     }
 
+    /// Returns the lower and upper bounds of the output, infinite when unbounded.
+    pub fn output_bounds(&self) -> (T, T) {
+        (self.omin, self.omax)
+    }
+
+    /// Indicates that a larger control effort corresponds to a smaller output, as for
+    /// a valve that closes as its command increases.
+    ///
+    /// Within bounded outputs, the effort is mirrored within the bounds, otherwise it
+    /// is negated. The gains keep the sign they would have for a direct acting
+    /// actuator, and the anti-windup, bias, rate limits and [`saturation`] all work on
+    /// the control effort, before the inversion.
+    ///
+    /// [`saturation`]: Self::saturation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(10.0, 0.0, 0.0, 0.0, 0.1, 50.0);
+    /// pid.bound_output(0.0..=100.0).invert_output(true);
+    /// assert_eq!(pid.step(45.0), 50.0);
+    /// assert_eq!(pid.step(40.0), 0.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn invert_output(&mut self, inverted: bool) -> &mut Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Replaces the hard clamp of the output by a smooth saturation, so the output
+    /// approaches its bounds gradually. The output starts bending away from the
+    /// unclamped value `knee` units before each bound, and reaches the bound when the
+    /// unclamped value is `knee` units beyond it.
+    ///
+    /// A `knee` of zero restores the hard clamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `knee` is negative, unless the `no-panic` feature is enabled, in which
+    /// case the knee is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 0.0);
+    /// pid.bound_output(-10.0..=10.0).soften_output(2.0);
+    /// assert_eq!(pid.step(-5.0), 5.0);
+    /// assert_eq!(pid.step(-10.0), 9.5);
+    /// assert_eq!(pid.step(-12.0), 10.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn soften_output(&mut self, knee: T) -> &mut Self {
+        if !precondition(knee >= T::zero()) {
+            return self;
+        }
+        self.knee = knee;
+        self
+    }
+
+    /// Indicates that the controller output may rise by at most `rise` and fall by at
+    /// most `fall` units per second. Either can be infinite to leave that direction
+    /// unrestricted.
+    ///
+    /// The integral term is held while a rate limit keeps the output from following it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rise` or `fall` are not positive, unless the `no-panic` feature is
+    /// enabled, in which case the rate limits are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// // A heater that ramps up slowly but may be cut instantly.
+    /// let mut pid = PID::new(10.0, 0.0, 0.0, 0.0, 0.5, 80.0);
+    /// pid.limit_rate(2.0, f32::INFINITY);
+    /// assert_eq!(pid.step(20.0), 1.0);
+    /// assert_eq!(pid.step(90.0), -100.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn limit_rate(&mut self, rise: T, fall: T) -> &mut Self {
+        if !precondition(rise > T::zero() && fall > T::zero()) {
+            return self;
+        }
+        self.rise = rise * self.sampling_time;
+        self.fall = fall * self.sampling_time;
+        self
+    }
+
+    /// Returns whether the output of the last step was held back by a rate limit,
+    /// [`Saturation::Upper`] meaning it wanted to rise faster than allowed.
+    pub fn rate_limit(&self) -> Saturation {
+        self.state.rate_limit
+    }
+
+    /// Adds a constant `bias` to the controller output, such as the hover throttle of a
+    /// drone or the nominal opening of a valve under proportional only control.
+    ///
+    /// The bias is added before the output is clamped and is accounted for by the
+    /// anti-windup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.1, 1.0);
+    /// pid.set_bias(50.0);
+    /// assert_eq!(pid.step(1.0), 50.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn set_bias(&mut self, bias: T) -> &mut Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Switches the controller to manual mode, where every step outputs `output`
+    /// regardless of the measurement. Can be called again to change the output.
+    ///
+    /// While in manual mode the integral term is continuously recalculated so that
+    /// the return to automatic mode is bumpless.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn set_manual(&mut self, output: T) -> &mut Self {
+        if self.manual.is_none() {
+            debug!("PID switched to manual mode with output {}", float(output));
+        } else {
+            trace!("PID manual output changed to {}", float(output));
+        }
+        self.manual = Some(output);
+        self
+    }
+
+    /// Switches the controller back to automatic mode.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn set_automatic(&mut self) -> &mut Self {
+        if self.manual.is_some() {
+            debug!("PID switched to automatic mode");
+        }
+        self.manual = None;
+        self
+    }
+
+    /// Returns whether the controller is in manual mode.
+    pub fn is_manual(&self) -> bool {
+        self.manual.is_some()
+    }
+
+    /// Prevents the integral term from moving in the given direction, for instance
+    /// because a downstream element is saturated there, until called again with
+    /// [`Saturation::Unsaturated`].
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn inhibit_integral(&mut self, direction: Saturation) -> &mut Self {
+        self.inhibit = direction;
+        self
+    }
+
+    /// Only lets the integral term move once the error has kept the same sign for
+    /// `steps` consecutive steps, so zero-mean noise around the setpoint does not make
+    /// it jitter while a persistent offset is still corrected. Zero or one disables the
+    /// gating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(0.0, 1.0, 0.0, 0.0, 1.0, 0.0);
+    /// pid.gate_integral(3);
+    /// // Noise around the setpoint.
+    /// for measurement in [1.0, -1.0, 1.0] {
+    ///     assert_eq!(pid.step(measurement), 0.0);
+    /// }
+    /// // A persistent offset, integrated from its third step.
+    /// assert_eq!(pid.step(-1.0), 0.0);
+    /// assert_eq!(pid.step(-1.0), 0.0);
+    /// assert_eq!(pid.step(-1.0), 1.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn gate_integral(&mut self, steps: u32) -> &mut Self {
+        self.persistence = steps;
+        self
+    }
+
+    /// Makes the integral term forget old errors, fading them with a time constant of
+    /// `memory` seconds, so a large disturbance long gone does not leave a long
+    /// recovery tail. An infinite memory gives back the classic integral.
+    ///
+    /// A constant error is then no longer fully corrected, the integral term settling
+    /// at about the error integrated over `memory` seconds, so the memory should be
+    /// several times the closed-loop time constant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `memory` is not positive, unless the `no-panic` feature is enabled, in
+    /// which case the memory is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(0.0, 2.0, 0.0, 0.0, 1.0, 0.0);
+    /// pid.fade_integral(1.5);
+    /// // A short disturbance, then the errors it left fade away.
+    /// assert_eq!(pid.step(-1.0), 1.0);
+    /// assert_eq!(pid.step(0.0), 1.5);
+    /// assert_eq!(pid.step(0.0), 0.75);
+    /// assert_eq!(pid.step(0.0), 0.375);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn fade_integral(&mut self, memory: T) -> &mut Self {
+        if !precondition(memory > T::zero()) {
+            return self;
+        }
+        self.fade = if memory.is_infinite() {
+            T::one()
+        } else {
+            filter::pole(memory, self.sampling_time).max(T::zero())
+        };
+        self
+    }
+
+    /// Recomputes the integral term so that the last step would have produced
+    /// `output`, used to keep a controller whose output is not being applied ready to
+    /// take over bumplessly.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn track(&mut self, output: T) -> &mut Self {
+        self.state = self.tracking(self.state, self.mirror(output));
+        self
+    }
+
+    /// Reports the output `actual` that was really applied after the last step, when
+    /// a downstream limiter, an interlock or a slow actuator kept the commanded output
+    /// from being realized.
+    ///
+    /// If it differs from the commanded output, the integral term is back-calculated
+    /// so that the last step would have produced `actual`, which keeps the integral
+    /// from winding up against a limit the controller does not know about. Reporting an
+    /// output equal to the commanded one has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0_f32, 1.0, 0.0, 0.0, 0.1, 10.0);
+    /// let commanded = pid.step(0.0);
+    /// // A safety interlock limits the heater to 5.
+    /// pid.report_actual_output(commanded.min(5.0));
+    /// assert_eq!(pid.output(), 5.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn report_actual_output(&mut self, actual: T) -> &mut Self {
+        let effort = self.mirror(actual);
+        if effort != self.state.output {
+            self.state = self.tracking(self.state, effort);
+        }
+        self
+    }
+
+    /// Returns the output of the last step.
+    pub fn output(&self) -> T {
+        self.mirror(self.state.output)
+    }
+
+    /// Returns whether the output of the last step had to be clamped.
+    pub fn saturation(&self) -> Saturation {
+        self.state.saturation
+    }
+
+    /// Returns the number of consecutive steps, up to and including the last one, for
+    /// which the output was clamped in the direction given by
+    /// [`saturation`](Self::saturation).
+    ///
+    /// A count that keeps growing points to a stuck or undersized actuator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::{Saturation, PID};
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 100.0);
+    /// pid.bound_output(0.0..=10.0);
+    /// for _ in 0..5 {
+    ///     pid.step(0.0);
+    /// }
+    /// assert_eq!(pid.saturation(), Saturation::Upper);
+    /// assert_eq!(pid.saturation_steps(), 5);
+    ///
+    /// pid.step(95.0);
+    /// assert_eq!(pid.saturation_steps(), 0);
+    /// ```
+    pub fn saturation_steps(&self) -> u32 {
+        self.state.saturation_steps
+    }
+
+    /// Returns whether the integral term of the last step had to be clamped to the
+    /// bounds given to [`bound_integral`](Self::bound_integral).
+    pub fn integral_saturation(&self) -> Saturation {
+        self.state.integral_saturation
+    }
+
+    /// Returns the number of consecutive steps, up to and including the last one, for
+    /// which the integral term was clamped in the direction given by
+    /// [`integral_saturation`](Self::integral_saturation).
+    pub fn integral_saturation_steps(&self) -> u32 {
+        self.state.integral_saturation_steps
+    }
+
+    /// Returns a snapshot of the last step.
+    ///
+    /// The terms are the ones of the control effort, before the output is clamped,
+    /// mirrored or rate limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// pid.step(8.0);
+    /// let record = pid.record();
+    /// assert_eq!(record.proportional, 4.0);
+    /// assert_eq!(record.output, 4.0);
+    /// ```
+    pub fn record(&self) -> StepRecord<T> {
+        StepRecord {
+            setpoint: self.setpoint,
+            measurement: self.state.measurement.unwrap_or_else(T::zero),
+            proportional: self.p * self.state.error,
+            integral: self.state.integral,
+            derivative: self.state.derivative,
+            feedforward: self.state.feedforward,
+            output: self.output(),
+            saturation: self.state.saturation,
+            rate_limit: self.state.rate_limit,
+            manual: self.manual.is_some(),
+        }
+    }
+
+    /// Clears the controller state, as if it had just been created. The mode,
+    /// setpoint, gains and bounds are kept.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn reset(&mut self) {
+        self.state = State::default();
+    }
+
     /// Performs a single step of the control loop. It should be called exactly
     /// once every `sampling_time` seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0_f32, 0.0, 0.0, 0.0, 0.1, 0.0);
+    /// // A non-finite measurement gives a non-finite output, without panicking on the
+    /// // following steps.
+    /// assert!(!pid.step(f32::INFINITY).is_finite());
+    /// pid.step(0.0);
+    /// pid.step(f32::MAX);
+    /// pid.step(-f32::MAX);
+    /// pid.step(0.0);
+    ///
+    /// pid.reset();
+    /// assert_eq!(pid.step(0.5), -0.5);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     pub fn step(&mut self, measurement: T) -> T {
+        self.advance(Input { measurement, rate: None, feedforward: T::zero() })
+    }
+
+    /// Performs a single step of the control loop, an alias of [`PID::step`] kept for
+    /// compatibility with the former name of the method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::PID;
+    ///
+    /// let mut pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// assert_eq!(pid.update(8.0), 4.0);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn update(&mut self, measurement: T) -> T {
+        self.step(measurement)
+    }
+
+    /// Performs a single step of the control loop, adding `feedforward` to the output
+    /// before it is clamped. It should be called exactly once every `sampling_time`
+    /// seconds.
+    ///
+    /// The anti-windup accounts for the feedforward term, so the integral term does not
+    /// wind up while the feedforward alone saturates the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    ///
+    /// let mut pid = PID::new(1.0_f64, 1.0, 0.0, 0.0, 0.1, 10.0);
+    /// pid.bound_output(0.0..=100.0);
+    /// assert_eq!(pid.step_with_feedforward(0.0, 120.0), 100.0);
+    /// // The integral term did not grow while the output was saturated, only the
+    /// // increment of this step is added to the proportional term.
+    /// let output = pid.step_with_feedforward(0.0, 0.0);
+    /// assert!((output - 11.0).abs() < 1e-9);
+    /// ```
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn step_with_feedforward(&mut self, measurement: T, feedforward: T) -> T {
+        self.advance(Input { measurement, rate: None, feedforward })
+    }
+
+    /// Performs a single step of the control loop using `rate` as the rate of change
+    /// of the measurement, in units per second, instead of differentiating the
+    /// measurement. It should be called exactly once every `sampling_time` seconds.
+    ///
+    /// This allows the derivative term to be fed by a better estimator, such as
+    /// [`SavitzkyGolay`](crate::filter::SavitzkyGolay). The derivative low-pass filter
+    /// is bypassed, as the estimate is expected to be smooth already.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn step_with_rate(&mut self, measurement: T, rate: T) -> T {
+        self.advance(Input { measurement, rate: Some(rate), feedforward: T::zero() })
+    }
+
+    /// Returns the state carried from the last step to the next.
+    pub fn state(&self) -> State<T> {
+        self.state
+    }
+
+    /// Replaces the state carried to the next step, for instance to restore a
+    /// controller or to start a test from a given state.
+    pub fn set_state(&mut self, state: State<T>) -> &mut Self {
+        self.state = state;
+        self
+    }
+
+    /// Returns the state following `state` and the output of a step on `input`, given
+    /// the configuration of the controller, without modifying it. Its own state is not
+    /// used.
+    ///
+    /// Every step of the controller goes through this function, so it can be fuzzed,
+    /// model checked or compared against a reference implementation on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::{Input, PID};
+    ///
+    /// let mut pid = PID::new(1.0, 0.5, 0.1, 0.01, 0.1, 10.0);
+    /// pid.bound_output(0.0..=20.0);
+    ///
+    /// let input = Input { measurement: 4.0, rate: None, feedforward: 0.0 };
+    /// let (state, output) = pid.transition(pid.state(), input);
+    /// assert_eq!(pid.step(4.0), output);
+    /// assert_eq!(pid.state(), state);
+    /// ```
+    pub fn transition(&self, state: State<T>, input: Input<T>) -> (State<T>, T) {
+        let Input { measurement, rate, feedforward } = input;
+        let mut next = state;
+        let derivative = match rate {
+            Some(rate) => -self.kd * rate,
+            // Derivative on measurement to prevent a kick during setpoint changes, the
+            // first measurement being its own previous one.
+            None => {
+                let previous = state.measurement.unwrap_or(measurement);
+                self.d * (measurement - previous) + self.t * state.derivative
+            }
+        };
         let error = self.setpoint - measurement;
 
         let proportional = self.p * error;
+        let zero = T::zero();
+        let same_sign =
+            (error > zero && state.error > zero) || (error < zero && state.error < zero);
+        next.sign_steps = if same_sign {
+            state.sign_steps.saturating_add(1)
+        } else if error != zero {
+            1
+        } else {
+            0
+        };
+        let increment = match self.inhibit {
+            _ if next.sign_steps < self.persistence => zero,
+            Saturation::Upper => (self.i * (error + state.error)).min(zero),
+            Saturation::Lower => (self.i * (error + state.error)).max(zero),
+            Saturation::Unsaturated => self.i * (error + state.error),
+        };
         // Calculate integral term and clamp it to prevent windup.
-        let integral = self.i * (error + self.error) + self.integral;
-        self.integral = num_traits::clamp(integral, self.imin, self.imax);
-        // Derivative on measurement to prevent a kick during setpoint changes.
-        self.derivative = self.d * (measurement - self.measurement) + self.t * self.derivative;
+        let retained = state.integral * self.fade;
+        let integral = num_traits::clamp(retained + increment, self.imin, self.imax);
+        next.integral_saturation = if retained + increment > self.imax {
+            Saturation::Upper
+        } else if retained + increment < self.imin {
+            Saturation::Lower
+        } else {
+            Saturation::Unsaturated
+        };
+        next.integral_saturation_steps = dwell(
+            state.integral_saturation,
+            next.integral_saturation,
+            state.integral_saturation_steps,
+        );
+        // Stop integrating while the output, bias and feedforward included, is already
+        // saturated in the direction the integral is moving.
+        let unclamped = self.bias + proportional + integral + derivative + feedforward;
+        let windup = (unclamped > self.omax && increment > T::zero())
+            || (unclamped < self.omin && increment < T::zero());
+        next.integral = if windup { retained } else { integral };
+        next.integral = num_traits::clamp(next.integral, self.imin, self.imax);
+        next.derivative = derivative;
+        next.feedforward = feedforward;
+
+        next.error = error;
+        next.measurement = Some(measurement);
+
+        let output = match self.manual {
+            Some(output) => {
+                // Track the manual output for a bumpless return to automatic mode.
+                let effort = self.mirror(output);
+                next = self.tracking(next, effort);
+                effort
+            }
+            None => self.bias + proportional + next.integral + next.derivative + next.feedforward,
+        };
+
+        next.saturation = if output > self.omax {
+            Saturation::Upper
+        } else if output < self.omin {
+            Saturation::Lower
+        } else {
+            Saturation::Unsaturated
+        };
+        next.saturation_steps = dwell(state.saturation, next.saturation, state.saturation_steps);
+        let clamped = self.saturate(output);
 
-        let output = proportional + self.integral + self.derivative;
+        // Compared directly, the bounds being NaN after a non-finite previous output.
+        let limited = if next.output.is_finite() && (self.rise.is_finite() || self.fall.is_finite())
+        {
+            let (low, high) = (next.output - self.fall, next.output + self.rise);
+            if clamped > high {
+                high
+            } else if clamped < low {
+                low
+            } else {
+                clamped
+            }
+        } else {
+            clamped
+        };
+        next.rate_limit = if limited < clamped {
+            Saturation::Upper
+        } else if limited > clamped {
+            Saturation::Lower
+        } else {
+            Saturation::Unsaturated
+        };
+        // Hold the integral term while the rate limit keeps the output from following it.
+        let held = match next.rate_limit {
+            Saturation::Upper => increment > T::zero(),
+            Saturation::Lower => increment < T::zero(),
+            Saturation::Unsaturated => false,
+        };
+        if held && self.manual.is_none() {
+            next.integral = retained;
+        }
+
+        next.output = limited;
+        (next, self.mirror(limited))
+    }
+
+    /// Steps the controller state on `input` and returns the output.
+    fn advance(&mut self, input: Input<T>) -> T {
+        let (state, output) = self.transition(self.state, input);
+        if state.integral_saturation != self.state.integral_saturation {
+            trace!("PID integral saturation changed to {:?}", state.integral_saturation);
+        }
+        if state.saturation != self.state.saturation {
+            debug!("PID output saturation changed to {:?}", state.saturation);
+        }
+        self.state = state;
+        output
+    }
+
+    /// Returns `state` with the integral term recomputed so that the last step would
+    /// have produced a control effort of `effort`.
+    fn tracking(&self, mut state: State<T>, effort: T) -> State<T> {
+        let integral =
+            effort - self.bias - self.p * state.error - state.derivative - state.feedforward;
+        state.integral = num_traits::clamp(integral, self.imin, self.imax);
+        state.output = effort;
+        state
+    }
+
+    /// Converts between control effort and output, which only differ when the output
+    /// is inverted. The conversion is its own inverse.
+    fn mirror(&self, value: T) -> T {
+        if !self.inverted {
+            value
+        } else if self.omin.is_finite() && self.omax.is_finite() {
+            self.omin + self.omax - value
+        } else {
+            -value
+        }
+    }
+
+    /// Restricts `output` to the output bounds, smoothly if a knee is configured.
+    fn saturate(&self, output: T) -> T {
+        let two = constant::two::<T>();
+        let four = constant::four::<T>();
+        // The transitions of both bounds must not overlap.
+        let knee = self.knee.min((self.omax - self.omin) / two);
+
+        if knee > T::zero() {
+            // Quadratic transition, tangent to the unclamped value on one end and to
+            // the bound on the other.
+            if output > self.omax - knee && output < self.omax + knee {
+                let excess = output - (self.omax - knee);
+                return output - excess * excess / (four * knee);
+            }
+            if output < self.omin + knee && output > self.omin - knee {
+                let excess = (self.omin + knee) - output;
+                return output + excess * excess / (four * knee);
+            }
+        }
         num_traits::clamp(output, self.omin, self.omax)
     }
 }
+
+/// Converts a value to a `f64` for logging.
+#[cfg(feature = "log")]
+fn float<T: FloatCore>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Converts a range into its lower and upper bounds, unbounded sides being infinite.
+fn bounds<T: FloatCore, R: RangeBounds<T>>(range: R) -> (T, T) {
+    let min = match range.start_bound() {
+        Bound::Included(&min) | Bound::Excluded(&min) => min,
+        Bound::Unbounded => T::neg_infinity(),
+    };
+    let max = match range.end_bound() {
+        Bound::Included(&max) | Bound::Excluded(&max) => max,
+        Bound::Unbounded => T::infinity(),
+    };
+    (min, max)
+}
+
+/// Checks the precondition of a setter, panicking if it does not hold. With the
+/// `no-panic` feature it only returns whether it holds, for the setter to leave the
+/// configuration unchanged.
+#[cfg_attr(not(feature = "no-panic"), track_caller)]
+fn precondition(holds: bool) -> bool {
+    #[cfg(not(feature = "no-panic"))]
+    assert!(holds);
+    holds
+}
+
+/// Returns the number of consecutive steps spent in the `current` saturation state,
+/// given the `previous` one and the number of steps spent in it.
+fn dwell(previous: Saturation, current: Saturation, steps: u32) -> u32 {
+    match current {
+        Saturation::Unsaturated => 0,
+        _ if current == previous => steps.saturating_add(1),
+        _ => 1,
+    }
+}
+
+/// A [`PID`] controller that hands the record of every step to an observer, leaving
+/// the control path untouched.
+#[derive(Clone, Copy, Debug)]
+pub struct Observed<T, O> {
+    /// Observed controller.
+    pub controller: PID<T>,
+    /// Receiver of the step records.
+    pub observer: O,
+}
+
+impl<T: FloatCore, O: StepObserver<T>> Observed<T, O> {
+    /// Creates a new `Observed` controller handing the records of `controller` to
+    /// `observer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::{Observed, PID};
+    ///
+    /// let mut outputs = Vec::new();
+    /// let pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// let mut observed = Observed::new(pid, |record: &_| outputs.push(*record));
+    /// observed.step(4.0);
+    /// observed.step(6.0);
+    /// drop(observed);
+    /// assert_eq!(outputs.len(), 2);
+    /// assert_eq!(outputs[1].output, 4.0);
+    /// ```
+    pub fn new(controller: PID<T>, observer: O) -> Self {
+        Self { controller, observer }
+    }
+
+    /// Performs a single step of the control loop, see [`PID::step`].
+    pub fn step(&mut self, measurement: T) -> T {
+        let output = self.controller.step(measurement);
+        self.observer.on_step(&self.controller.record());
+        output
+    }
+
+    /// Performs a single step of the control loop with a feedforward term, see
+    /// [`PID::step_with_feedforward`].
+    pub fn step_with_feedforward(&mut self, measurement: T, feedforward: T) -> T {
+        let output = self.controller.step_with_feedforward(measurement, feedforward);
+        self.observer.on_step(&self.controller.record());
+        output
+    }
+
+    /// Performs a single step of the control loop with a supplied rate of change of
+    /// the measurement, see [`PID::step_with_rate`].
+    pub fn step_with_rate(&mut self, measurement: T, rate: T) -> T {
+        let output = self.controller.step_with_rate(measurement, rate);
+        self.observer.on_step(&self.controller.record());
+        output
+    }
+}
@@ -0,0 +1,21 @@
+//! The controller, filter and process model types most control loops need, to be
+//! imported at once with `use control::prelude::*;`.
+//!
+//! # Examples
+//!
+//! ```
+//! use control::prelude::*;
+//!
+//! let mut filter = Exponential::new(0.5);
+//! let mut pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.1, 1.0);
+//! let output = Controller::step(&mut pid, filter.step(0.5));
+//! assert_eq!(output, 1.0);
+//! ```
+
+pub use crate::controller::Controller;
+pub use crate::filter::{
+    AlphaBeta, Biquad, Decimator, Delay, Exponential, Median, MovingAverage, Notch, OutlierGate,
+    SavitzkyGolay, SlewLimiter, SquareRoot,
+};
+pub use crate::pid::{Input, Saturation, State, PID};
+pub use crate::simulation::{FirstOrder, Fopdt, Integrator, Plant, SecondOrder, Simulation};
@@ -0,0 +1,261 @@
+//! Python bindings, to prototype tunings in notebooks against the exact arithmetic
+//! that runs on the target.
+//!
+//! The extension module is built as a `cdylib`, for instance with
+//! `cargo rustc --release --features pyo3,pyo3/extension-module --crate-type cdylib`,
+//! and used as:
+//!
+//! ```python
+//! import control
+//!
+//! pid = control.PID(2.0, 1.0, 0.0, 0.0, 0.1, 1.0)
+//! plant = control.Plant.fopdt(1.0, 2.0, 0.1, 5)
+//! setpoint, measurement, output = control.simulate(pid, plant, [1.0] * 600)
+//! rise_time, overshoot, settling_time, error = control.step_response(measurement, 0.1, 1.0, 0.02)
+//! ```
+//!
+//! All values are `f64`.
+
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use pyo3::prelude::*;
+
+use crate::diagnostics;
+use crate::pid::PID;
+use crate::simulation::{self, FirstOrder, Integrator, SecondOrder, Simulation};
+
+/// Python wrapper of a [`PID`] controller.
+#[pyclass(name = "PID")]
+#[derive(Clone, Copy)]
+struct PyPid {
+    /// Wrapped controller.
+    controller: PID<f64>,
+}
+
+#[pymethods]
+impl PyPid {
+    /// See [`PID::new`].
+    #[new]
+    fn new(kp: f64, ki: f64, kd: f64, tau: f64, sampling_time: f64, setpoint: f64) -> Self {
+        Self { controller: PID::new(kp, ki, kd, tau, sampling_time, setpoint) }
+    }
+
+    #[getter]
+    fn setpoint(&self) -> f64 {
+        self.controller.setpoint
+    }
+
+    #[setter]
+    fn set_setpoint(&mut self, setpoint: f64) {
+        self.controller.setpoint = setpoint;
+    }
+
+    /// See [`PID::output`].
+    #[getter]
+    fn output(&self) -> f64 {
+        self.controller.output()
+    }
+
+    /// See [`PID::set_gains`].
+    fn set_gains(&mut self, kp: f64, ki: f64, kd: f64) {
+        self.controller.set_gains(kp, ki, kd);
+    }
+
+    /// See [`PID::bound_integral`].
+    fn bound_integral(&mut self, min: f64, max: f64) {
+        self.controller.bound_integral(min..=max);
+    }
+
+    /// See [`PID::bound_output`].
+    fn bound_output(&mut self, min: f64, max: f64) {
+        self.controller.bound_output(min..=max);
+    }
+
+    /// See [`PID::limit_rate`].
+    fn limit_rate(&mut self, rise: f64, fall: f64) {
+        self.controller.limit_rate(rise, fall);
+    }
+
+    /// See [`PID::set_manual`].
+    fn set_manual(&mut self, output: f64) {
+        self.controller.set_manual(output);
+    }
+
+    /// See [`PID::set_automatic`].
+    fn set_automatic(&mut self) {
+        self.controller.set_automatic();
+    }
+
+    /// See [`PID::reset`].
+    fn reset(&mut self) {
+        self.controller.reset();
+    }
+
+    /// See [`PID::step`].
+    fn step(&mut self, measurement: f64) -> f64 {
+        self.controller.step(measurement)
+    }
+
+    /// See [`PID::step_with_feedforward`].
+    fn step_with_feedforward(&mut self, measurement: f64, feedforward: f64) -> f64 {
+        self.controller.step_with_feedforward(measurement, feedforward)
+    }
+}
+
+/// Process models available from Python.
+#[derive(Clone, Copy)]
+enum Model {
+    /// See [`FirstOrder`].
+    FirstOrder(FirstOrder<f64>),
+    /// See [`SecondOrder`].
+    SecondOrder(SecondOrder<f64>),
+    /// See [`Integrator`].
+    Integrator(Integrator<f64>),
+}
+
+/// Python wrapper of a process model, with a dead time chosen at runtime.
+#[pyclass(name = "Plant")]
+#[derive(Clone)]
+struct PyPlant {
+    /// Process without its dead time.
+    model: Model,
+    /// Inputs waiting to reach the process, as many as steps of dead time.
+    delay: VecDeque<f64>,
+}
+
+impl PyPlant {
+    /// Creates a new plant with a dead time of `delay` steps.
+    fn delayed(model: Model, delay: usize) -> Self {
+        let mut inputs = VecDeque::new();
+        inputs.resize(delay, 0.0);
+        Self { model, delay: inputs }
+    }
+}
+
+#[pymethods]
+impl PyPlant {
+    /// See [`FirstOrder::new`].
+    #[staticmethod]
+    fn first_order(gain: f64, time_constant: f64, sampling_time: f64) -> Self {
+        Self::delayed(Model::FirstOrder(FirstOrder::new(gain, time_constant, sampling_time)), 0)
+    }
+
+    /// See [`Fopdt::new`](crate::simulation::Fopdt::new), with a dead time of `delay`
+    /// steps.
+    #[staticmethod]
+    fn fopdt(gain: f64, time_constant: f64, sampling_time: f64, delay: usize) -> Self {
+        let model = Model::FirstOrder(FirstOrder::new(gain, time_constant, sampling_time));
+        Self::delayed(model, delay)
+    }
+
+    /// See [`SecondOrder::new`].
+    #[staticmethod]
+    fn second_order(gain: f64, frequency: f64, damping: f64, sampling_time: f64) -> Self {
+        let model = Model::SecondOrder(SecondOrder::new(gain, frequency, damping, sampling_time));
+        Self::delayed(model, 0)
+    }
+
+    /// See [`Integrator::new`].
+    #[staticmethod]
+    fn integrator(gain: f64, sampling_time: f64) -> Self {
+        Self::delayed(Model::Integrator(Integrator::new(gain, sampling_time)), 0)
+    }
+
+    /// See [`Plant::step`](simulation::Plant::step).
+    fn step(&mut self, input: f64) -> f64 {
+        simulation::Plant::step(self, input)
+    }
+
+    /// See [`Plant::output`](simulation::Plant::output).
+    #[getter]
+    fn output(&self) -> f64 {
+        simulation::Plant::output(self)
+    }
+
+    /// See [`Plant::reset`](simulation::Plant::reset).
+    fn reset(&mut self) {
+        simulation::Plant::reset(self);
+    }
+}
+
+impl simulation::Plant<f64> for PyPlant {
+    fn step(&mut self, input: f64) -> f64 {
+        let input = match self.delay.pop_front() {
+            Some(delayed) => {
+                self.delay.push_back(input);
+                delayed
+            }
+            None => input,
+        };
+        match &mut self.model {
+            Model::FirstOrder(model) => model.step(input),
+            Model::SecondOrder(model) => model.step(input),
+            Model::Integrator(model) => model.step(input),
+        }
+    }
+
+    fn output(&self) -> f64 {
+        match &self.model {
+            Model::FirstOrder(model) => model.output(),
+            Model::SecondOrder(model) => model.output(),
+            Model::Integrator(model) => model.output(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.delay.iter_mut().for_each(|input| *input = 0.0);
+        match &mut self.model {
+            Model::FirstOrder(model) => model.reset(),
+            Model::SecondOrder(model) => model.reset(),
+            Model::Integrator(model) => model.reset(),
+        }
+    }
+}
+
+/// Simulates copies of `pid` and `plant` for one step per setpoint, with an optional
+/// load disturbance per step, and returns the setpoints, measurements and outputs.
+#[pyfunction]
+#[pyo3(signature = (pid, plant, setpoints, disturbances = None))]
+fn simulate(
+    pid: &PyPid,
+    plant: &PyPlant,
+    setpoints: Vec<f64>,
+    disturbances: Option<Vec<f64>>,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let disturbances = disturbances.unwrap_or_default();
+    let mut simulation = Simulation::new(pid.controller, plant.clone());
+    let disturbance = |k: usize| disturbances.get(k).copied().unwrap_or(0.0);
+
+    let steps = simulation.iter(|k| setpoints[k], disturbance).take(setpoints.len());
+    let mut trajectory = (Vec::new(), Vec::new(), Vec::new());
+    for sample in steps {
+        trajectory.0.push(sample.setpoint);
+        trajectory.1.push(sample.measurement);
+        trajectory.2.push(sample.output);
+    }
+    trajectory
+}
+
+/// See [`diagnostics::step_response`], returning the rise time, overshoot, settling
+/// time and steady state error, or `None`.
+#[pyfunction]
+fn step_response(
+    samples: Vec<f64>,
+    sampling_time: f64,
+    setpoint: f64,
+    tolerance: f64,
+) -> Option<(Option<f64>, f64, Option<f64>, f64)> {
+    let metrics = diagnostics::step_response(&samples, sampling_time, setpoint, tolerance)?;
+    Some((metrics.rise_time, metrics.overshoot, metrics.settling_time, metrics.steady_state_error))
+}
+
+/// Python extension module.
+#[pymodule]
+fn control(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyPid>()?;
+    module.add_class::<PyPlant>()?;
+    module.add_function(wrap_pyfunction!(simulate, module)?)?;
+    module.add_function(wrap_pyfunction!(step_response, module)?)?;
+    Ok(())
+}
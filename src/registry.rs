@@ -0,0 +1,114 @@
+//! Controllers created, configured and destroyed at runtime, such as loops defined by
+//! a configuration file on a gateway.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use crate::controller::Controller;
+
+/// Implementation of a registry of controllers keyed by name, stepped through the
+/// [`Controller`] trait so different control laws can live side by side.
+pub struct Registry<T> {
+    /// Registered controllers, by name.
+    controllers: BTreeMap<String, Box<dyn Controller<T>>>,
+}
+
+impl<T> Registry<T> {
+    /// Creates a new empty `Registry`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::multirate::MultiRate;
+    /// use control::pid::PID;
+    /// use control::registry::Registry;
+    ///
+    /// let mut registry = Registry::new();
+    /// registry.insert("boiler", PID::new(1.0, 0.0, 0.0, 0.0, 1.0, 60.0));
+    /// registry.insert("pump", MultiRate::new(2.0, 0.0, 0.0, 0.0, 1.0, 2, 3.0));
+    ///
+    /// registry.get_mut("boiler").unwrap().set_setpoint(70.0);
+    /// assert_eq!(registry.step("boiler", 65.0), Some(5.0));
+    ///
+    /// registry.remove("pump");
+    /// assert_eq!(registry.names().collect::<Vec<_>>(), ["boiler"]);
+    /// ```
+    pub fn new() -> Self {
+        Self { controllers: BTreeMap::new() }
+    }
+
+    /// Returns the number of registered controllers.
+    pub fn len(&self) -> usize {
+        self.controllers.len()
+    }
+
+    /// Returns whether no controller is registered.
+    pub fn is_empty(&self) -> bool {
+        self.controllers.is_empty()
+    }
+
+    /// Returns whether a controller is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.controllers.contains_key(name)
+    }
+
+    /// Registers a controller under `name` and returns the one it replaces, if any.
+    pub fn insert<C>(
+        &mut self,
+        name: impl Into<String>,
+        controller: C,
+    ) -> Option<Box<dyn Controller<T>>>
+    where
+        C: Controller<T> + 'static,
+    {
+        self.controllers.insert(name.into(), Box::new(controller))
+    }
+
+    /// Removes the controller registered under `name` and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Controller<T>>> {
+        self.controllers.remove(name)
+    }
+
+    /// Returns the controller registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Controller<T>> {
+        self.controllers.get(name).map(|controller| &**controller)
+    }
+
+    /// Returns the controller registered under `name`, mutably.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut (dyn Controller<T> + 'static)> {
+        self.controllers.get_mut(name).map(|controller| &mut **controller)
+    }
+
+    /// Returns an iterator over the names of the registered controllers, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.controllers.keys().map(String::as_str)
+    }
+
+    /// Performs a single step of the controller registered under `name` and returns
+    /// its output, or `None` if there is no such controller.
+    pub fn step(&mut self, name: &str, measurement: T) -> Option<T> {
+        self.get_mut(name).map(|controller| controller.step(measurement))
+    }
+
+    /// Performs a single step of every controller, in name order.
+    ///
+    /// `sensor` returns the measurement of the loop with the given name and `actuator`
+    /// applies its output.
+    pub fn step_all<S, A>(&mut self, mut sensor: S, mut actuator: A)
+    where
+        S: FnMut(&str) -> T,
+        A: FnMut(&str, T),
+    {
+        for (name, controller) in self.controllers.iter_mut() {
+            let output = controller.step(sensor(name));
+            actuator(name, output);
+        }
+    }
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
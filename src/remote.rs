@@ -0,0 +1,421 @@
+//! A framed request and response protocol to tune a controller over the wire, with
+//! sequence numbers and a CRC so every change is acknowledged exactly once.
+//!
+//! # Format
+//!
+//! Frames are framed as [`telemetry`](crate::telemetry) frames: the payload is
+//! followed by its CRC-16/CCITT-FALSE, little endian, then COBS encoded and terminated
+//! by a zero byte. Requests and responses start with the format version, [`VERSION`],
+//! a `u16` session identifier and a `u16` sequence number, little endian. A request
+//! then has an opcode and its arguments, a response echoes the session identifier and
+//! sequence number and has a [`Status`] then, for a snapshot, the flags and values of
+//! a telemetry frame.
+//!
+//! | Opcode | Request          | Arguments                 |
+//! |--------|------------------|---------------------------|
+//! | 1      | Set gains        | `f32` kp, ki and kd       |
+//! | 2      | Set setpoint     | `f32` setpoint            |
+//! | 3      | Read snapshot    |                           |
+//! | 4      | Start autotune   |                           |
+//! | 5      | Stop autotune    |                           |
+//!
+//! Corrupted frames are dropped without response, so the host retries the same frame
+//! after a timeout. A request repeating the session identifier and sequence number of
+//! the previous one gets the previous response again without being applied twice, so
+//! a restarted host must pick another session identifier.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, StepRecord, PID};
+use crate::telemetry::{cobs, crc16, flag, uncobs};
+
+/// Version of the frame format.
+pub const VERSION: u8 = 2;
+
+/// Length of the longest encoded frame, zero terminator included.
+pub const MAX_FRAME_LEN: usize = MAX_PAYLOAD_LEN + 4;
+
+/// Length of the longest payload, a snapshot response, without its CRC.
+const MAX_PAYLOAD_LEN: usize = 35;
+
+/// A request sent by the host.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Request<T> {
+    /// Changes the proportional, integral and derivative gains, see
+    /// [`PID::set_gains`].
+    SetGains(T, T, T),
+    /// Changes the setpoint.
+    SetSetpoint(T),
+    /// Reads the record of the last step.
+    ReadSnapshot,
+    /// Starts the autotuner of the application.
+    StartAutotune,
+    /// Stops the autotuner of the application.
+    StopAutotune,
+}
+
+/// Outcome of a request, sent back in its response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The request was applied.
+    Ok,
+    /// The request does not have the arguments of its opcode.
+    Malformed,
+    /// The opcode is unknown.
+    UnknownRequest,
+    /// The arguments are not acceptable, such as a value that is not finite.
+    Rejected,
+    /// The request is not supported by the device.
+    Unsupported,
+}
+
+/// A response sent by the device.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Response<T> {
+    /// Session identifier of the request.
+    pub session: u16,
+    /// Sequence number of the request.
+    pub sequence: u16,
+    /// Outcome of the request.
+    pub status: Status,
+    /// Record of the last step, for a successful snapshot request.
+    pub snapshot: Option<StepRecord<T>>,
+}
+
+/// Why a frame could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The framing or the CRC is wrong.
+    Corrupted,
+    /// The frame has another format version.
+    Version,
+    /// The frame is well formed but its content is not valid.
+    Invalid,
+    /// The response does not answer the last request, from another session or with
+    /// another sequence number.
+    UnexpectedSequence,
+}
+
+/// Opcodes of the requests.
+const SET_GAINS: u8 = 1;
+const SET_SETPOINT: u8 = 2;
+const READ_SNAPSHOT: u8 = 3;
+const START_AUTOTUNE: u8 = 4;
+const STOP_AUTOTUNE: u8 = 5;
+
+/// Implementation of the host side of the protocol, numbering the requests.
+#[derive(Clone, Copy, Debug)]
+pub struct Client {
+    /// Identifier of the session, told apart by the server from earlier ones.
+    session: u16,
+    /// Sequence number of the last request.
+    sequence: u16,
+}
+
+impl Client {
+    /// Creates a new `Client` for the `session` identifier, the first request having a
+    /// sequence number of one.
+    ///
+    /// The identifier should change every time the host starts, for instance a random
+    /// number or a boot counter, so the server does not take the first requests of a
+    /// restarted host for retries of the previous session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::remote::{Client, Request, Server, Status, MAX_FRAME_LEN};
+    ///
+    /// let mut pid = PID::new(1.0_f32, 0.0, 0.0, 0.0, 0.1, 20.0);
+    /// let mut server = Server::new();
+    /// let mut client = Client::new(0x5eed);
+    ///
+    /// // On the host.
+    /// let mut frame = [0; MAX_FRAME_LEN];
+    /// let len = client.request(&Request::SetGains(2.0, 0.5, 0.0), &mut frame);
+    ///
+    /// // On the device, with the bytes received up to the zero terminator.
+    /// let reply = server.handle(&mut pid, &frame[..len], |_, _| Status::Unsupported);
+    /// let reply = reply.unwrap().to_vec();
+    /// assert_eq!(pid.gains(), (2.0, 0.5, 0.0));
+    ///
+    /// // Back on the host.
+    /// let response = client.response::<f32>(&reply).unwrap();
+    /// assert_eq!(response.status, Status::Ok);
+    /// ```
+    pub fn new(session: u16) -> Self {
+        Self { session, sequence: 0 }
+    }
+
+    /// Encodes the next request into `frame` and returns the frame length. The same
+    /// bytes should be sent again if no response arrives.
+    pub fn request<T: FloatCore>(
+        &mut self,
+        request: &Request<T>,
+        frame: &mut [u8; MAX_FRAME_LEN],
+    ) -> usize {
+        self.sequence = self.sequence.wrapping_add(1);
+
+        let mut payload = [0; MAX_PAYLOAD_LEN + 2];
+        payload[0] = VERSION;
+        payload[1..3].copy_from_slice(&self.session.to_le_bytes());
+        payload[3..5].copy_from_slice(&self.sequence.to_le_bytes());
+        let (opcode, values, count) = match *request {
+            Request::SetGains(kp, ki, kd) => (SET_GAINS, [kp, ki, kd], 3),
+            Request::SetSetpoint(setpoint) => (SET_SETPOINT, [setpoint; 3], 1),
+            Request::ReadSnapshot => (READ_SNAPSHOT, [T::zero(); 3], 0),
+            Request::StartAutotune => (START_AUTOTUNE, [T::zero(); 3], 0),
+            Request::StopAutotune => (STOP_AUTOTUNE, [T::zero(); 3], 0),
+        };
+        payload[5] = opcode;
+        let len = 6 + write_values(&mut payload[6..], &values[..count]);
+        seal(&mut payload, len, frame)
+    }
+
+    /// Decodes the response to the last request from a frame, with or without its zero
+    /// terminator.
+    pub fn response<T: FloatCore>(&self, frame: &[u8]) -> Result<Response<T>, Error> {
+        let mut payload = [0; MAX_PAYLOAD_LEN + 2];
+        let payload = open(frame, &mut payload)?;
+        if payload.len() < 6 {
+            return Err(Error::Invalid);
+        }
+        let session = u16::from_le_bytes([payload[1], payload[2]]);
+        let sequence = u16::from_le_bytes([payload[3], payload[4]]);
+        if session != self.session || sequence != self.sequence {
+            return Err(Error::UnexpectedSequence);
+        }
+
+        let status = match payload[5] {
+            0 => Status::Ok,
+            1 => Status::Malformed,
+            2 => Status::UnknownRequest,
+            3 => Status::Rejected,
+            4 => Status::Unsupported,
+            _ => return Err(Error::Invalid),
+        };
+        let snapshot = match &payload[6..] {
+            [] => None,
+            [flags, values @ ..] if values.len() == 28 => {
+                let mut record = [T::zero(); 7];
+                read_values(values, &mut record).ok_or(Error::Invalid)?;
+                Some(StepRecord {
+                    setpoint: record[0],
+                    measurement: record[1],
+                    proportional: record[2],
+                    integral: record[3],
+                    derivative: record[4],
+                    feedforward: record[5],
+                    output: record[6],
+                    saturation: saturation(flags & 0b11),
+                    rate_limit: saturation((flags >> 2) & 0b11),
+                    manual: flags & 0b1_0000 != 0,
+                })
+            }
+            _ => return Err(Error::Invalid),
+        };
+        Ok(Response { session, sequence, status, snapshot })
+    }
+}
+
+/// Implementation of the device side of the protocol, applying the requests to a
+/// controller and remembering the last response for retries.
+#[derive(Clone, Copy, Debug)]
+pub struct Server {
+    /// Session identifier and sequence number of the last request handled.
+    last: Option<(u16, u16)>,
+    /// Encoded response to the last request.
+    response: [u8; MAX_FRAME_LEN],
+    /// Length of the encoded response.
+    len: usize,
+}
+
+impl Server {
+    /// Creates a new `Server` that has not handled any request yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::remote::{Client, Request, Server, Status, MAX_FRAME_LEN};
+    ///
+    /// let mut pid = PID::new(1.0_f32, 0.0, 0.0, 0.0, 0.1, 20.0);
+    /// pid.step(18.0);
+    /// let mut server = Server::new();
+    /// let mut client = Client::new(1);
+    ///
+    /// let mut frame = [0; MAX_FRAME_LEN];
+    /// let len = client.request(&Request::<f32>::ReadSnapshot, &mut frame);
+    /// let first = server.handle(&mut pid, &frame[..len], |_, _| Status::Unsupported).unwrap().to_vec();
+    ///
+    /// // The response got lost, the host sends the same frame again.
+    /// let retry = server.handle(&mut pid, &frame[..len], |_, _| Status::Unsupported).unwrap();
+    /// assert_eq!(retry, &first[..]);
+    ///
+    /// let snapshot = client.response::<f32>(retry).unwrap().snapshot.unwrap();
+    /// assert_eq!((snapshot.measurement, snapshot.output), (18.0, 2.0));
+    ///
+    /// // The host restarts with another session and the same first sequence number.
+    /// let mut client = Client::new(2);
+    /// let len = client.request(&Request::SetSetpoint(21.0), &mut frame);
+    /// let reply = server.handle(&mut pid, &frame[..len], |_, _| Status::Unsupported).unwrap();
+    /// assert_eq!(client.response::<f32>(reply).unwrap().status, Status::Ok);
+    /// assert_eq!(pid.setpoint, 21.0);
+    /// ```
+    pub fn new() -> Self {
+        Self { last: None, response: [0; MAX_FRAME_LEN], len: 0 }
+    }
+
+    /// Handles a request frame, with or without its zero terminator, and returns the
+    /// response frame to send, or `None` if the request is corrupted.
+    ///
+    /// `autotune` is called with `true` to start the autotuner of the application and
+    /// `false` to stop it, and returns the status to respond with.
+    pub fn handle<T, A>(
+        &mut self,
+        controller: &mut PID<T>,
+        frame: &[u8],
+        mut autotune: A,
+    ) -> Option<&[u8]>
+    where
+        T: FloatCore,
+        A: FnMut(&mut PID<T>, bool) -> Status,
+    {
+        let mut payload = [0; MAX_PAYLOAD_LEN + 2];
+        let payload = open(frame, &mut payload).ok()?;
+        if payload.len() < 6 {
+            return None;
+        }
+        let session = u16::from_le_bytes([payload[1], payload[2]]);
+        let sequence = u16::from_le_bytes([payload[3], payload[4]]);
+        if self.last == Some((session, sequence)) {
+            return Some(&self.response[..self.len]);
+        }
+
+        let opcode = payload[5];
+        let arguments = &payload[6..];
+        let mut values = [T::zero(); 3];
+        let count = match opcode {
+            SET_GAINS => 3,
+            SET_SETPOINT => 1,
+            _ => 0,
+        };
+        let status = if arguments.len() != 4 * count {
+            if (SET_GAINS..=STOP_AUTOTUNE).contains(&opcode) {
+                Status::Malformed
+            } else {
+                Status::UnknownRequest
+            }
+        } else if read_values(arguments, &mut values[..count]).is_none() {
+            Status::Rejected
+        } else {
+            match opcode {
+                SET_GAINS => {
+                    controller.set_gains(values[0], values[1], values[2]);
+                    Status::Ok
+                }
+                SET_SETPOINT => {
+                    controller.setpoint = values[0];
+                    Status::Ok
+                }
+                READ_SNAPSHOT => Status::Ok,
+                START_AUTOTUNE => autotune(controller, true),
+                _ => autotune(controller, false),
+            }
+        };
+
+        let mut response = [0; MAX_PAYLOAD_LEN + 2];
+        response[0] = VERSION;
+        response[1..3].copy_from_slice(&session.to_le_bytes());
+        response[3..5].copy_from_slice(&sequence.to_le_bytes());
+        response[5] = status as u8;
+        let mut len = 6;
+        if opcode == READ_SNAPSHOT && status == Status::Ok {
+            let record = controller.record();
+            response[6] = flag(record.saturation)
+                | (flag(record.rate_limit) << 2)
+                | ((record.manual as u8) << 4);
+            let values = [
+                record.setpoint,
+                record.measurement,
+                record.proportional,
+                record.integral,
+                record.derivative,
+                record.feedforward,
+                record.output,
+            ];
+            len = 7 + write_values(&mut response[7..], &values);
+        }
+
+        self.len = seal(&mut response, len, &mut self.response);
+        self.last = Some((session, sequence));
+        Some(&self.response[..self.len])
+    }
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes `values` as little endian `f32` and returns the number of bytes written.
+fn write_values<T: FloatCore>(bytes: &mut [u8], values: &[T]) -> usize {
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+        let value = value.to_f32().unwrap_or(f32::NAN);
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    4 * values.len()
+}
+
+/// Reads little endian `f32` into `values`, or returns `None` if one is not finite.
+fn read_values<T: FloatCore>(bytes: &[u8], values: &mut [T]) -> Option<()> {
+    for (chunk, value) in bytes.chunks_exact(4).zip(values.iter_mut()) {
+        let number = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        if !number.is_finite() {
+            return None;
+        }
+        *value = T::from(number)?;
+    }
+    Some(())
+}
+
+/// Appends the CRC to the first `len` bytes of `payload` and encodes them into
+/// `frame`, returning the frame length.
+fn seal(
+    payload: &mut [u8; MAX_PAYLOAD_LEN + 2],
+    len: usize,
+    frame: &mut [u8; MAX_FRAME_LEN],
+) -> usize {
+    let crc = crc16(&payload[..len]);
+    payload[len..len + 2].copy_from_slice(&crc.to_le_bytes());
+    cobs(&payload[..len + 2], frame);
+    len + 4
+}
+
+/// Decodes a frame into `payload` and checks its CRC and version, returning the
+/// payload without its CRC.
+fn open<'a>(frame: &[u8], payload: &'a mut [u8; MAX_PAYLOAD_LEN + 2]) -> Result<&'a [u8], Error> {
+    let frame = frame.strip_suffix(&[0]).unwrap_or(frame);
+    let len = uncobs(frame, payload).ok_or(Error::Corrupted)?;
+    if len < 3 {
+        return Err(Error::Corrupted);
+    }
+    let (data, crc) = payload[..len].split_at(len - 2);
+    if crc16(data) != u16::from_le_bytes([crc[0], crc[1]]) {
+        return Err(Error::Corrupted);
+    }
+    if data[0] != VERSION {
+        return Err(Error::Version);
+    }
+    Ok(data)
+}
+
+/// Returns the saturation direction of a two bit flag.
+fn saturation(flag: u8) -> Saturation {
+    match flag {
+        1 => Saturation::Lower,
+        2 => Saturation::Upper,
+        _ => Saturation::Unsaturated,
+    }
+}
@@ -0,0 +1,20 @@
+//! Runners executing a control loop at its sampling period, each behind the feature
+//! of the environment it runs in.
+
+#[cfg(feature = "embassy")]
+mod embassy;
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "std")]
+mod thread;
+#[cfg(feature = "tokio")]
+mod tokio;
+
+#[cfg(feature = "embassy")]
+pub use self::embassy::{EmbassyRunner, Tuning};
+#[cfg(feature = "embedded-hal")]
+pub use self::hal::HalRunner;
+#[cfg(feature = "std")]
+pub use self::thread::ThreadRunner;
+#[cfg(feature = "tokio")]
+pub use self::tokio::TokioRunner;
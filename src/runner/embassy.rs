@@ -0,0 +1,147 @@
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_time::{Duration, Ticker};
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Changes to apply to the controller of an [`EmbassyRunner`] at its next step.
+#[derive(Clone, Copy, Debug)]
+struct Pending<T> {
+    /// New setpoint.
+    setpoint: Option<T>,
+    /// New proportional, integral and derivative gains.
+    gains: Option<(T, T, T)>,
+}
+
+/// A handle to change the setpoint and gains of an [`EmbassyRunner`] from other
+/// tasks or interrupts, meant to be stored in a `static`.
+///
+/// The changes are applied at the start of the next step of the runner.
+pub struct Tuning<T> {
+    /// Changes not yet applied.
+    pending: Mutex<CriticalSectionRawMutex, Cell<Pending<T>>>,
+}
+
+impl<T: Copy> Tuning<T> {
+    /// Creates a new `Tuning` handle without pending changes.
+    pub const fn new() -> Self {
+        let pending = Pending { setpoint: None, gains: None };
+        Self { pending: Mutex::new(Cell::new(pending)) }
+    }
+
+    /// Changes the setpoint of the controller.
+    pub fn set_setpoint(&self, setpoint: T) {
+        self.pending.lock(|pending| {
+            pending.set(Pending { setpoint: Some(setpoint), ..pending.get() });
+        });
+    }
+
+    /// Changes the gains of the controller, see [`PID::set_gains`].
+    pub fn set_gains(&self, kp: T, ki: T, kd: T) {
+        self.pending.lock(|pending| {
+            pending.set(Pending { gains: Some((kp, ki, kd)), ..pending.get() });
+        });
+    }
+
+    /// Takes the pending changes.
+    fn take(&self) -> Pending<T> {
+        self.pending.lock(|pending| pending.replace(Pending { setpoint: None, gains: None }))
+    }
+}
+
+impl<T: Copy> Default for Tuning<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of an async loop runner on top of Embassy, which reads the sensor,
+/// steps the controller and drives the actuator once every sampling period.
+///
+/// The periods are scheduled by an `embassy_time::Ticker`, which does not drift.
+pub struct EmbassyRunner<'a, T, S, A> {
+    /// Controller being run.
+    pub controller: PID<T>,
+
+    /// Ticker firing once every sampling period.
+    ticker: Ticker,
+    /// Changes requested by other tasks.
+    tuning: &'a Tuning<T>,
+    /// Reads the measurement.
+    sensor: S,
+    /// Applies the controller output.
+    actuator: A,
+}
+
+impl<'a, T, S, A> EmbassyRunner<'a, T, S, A>
+where
+    T: FloatCore,
+    S: FnMut() -> T,
+    A: FnMut(T),
+{
+    /// Creates a new `EmbassyRunner` stepping `controller` once every `period`, which
+    /// should match its sampling time, and applying the changes requested through
+    /// `tuning`.
+    ///
+    /// `sensor` returns the measurement and `actuator` applies the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::runner::{EmbassyRunner, Tuning};
+    /// use embassy_time::Duration;
+    ///
+    /// static TUNING: Tuning<f32> = Tuning::new();
+    ///
+    /// let mut heater = 0.0;
+    /// let pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.001, 0.0);
+    /// let period = Duration::from_millis(1);
+    /// let mut runner = EmbassyRunner::new(pid, period, &TUNING, || 20.0, |output| heater = output);
+    ///
+    /// // From another task.
+    /// TUNING.set_setpoint(25.0);
+    /// TUNING.set_gains(2.0, 0.0, 0.0);
+    ///
+    /// embassy_futures::block_on(runner.step());
+    /// drop(runner);
+    /// assert_eq!(heater, 10.0);
+    /// ```
+    pub fn new(
+        controller: PID<T>,
+        period: Duration,
+        tuning: &'a Tuning<T>,
+        sensor: S,
+        actuator: A,
+    ) -> Self {
+        Self { controller, ticker: Ticker::every(period), tuning, sensor, actuator }
+    }
+
+    /// Waits for the next tick, applies the pending changes, then reads the sensor,
+    /// steps the controller and drives the actuator.
+    pub async fn step(&mut self) {
+        self.ticker.next().await;
+
+        let pending = self.tuning.take();
+        if let Some(setpoint) = pending.setpoint {
+            self.controller.setpoint = setpoint;
+        }
+        if let Some((kp, ki, kd)) = pending.gains {
+            self.controller.set_gains(kp, ki, kd);
+        }
+
+        let measurement = (self.sensor)();
+        let output = self.controller.step(measurement);
+        (self.actuator)(output);
+    }
+
+    /// Runs the loop forever.
+    pub async fn run(&mut self) -> ! {
+        loop {
+            self.step().await;
+        }
+    }
+}
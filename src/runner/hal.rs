@@ -0,0 +1,123 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::controller::Controller;
+
+/// Implementation of a blocking loop runner on top of embedded-hal, which reads the
+/// sensor, steps the controller and drives the actuator once every sampling period.
+///
+/// The periods are scheduled against a microsecond clock, so the time spent in the
+/// loop itself does not make it drift. A step that ends after the start of the next
+/// period is counted as an overrun and the schedule restarts from it.
+#[derive(Debug)]
+pub struct HalRunner<C, D, N, S, A> {
+    /// Controller being run.
+    pub controller: C,
+
+    /// Delay provider waiting for the next period.
+    delay: D,
+    /// Microsecond clock, wrapping around.
+    now: N,
+    /// Reads the measurement.
+    sensor: S,
+    /// Applies the controller output.
+    actuator: A,
+    /// Sampling period, in microseconds.
+    period: u32,
+    /// Start time of the next period, `None` until the first step.
+    deadline: Option<u32>,
+    /// Number of steps that did not fit in their period.
+    overruns: u32,
+}
+
+impl<C, D, N, S, A> HalRunner<C, D, N, S, A> {
+    /// Creates a new `HalRunner` stepping `controller` once every `period`
+    /// microseconds, which should match its sampling time.
+    ///
+    /// `now` returns the time in microseconds of a free running clock, which may wrap
+    /// around. `sensor` returns the measurement and `actuator` applies the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::cell::Cell;
+    /// use control::pid::PID;
+    /// use control::runner::HalRunner;
+    /// use embedded_hal::delay::DelayNs;
+    ///
+    /// // A fake clock advanced by the delay.
+    /// struct Delay<'a>(&'a Cell<u32>);
+    /// impl DelayNs for Delay<'_> {
+    ///     fn delay_ns(&mut self, ns: u32) {
+    ///         self.0.set(self.0.get() + ns / 1000);
+    ///     }
+    /// }
+    ///
+    /// let clock = Cell::new(0);
+    /// let mut valve = 0.0;
+    /// let pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.01, 5.0);
+    /// let mut runner =
+    ///     HalRunner::new(pid, Delay(&clock), 10_000, || clock.get(), || 4.0, |output| valve = output);
+    /// runner.step();
+    /// runner.step();
+    /// assert_eq!(clock.get(), 10_000);
+    /// assert_eq!(runner.overruns(), 0);
+    /// drop(runner);
+    /// assert_eq!(valve, 2.0);
+    /// ```
+    pub fn new(controller: C, delay: D, period: u32, now: N, sensor: S, actuator: A) -> Self {
+        Self { controller, delay, now, sensor, actuator, period, deadline: None, overruns: 0 }
+    }
+
+    /// Returns the number of steps that did not fit in their period.
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+
+    /// Waits for the start of the next period, then reads the sensor, steps the
+    /// controller and drives the actuator. The first call does not wait.
+    pub fn step<T>(&mut self)
+    where
+        C: Controller<T>,
+        D: DelayNs,
+        N: FnMut() -> u32,
+        S: FnMut() -> T,
+        A: FnMut(T),
+    {
+        let now = (self.now)();
+        let start = match self.deadline {
+            Some(deadline) => {
+                // Wrapping difference, positive while the deadline is ahead.
+                let remaining = deadline.wrapping_sub(now) as i32;
+                if remaining > 0 {
+                    self.delay.delay_us(remaining as u32);
+                    deadline
+                } else {
+                    if remaining < 0 {
+                        self.overruns = self.overruns.saturating_add(1);
+                    }
+                    now
+                }
+            }
+            None => now,
+        };
+        self.deadline = Some(start.wrapping_add(self.period));
+
+        let measurement = (self.sensor)();
+        let output = self.controller.step(measurement);
+        (self.actuator)(output);
+    }
+
+    /// Runs the loop forever.
+    pub fn run<T>(&mut self) -> !
+    where
+        C: Controller<T>,
+        D: DelayNs,
+        N: FnMut() -> u32,
+        S: FnMut() -> T,
+        A: FnMut(T),
+    {
+        loop {
+            self.step();
+        }
+    }
+}
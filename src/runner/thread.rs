@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::controller::Controller;
+
+/// Implementation of a loop runner on a dedicated thread, which reads the sensor,
+/// steps the controller and drives the actuator once every sampling period.
+///
+/// The thread sleeps until absolute deadlines, so the time spent in the loop itself
+/// does not make it drift. A step that ends after the start of the next period is
+/// counted as an overrun and the schedule restarts from it.
+#[derive(Debug)]
+pub struct ThreadRunner<C> {
+    /// Set to stop the loop.
+    stop: Arc<AtomicBool>,
+    /// Number of steps that did not fit in their period.
+    overruns: Arc<AtomicU32>,
+    /// Thread running the loop, returning the controller once stopped.
+    thread: JoinHandle<C>,
+}
+
+impl<C: Send + 'static> ThreadRunner<C> {
+    /// Spawns a thread stepping `controller` once every `period`, which should match
+    /// its sampling time.
+    ///
+    /// `sensor` returns the measurement and `actuator` applies the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    ///
+    /// use control::pid::PID;
+    /// use control::runner::ThreadRunner;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.001, 5.0);
+    /// let runner = ThreadRunner::spawn(pid, Duration::from_millis(1), || 4.0, move |output| {
+    ///     let _ = sender.send(output);
+    /// });
+    ///
+    /// assert_eq!(receiver.recv().unwrap(), 2.0);
+    /// let pid = runner.stop();
+    /// assert_eq!(pid.output(), 2.0);
+    /// ```
+    pub fn spawn<T, S, A>(
+        mut controller: C,
+        period: Duration,
+        mut sensor: S,
+        mut actuator: A,
+    ) -> Self
+    where
+        C: Controller<T>,
+        S: FnMut() -> T + Send + 'static,
+        A: FnMut(T) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let overruns = Arc::new(AtomicU32::new(0));
+
+        let thread = {
+            let stop = Arc::clone(&stop);
+            let overruns = Arc::clone(&overruns);
+            thread::spawn(move || {
+                let mut deadline = Instant::now();
+                while !stop.load(Ordering::Relaxed) {
+                    let measurement = sensor();
+                    let output = controller.step(measurement);
+                    actuator(output);
+
+                    deadline += period;
+                    let now = Instant::now();
+                    if deadline > now {
+                        thread::sleep(deadline - now);
+                    } else {
+                        overruns.fetch_add(1, Ordering::Relaxed);
+                        deadline = now;
+                    }
+                }
+                controller
+            })
+        };
+
+        Self { stop, overruns, thread }
+    }
+
+    /// Returns the number of steps that did not fit in their period.
+    pub fn overruns(&self) -> u32 {
+        self.overruns.load(Ordering::Relaxed)
+    }
+
+    /// Stops the loop after its current step and gives the controller back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sensor, the controller or the actuator panicked.
+    pub fn stop(self) -> C {
+        self.stop.store(true, Ordering::Relaxed);
+        self.thread.join().expect("The control loop thread panicked")
+    }
+}
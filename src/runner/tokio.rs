@@ -0,0 +1,92 @@
+use core::future::Future;
+
+use tokio::time::{self, Duration, Interval, MissedTickBehavior};
+
+use crate::controller::Controller;
+
+/// Implementation of an async loop runner on top of Tokio, which reads the sensor,
+/// steps the controller and drives the actuator once every sampling period.
+///
+/// The periods are scheduled by a `tokio::time::Interval`, which does not drift. When
+/// a step takes longer than its period the missed periods are skipped rather than
+/// stepped in a burst, since their measurements would be stale anyway.
+#[derive(Debug)]
+pub struct TokioRunner<C, S, A> {
+    /// Controller being run.
+    pub controller: C,
+
+    /// Interval ticking once every sampling period.
+    interval: Interval,
+    /// Reads the measurement.
+    sensor: S,
+    /// Applies the controller output.
+    actuator: A,
+}
+
+impl<C, S, A> TokioRunner<C, S, A> {
+    /// Creates a new `TokioRunner` stepping `controller` once every `period`, which
+    /// should match its sampling time.
+    ///
+    /// `sensor` returns a future of the measurement and `actuator` a future applying
+    /// the output, so both may talk over the network.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use control::pid::PID;
+    /// use control::runner::TokioRunner;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let pid = PID::new(2.0, 0.0, 0.0, 0.0, 0.001, 5.0);
+    ///     let sensor = || async { 4.0 };
+    ///     let actuator = |output: f64| async move { assert_eq!(output, 2.0) };
+    ///     let mut runner = TokioRunner::new(pid, Duration::from_millis(1), sensor, actuator);
+    ///
+    ///     runner.step().await;
+    ///     assert_eq!(runner.controller.output(), 2.0);
+    /// }
+    /// ```
+    pub fn new(controller: C, period: Duration, sensor: S, actuator: A) -> Self {
+        let mut interval = time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        Self { controller, interval, sensor, actuator }
+    }
+
+    /// Waits for the next tick, then reads the sensor, steps the controller and drives
+    /// the actuator.
+    pub async fn step<T, FS, FA>(&mut self)
+    where
+        C: Controller<T>,
+        S: FnMut() -> FS,
+        FS: Future<Output = T>,
+        A: FnMut(T) -> FA,
+        FA: Future<Output = ()>,
+    {
+        self.interval.tick().await;
+
+        let measurement = (self.sensor)().await;
+        let output = self.controller.step(measurement);
+        (self.actuator)(output).await;
+    }
+
+    /// Runs the loop forever.
+    pub async fn run<T, FS, FA>(&mut self) -> !
+    where
+        C: Controller<T>,
+        S: FnMut() -> FS,
+        FS: Future<Output = T>,
+        A: FnMut(T) -> FA,
+        FA: Future<Output = ()>,
+    {
+        loop {
+            self.step().await;
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! A controller over a minimal numeric trait, for user-defined number types such as
+//! fixed-point wrappers, saturating integers or dimensioned types.
+
+use core::ops::{Add, Mul, Sub};
+
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+
+use crate::controller::Controller;
+use crate::pid::Saturation;
+
+/// The operations [`ScalarPid`] needs from its number type.
+///
+/// It is implemented for every [`FloatCore`] type and can be implemented for any
+/// other type with addition, subtraction, multiplication, comparison and a zero.
+pub trait Scalar:
+    Copy + PartialOrd + Zero + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Restricts the value to the `[min, max]` interval.
+    fn clamp(self, min: Self, max: Self) -> Self {
+        if self < min {
+            min
+        } else if self > max {
+            max
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: FloatCore> Scalar for T {}
+
+/// Implementation of a proportional–integral–derivative controller over any
+/// [`Scalar`] type.
+///
+/// As the number type may not support division or casts, the gains are given already
+/// discretized. With a proportional gain `kp`, an integral gain `ki`, a derivative
+/// gain `kd` and a sampling time `Ts`, the coefficients are `kp`, `ki·Ts/2` and
+/// `kd/Ts`. The derivative is a plain backward difference, so it produces the same
+/// steps as a [`PID`](crate::pid::PID) whose derivative filter time constant is
+/// `Ts/2`, which puts the filter pole at zero, without rate limit or bias. A `PID`
+/// with a time constant of zero differs, its pole of minus one makes the derivative
+/// alternate in sign.
+///
+/// It is a separate type because the `PID` step cannot be written over these
+/// operations alone: its unbounded limits are infinities, its coefficients and filter
+/// pole are computed with divisions and casts, and its soft clamp, output inversion
+/// and rate limits need more arithmetic than a fixed-point or saturating integer type
+/// provides.
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::scalar::ScalarPid;
+///
+/// let (kp, ki, kd, ts) = (2.0, 1.0, 0.5, 0.5);
+/// let mut pid = PID::new(kp, ki, kd, ts / 2.0, ts, 3.0);
+/// let mut scalar = ScalarPid::new(kp, ki * ts / 2.0, kd / ts, 3.0);
+/// for measurement in [1.0, 1.5, 2.5, 2.0] {
+///     assert_eq!(pid.step(measurement), scalar.step(measurement));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ScalarPid<T> {
+    /// Desired setpoint.
+    pub setpoint: T,
+
+    /// Proportional coefficient.
+    p: T,
+    /// Integral coefficient, half the integral gain times the sampling time.
+    i: T,
+    /// Derivative coefficient, the derivative gain over the sampling time.
+    d: T,
+
+    /// Bounds of the integral term.
+    integral_bounds: Option<(T, T)>,
+    /// Bounds of the controller output.
+    output_bounds: Option<(T, T)>,
+
+    /// Error value from the previous update.
+    error: T,
+    /// Integral value from the previous update.
+    integral: T,
+    /// Derivative value from the previous update.
+    derivative: T,
+    /// Previous measurement value, `None` before the first update.
+    measurement: Option<T>,
+    /// Output of the previous update.
+    output: T,
+    /// Whether the output of the previous update was clamped.
+    saturation: Saturation,
+}
+
+impl<T: Scalar> ScalarPid<T> {
+    /// Creates a new `ScalarPid` with the discretized proportional, integral and
+    /// derivative coefficients `p`, `i` and `d`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::{Add, Mul, Sub};
+    ///
+    /// use control::scalar::{Scalar, ScalarPid};
+    /// use num_traits::Zero;
+    ///
+    /// // A Q16.16 fixed-point number.
+    /// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    /// struct Fixed(i32);
+    ///
+    /// impl Add for Fixed {
+    ///     type Output = Self;
+    ///     fn add(self, other: Self) -> Self {
+    ///         Fixed(self.0.saturating_add(other.0))
+    ///     }
+    /// }
+    /// impl Sub for Fixed {
+    ///     type Output = Self;
+    ///     fn sub(self, other: Self) -> Self {
+    ///         Fixed(self.0.saturating_sub(other.0))
+    ///     }
+    /// }
+    /// impl Mul for Fixed {
+    ///     type Output = Self;
+    ///     fn mul(self, other: Self) -> Self {
+    ///         Fixed(((i64::from(self.0) * i64::from(other.0)) >> 16) as i32)
+    ///     }
+    /// }
+    /// impl Zero for Fixed {
+    ///     fn zero() -> Self {
+    ///         Fixed(0)
+    ///     }
+    ///     fn is_zero(&self) -> bool {
+    ///         self.0 == 0
+    ///     }
+    /// }
+    /// impl Scalar for Fixed {}
+    ///
+    /// let one = Fixed(1 << 16);
+    /// let mut pid = ScalarPid::new(Fixed(2 << 16), Fixed::zero(), Fixed::zero(), Fixed(5 << 16));
+    /// pid.bound_output(Fixed::zero(), Fixed(6 << 16));
+    /// assert_eq!(pid.step(one), Fixed(6 << 16));
+    /// assert_eq!(pid.step(Fixed(3 << 16)), Fixed(4 << 16));
+    /// ```
+    pub fn new(p: T, i: T, d: T, setpoint: T) -> Self {
+        Self {
+            setpoint,
+            p,
+            i,
+            d,
+            integral_bounds: None,
+            output_bounds: None,
+            error: T::zero(),
+            integral: T::zero(),
+            derivative: T::zero(),
+            measurement: None,
+            output: T::zero(),
+            saturation: Saturation::Unsaturated,
+        }
+    }
+
+    /// Changes the discretized proportional, integral and derivative coefficients.
+    ///
+    /// The integral term is kept as is, so changing the integral coefficient does not
+    /// bump the output.
+    pub fn set_gains(&mut self, p: T, i: T, d: T) -> &mut Self {
+        self.p = p;
+        self.i = i;
+        self.d = d;
+        self
+    }
+
+    /// Restricts the integral term to the `[min, max]` interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` > `max`.
+    pub fn bound_integral(&mut self, min: T, max: T) -> &mut Self {
+        assert!(min <= max, "The lower bound is larger than the upper bound");
+        self.integral_bounds = Some((min, max));
+        self
+    }
+
+    /// Restricts the output to the `[min, max]` interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` > `max`.
+    pub fn bound_output(&mut self, min: T, max: T) -> &mut Self {
+        assert!(min <= max, "The lower bound is larger than the upper bound");
+        self.output_bounds = Some((min, max));
+        self
+    }
+
+    /// Returns the output of the last step.
+    pub fn output(&self) -> T {
+        self.output
+    }
+
+    /// Returns whether the output of the last step had to be clamped.
+    pub fn saturation(&self) -> Saturation {
+        self.saturation
+    }
+
+    /// Recomputes the integral term so that the last step would have produced
+    /// `output`, for bumpless transfers and external anti-windup.
+    pub fn track(&mut self, output: T) -> &mut Self {
+        let integral = output - self.p * self.error - self.derivative;
+        self.integral = bound(integral, self.integral_bounds);
+        self.output = output;
+        self
+    }
+
+    /// Clears the controller state, as if it had just been created. The setpoint,
+    /// gains and bounds are kept.
+    pub fn reset(&mut self) {
+        self.error = T::zero();
+        self.integral = T::zero();
+        self.derivative = T::zero();
+        self.measurement = None;
+        self.output = T::zero();
+        self.saturation = Saturation::Unsaturated;
+    }
+
+    /// Performs a single step of the control loop. It should be called exactly once
+    /// every sampling time.
+    pub fn step(&mut self, measurement: T) -> T {
+        let error = self.setpoint - measurement;
+
+        let proportional = self.p * error;
+        // Derivative on measurement to prevent a kick during setpoint changes, the
+        // first measurement being its own previous one.
+        let previous = self.measurement.unwrap_or(measurement);
+        let derivative = self.d * (previous - measurement);
+        let increment = self.i * (error + self.error);
+        let integral = bound(self.integral + increment, self.integral_bounds);
+
+        // Stop integrating while the output is already saturated in the direction the
+        // integral is moving.
+        let unclamped = proportional + integral + derivative;
+        let windup = match self.output_bounds {
+            Some((min, max)) => {
+                (unclamped > max && increment > T::zero())
+                    || (unclamped < min && increment < T::zero())
+            }
+            None => false,
+        };
+        if !windup {
+            self.integral = integral;
+        }
+        self.derivative = derivative;
+        self.error = error;
+        self.measurement = Some(measurement);
+
+        let output = proportional + self.integral + derivative;
+        self.saturation = match self.output_bounds {
+            Some((_, max)) if output > max => Saturation::Upper,
+            Some((min, _)) if output < min => Saturation::Lower,
+            _ => Saturation::Unsaturated,
+        };
+        self.output = bound(output, self.output_bounds);
+        self.output
+    }
+}
+
+impl<T: Scalar> Controller<T> for ScalarPid<T> {
+    fn step(&mut self, measurement: T) -> T {
+        ScalarPid::step(self, measurement)
+    }
+
+    fn setpoint(&self) -> T {
+        self.setpoint
+    }
+
+    fn set_setpoint(&mut self, setpoint: T) {
+        self.setpoint = setpoint;
+    }
+
+    fn output(&self) -> T {
+        self.output
+    }
+
+    fn saturation(&self) -> Saturation {
+        self.saturation
+    }
+
+    fn limit_output(&mut self, min: T, max: T) {
+        self.bound_output(min, max);
+    }
+
+    fn track(&mut self, output: T) {
+        ScalarPid::track(self, output);
+    }
+
+    fn reset(&mut self) {
+        ScalarPid::reset(self);
+    }
+}
+
+/// Restricts `value` to `bounds`, if any.
+fn bound<T: Scalar>(value: T, bounds: Option<(T, T)>) -> T {
+    match bounds {
+        Some((min, max)) => value.clamp(min, max),
+        None => value,
+    }
+}
@@ -0,0 +1,96 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Which of the controller outputs is passed on by a [`Selector`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Select {
+    /// The smallest output.
+    Low,
+    /// The largest output.
+    High,
+}
+
+/// Implementation of override control, where `N` controllers compete for a single
+/// actuator and only the limiting one is passed on.
+///
+/// The controllers that are not selected are kept tracking the selected output, so
+/// any of them can take over bumplessly when its constraint becomes the limiting one.
+#[derive(Clone, Copy, Debug)]
+pub struct Selector<T, const N: usize> {
+    /// Competing controllers.
+    pub controllers: [PID<T>; N],
+
+    /// Which output is passed on.
+    select: Select,
+    /// Index of the controller selected in the last step.
+    selected: usize,
+}
+
+impl<T: FloatCore, const N: usize> Selector<T, N> {
+    /// Creates a new `Selector` that passes on the output picked by `select`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::selector::{Select, Selector};
+    ///
+    /// // Heat towards 80 degrees as long as the pressure stays below 5 bar.
+    /// let temperature = PID::new(4.0, 0.5, 0.0, 0.0, 1.0, 80.0);
+    /// let pressure = PID::new(20.0, 2.0, 0.0, 0.0, 1.0, 5.0);
+    ///
+    /// let mut selector = Selector::new([temperature, pressure], Select::Low);
+    /// selector.step([20.0, 4.9]);
+    /// assert_eq!(selector.selected(), 1);
+    /// ```
+    pub fn new(controllers: [PID<T>; N], select: Select) -> Self {
+        assert!(N > 0);
+        Self { controllers, select, selected: 0 }
+    }
+
+    /// Returns the index of the controller selected in the last step.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Clears the state of every controller.
+    pub fn reset(&mut self) {
+        for controller in self.controllers.iter_mut() {
+            controller.reset();
+        }
+        self.selected = 0;
+    }
+
+    /// Steps every controller with its own measurement and returns the selected output.
+    pub fn step(&mut self, measurements: [T; N]) -> T {
+        let mut selected = 0;
+        let mut output = T::zero();
+        for (k, (controller, &measurement)) in
+            self.controllers.iter_mut().zip(measurements.iter()).enumerate()
+        {
+            let candidate = controller.step(measurement);
+            let better = match self.select {
+                Select::Low => candidate < output,
+                Select::High => candidate > output,
+            };
+            if k == 0 || better {
+                selected = k;
+                output = candidate;
+            }
+        }
+
+        for (k, controller) in self.controllers.iter_mut().enumerate() {
+            if k != selected {
+                controller.track(output);
+            }
+        }
+
+        self.selected = selected;
+        output
+    }
+}
@@ -0,0 +1,176 @@
+use num_traits::float::FloatCore;
+
+use crate::cascade::Cascade;
+use crate::filter::Exponential;
+use crate::pid::PID;
+
+/// Implementation of a position servo for motor control: a position loop whose output
+/// is the velocity setpoint of a faster velocity loop, both fed from the counts of an
+/// incremental encoder.
+///
+/// The position controller is proportional only, the velocity controller is a PI
+/// driving the motor effort, such as a current or a duty cycle. The velocity is
+/// estimated from the count difference over each step, optionally smoothed by a
+/// low-pass filter. Positions are in units of `1 / counts_per_unit` counts, for
+/// instance revolutions or millimetres, and velocities in units per second.
+#[derive(Clone, Copy, Debug)]
+pub struct Servo<T> {
+    /// Position loop driving the velocity loop.
+    pub cascade: Cascade<PID<T>, PID<T>>,
+
+    /// Position change of a single count.
+    units_per_count: T,
+    /// Sampling time of the velocity loop.
+    sampling_time: T,
+    /// Low-pass filter of the velocity estimate.
+    filter: Exponential<T>,
+    /// Encoder counts of the previous step, `None` until the first step.
+    counts: Option<i32>,
+    /// Position of the last step.
+    position: T,
+    /// Filtered velocity of the last step.
+    velocity: T,
+}
+
+impl<T: FloatCore> Servo<T> {
+    /// Creates a new `Servo` with a position loop of gain `position_kp` and a
+    /// velocity loop of gains `velocity_kp` and `velocity_ki`.
+    ///
+    /// The velocity loop steps every `sampling_time` seconds and the position loop
+    /// once every `ratio` velocity steps. The encoder reads `counts_per_unit` counts
+    /// per unit of position. The velocity and effort are left unbounded and the
+    /// velocity estimate unfiltered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::servo::Servo;
+    ///
+    /// // A 4096 counts per revolution encoder, the velocity loop at 1 kHz and the
+    /// // position loop at 250 Hz.
+    /// let mut servo = Servo::new(20.0, 0.5, 10.0, 0.001, 4, 4096.0);
+    /// servo.limit(5.0, 1.0).filter_velocity(0.002);
+    /// servo.set_target(2.0);
+    ///
+    /// // The motor is at rest on the first reading, at the zero position.
+    /// let effort = servo.step(0);
+    /// assert_eq!(effort, 1.0);
+    /// assert_eq!(servo.cascade.secondary.setpoint, 5.0);
+    ///
+    /// servo.step(4);
+    /// assert_eq!(servo.position(), 4.0 / 4096.0);
+    /// assert!(servo.velocity() > 0.0);
+    /// ```
+    pub fn new(
+        position_kp: T,
+        velocity_kp: T,
+        velocity_ki: T,
+        sampling_time: T,
+        ratio: u32,
+        counts_per_unit: T,
+    ) -> Self {
+        let zero = T::zero();
+        let position_sampling_time =
+            sampling_time * T::from(ratio).expect("Unable to cast the ratio");
+        let position = PID::new(position_kp, zero, zero, zero, position_sampling_time, zero);
+        let velocity = PID::new(velocity_kp, velocity_ki, zero, zero, sampling_time, zero);
+
+        Self {
+            cascade: Cascade::new(position, velocity, ratio),
+            units_per_count: T::one() / counts_per_unit,
+            sampling_time,
+            filter: Exponential::new(T::one()),
+            counts: None,
+            position: zero,
+            velocity: zero,
+        }
+    }
+
+    /// Restricts the velocity setpoint to `[-max_velocity, max_velocity]` and the
+    /// effort, integral term included, to `[-max_effort, max_effort]`.
+    ///
+    /// The position loop does not wind up while the velocity loop saturates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_velocity` or `max_effort` are negative.
+    pub fn limit(&mut self, max_velocity: T, max_effort: T) -> &mut Self {
+        self.cascade.primary.bound_output(-max_velocity..=max_velocity);
+        self.cascade
+            .secondary
+            .bound_output(-max_effort..=max_effort)
+            .bound_integral(-max_effort..=max_effort);
+        self
+    }
+
+    /// Smooths the velocity estimate with a low-pass filter of time constant `tau`
+    /// seconds, zero disabling it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tau` is negative.
+    pub fn filter_velocity(&mut self, tau: T) -> &mut Self {
+        self.filter = Exponential::from_time_constant(tau, self.sampling_time);
+        self
+    }
+
+    /// Changes the position to reach.
+    pub fn set_target(&mut self, position: T) -> &mut Self {
+        self.cascade.primary.setpoint = position;
+        self
+    }
+
+    /// Returns the position to reach.
+    pub fn target(&self) -> T {
+        self.cascade.primary.setpoint
+    }
+
+    /// Returns the position of the last step.
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    /// Returns the filtered velocity of the last step.
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    /// Clears the state of both loops and of the velocity estimate. The next step
+    /// takes its encoder counts as the position again.
+    pub fn reset(&mut self) {
+        self.cascade.reset();
+        self.filter.reset();
+        self.counts = None;
+        self.position = T::zero();
+        self.velocity = T::zero();
+    }
+
+    /// Performs a single step of the velocity loop, and of the position loop when it
+    /// is due, from the `counts` of a free running encoder counter, and returns the
+    /// effort. It should be called exactly once every sampling time.
+    ///
+    /// The counter may wrap around, narrower hardware counters must be sign extended
+    /// to 32 bits first. The first step takes the counts as the position, at rest. A
+    /// reading that cannot be converted to `T` holds the position and velocity
+    /// estimates.
+    pub fn step(&mut self, counts: i32) -> T {
+        let delta = match self.counts {
+            Some(previous) => T::from(counts.wrapping_sub(previous)),
+            None => T::from(counts).map(|counts| {
+                self.position = counts * self.units_per_count;
+                T::zero()
+            }),
+        };
+        if let Some(delta) = delta {
+            self.counts = Some(counts);
+            let distance = delta * self.units_per_count;
+            self.position = self.position + distance;
+            self.velocity = self.filter.step(distance / self.sampling_time);
+        }
+        self.cascade.step(self.position, self.velocity)
+    }
+}
@@ -0,0 +1,135 @@
+//! Sharing a controller between an interrupt handler stepping it and the rest of the
+//! firmware tuning it.
+
+use core::cell::RefCell;
+use core::ops::RangeBounds;
+
+use critical_section::Mutex;
+use num_traits::float::FloatCore;
+
+use crate::pid::{StepRecord, PID};
+
+/// A [`PID`] controller shared between contexts through critical sections, split into
+/// a [`Stepper`] handle for the control interrupt and a [`Tuner`] handle for the
+/// thread or idle context.
+///
+/// Every access happens within a critical section, so setpoint and gain changes never
+/// tear a step.
+pub struct SharedPid<T> {
+    /// Shared controller.
+    pid: Mutex<RefCell<PID<T>>>,
+}
+
+/// The real-time handle of a [`SharedPid`], stepping the controller.
+#[derive(Clone, Copy)]
+pub struct Stepper<'a, T> {
+    /// Shared controller.
+    shared: &'a SharedPid<T>,
+}
+
+/// The tuning handle of a [`SharedPid`], changing the controller configuration.
+#[derive(Clone, Copy)]
+pub struct Tuner<'a, T> {
+    /// Shared controller.
+    shared: &'a SharedPid<T>,
+}
+
+impl<T: FloatCore> SharedPid<T> {
+    /// Creates a new `SharedPid` from a controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::shared::SharedPid;
+    ///
+    /// let mut shared = SharedPid::new(PID::new(1.0, 0.0, 0.0, 0.0, 0.01, 0.0));
+    /// let (stepper, tuner) = shared.split();
+    ///
+    /// // From the thread context.
+    /// tuner.set_setpoint(10.0);
+    /// tuner.set_gains(2.0, 0.0, 0.0);
+    ///
+    /// // From the control interrupt.
+    /// assert_eq!(stepper.step(8.0), 4.0);
+    /// assert_eq!(tuner.record().output, 4.0);
+    /// ```
+    pub fn new(pid: PID<T>) -> Self {
+        Self { pid: Mutex::new(RefCell::new(pid)) }
+    }
+
+    /// Splits the shared controller into its real-time and tuning handles.
+    pub fn split(&mut self) -> (Stepper<'_, T>, Tuner<'_, T>) {
+        let shared = &*self;
+        (Stepper { shared }, Tuner { shared })
+    }
+
+    /// Gives the shared controller back.
+    pub fn into_inner(self) -> PID<T> {
+        self.pid.into_inner().into_inner()
+    }
+
+    /// Calls `f` on the controller within a critical section.
+    fn with<R>(&self, f: impl FnOnce(&mut PID<T>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.pid.borrow(cs).borrow_mut()))
+    }
+}
+
+impl<T: FloatCore> Stepper<'_, T> {
+    /// Performs a single step of the control loop, see [`PID::step`].
+    pub fn step(&self, measurement: T) -> T {
+        self.shared.with(|pid| pid.step(measurement))
+    }
+
+    /// Performs a single step of the control loop with a feedforward term, see
+    /// [`PID::step_with_feedforward`].
+    pub fn step_with_feedforward(&self, measurement: T, feedforward: T) -> T {
+        self.shared.with(|pid| pid.step_with_feedforward(measurement, feedforward))
+    }
+}
+
+impl<T: FloatCore> Tuner<'_, T> {
+    /// Changes the setpoint.
+    pub fn set_setpoint(&self, setpoint: T) {
+        self.shared.with(|pid| pid.setpoint = setpoint);
+    }
+
+    /// Changes the gains, see [`PID::set_gains`].
+    pub fn set_gains(&self, kp: T, ki: T, kd: T) {
+        self.shared.with(|pid| {
+            pid.set_gains(kp, ki, kd);
+        });
+    }
+
+    /// Restricts the controller output, see [`PID::bound_output`].
+    pub fn bound_output<R: RangeBounds<T>>(&self, range: R) {
+        self.shared.with(|pid| {
+            pid.bound_output(range);
+        });
+    }
+
+    /// Switches the controller to manual mode, see [`PID::set_manual`].
+    pub fn set_manual(&self, output: T) {
+        self.shared.with(|pid| {
+            pid.set_manual(output);
+        });
+    }
+
+    /// Switches the controller back to automatic mode.
+    pub fn set_automatic(&self) {
+        self.shared.with(|pid| {
+            pid.set_automatic();
+        });
+    }
+
+    /// Returns a snapshot of the last step.
+    pub fn record(&self) -> StepRecord<T> {
+        self.shared.with(|pid| pid.record())
+    }
+
+    /// Calls `f` on the controller within a critical section, for any other change.
+    /// `f` should be short, as the control interrupt is held off meanwhile.
+    pub fn update<R>(&self, f: impl FnOnce(&mut PID<T>) -> R) -> R {
+        self.shared.with(f)
+    }
+}
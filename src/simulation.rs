@@ -0,0 +1,41 @@
+//! Models of the controlled process, to simulate a control loop on the host before
+//! deploying it to hardware.
+
+mod closed_loop;
+#[cfg(feature = "std")]
+mod csv;
+mod first_order;
+mod fopdt;
+#[cfg(feature = "std")]
+mod golden;
+mod integrator;
+#[cfg(feature = "std")]
+mod monte_carlo;
+mod noise;
+mod second_order;
+
+pub use self::closed_loop::{Sample, Simulation, Steps};
+#[cfg(feature = "std")]
+pub use self::csv::{write_csv, write_records_csv};
+pub use self::first_order::FirstOrder;
+pub use self::fopdt::Fopdt;
+#[cfg(feature = "std")]
+pub use self::golden::{read_vectors, verify, Mismatch, Vector};
+pub use self::integrator::Integrator;
+#[cfg(feature = "std")]
+pub use self::monte_carlo::{MonteCarlo, Robustness};
+pub use self::noise::NoiseSource;
+pub use self::second_order::SecondOrder;
+
+/// A discrete-time model of a controlled process, driven by the controller output.
+pub trait Plant<T> {
+    /// Applies `input` for one sampling period and returns the new output of the
+    /// process, the measurement seen by the controller on its next step.
+    fn step(&mut self, input: T) -> T;
+
+    /// Returns the current output of the process.
+    fn output(&self) -> T;
+
+    /// Brings the process back to rest, with a zero output.
+    fn reset(&mut self);
+}
@@ -0,0 +1,147 @@
+use num_traits::float::FloatCore;
+
+use super::Plant;
+use crate::controller::Controller;
+
+/// A single step of a simulated control loop.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample<T> {
+    /// Setpoint of the controller.
+    pub setpoint: T,
+    /// Measurement the controller stepped on.
+    pub measurement: T,
+    /// Controller output.
+    pub output: T,
+    /// Load disturbance added to the controller output at the plant input.
+    pub disturbance: T,
+}
+
+/// Implementation of a closed-loop simulation, wiring a controller to a plant so a
+/// tuning can be checked in a host-side unit test.
+#[derive(Clone, Copy, Debug)]
+pub struct Simulation<C, P> {
+    /// Simulated controller.
+    pub controller: C,
+    /// Simulated plant.
+    pub plant: P,
+}
+
+impl<C, P> Simulation<C, P> {
+    /// Creates a new `Simulation` of `controller` driving `plant`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::simulation::{FirstOrder, Sample, Simulation};
+    ///
+    /// let pid = PID::new(2.0, 1.0, 0.0, 0.0, 0.1, 0.0);
+    /// let mut simulation = Simulation::new(pid, FirstOrder::new(1.0, 2.0, 0.1));
+    ///
+    /// // A setpoint step to 1, then a load disturbance after 30 seconds.
+    /// let mut trajectory = [Sample::default(); 600];
+    /// simulation.run(&mut trajectory, |_| 1.0, |k| if k < 300 { 0.0 } else { -0.5 });
+    ///
+    /// let before = trajectory[299].measurement;
+    /// let after = trajectory[599].measurement;
+    /// assert!((before - 1.0_f64).abs() < 1e-3);
+    /// assert!((after - 1.0_f64).abs() < 1e-3);
+    /// ```
+    pub fn new(controller: C, plant: P) -> Self {
+        Self { controller, plant }
+    }
+
+    /// Performs a single step of the loop with the given `setpoint` and load
+    /// `disturbance`.
+    pub fn step<T>(&mut self, setpoint: T, disturbance: T) -> Sample<T>
+    where
+        T: FloatCore,
+        C: Controller<T>,
+        P: Plant<T>,
+    {
+        let measurement = self.plant.output();
+        self.controller.set_setpoint(setpoint);
+        let output = self.controller.step(measurement);
+        self.plant.step(output + disturbance);
+        Sample { setpoint, measurement, output, disturbance }
+    }
+
+    /// Performs as many steps of the loop as there are samples in `trajectory` and
+    /// records them there.
+    ///
+    /// The setpoint and load disturbance of each step are given by closures of the
+    /// step index, such as `|_| 1.0` for a constant or `|k| values[k]` for a sequence.
+    pub fn run<T, S, D>(&mut self, trajectory: &mut [Sample<T>], setpoint: S, disturbance: D)
+    where
+        T: FloatCore,
+        C: Controller<T>,
+        P: Plant<T>,
+        S: FnMut(usize) -> T,
+        D: FnMut(usize) -> T,
+    {
+        for (sample, step) in trajectory.iter_mut().zip(self.iter(setpoint, disturbance)) {
+            *sample = step;
+        }
+    }
+
+    /// Returns an endless iterator performing a step of the loop for each item, the
+    /// setpoint and load disturbance of each step being given by closures of the step
+    /// index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::simulation::{FirstOrder, Simulation};
+    ///
+    /// let pid = PID::new(2.0, 1.0, 0.0, 0.0, 0.1, 0.0);
+    /// let mut simulation = Simulation::new(pid, FirstOrder::new(1.0, 2.0, 0.1));
+    /// let rising = simulation
+    ///     .iter(|_| 1.0, |_| 0.0)
+    ///     .take_while(|sample| sample.measurement < 0.9_f64)
+    ///     .count();
+    /// assert!(rising < 50);
+    /// ```
+    pub fn iter<T, S, D>(&mut self, setpoint: S, disturbance: D) -> Steps<'_, C, P, S, D>
+    where
+        T: FloatCore,
+        C: Controller<T>,
+        P: Plant<T>,
+        S: FnMut(usize) -> T,
+        D: FnMut(usize) -> T,
+    {
+        Steps { simulation: self, setpoint, disturbance, index: 0 }
+    }
+}
+
+/// Endless iterator over the steps of a [`Simulation`], created by
+/// [`Simulation::iter`].
+#[derive(Debug)]
+pub struct Steps<'a, C, P, S, D> {
+    /// Simulation being stepped.
+    simulation: &'a mut Simulation<C, P>,
+    /// Setpoint of each step.
+    setpoint: S,
+    /// Load disturbance of each step.
+    disturbance: D,
+    /// Index of the next step.
+    index: usize,
+}
+
+impl<'a, T, C, P, S, D> Iterator for Steps<'a, C, P, S, D>
+where
+    T: FloatCore,
+    C: Controller<T>,
+    P: Plant<T>,
+    S: FnMut(usize) -> T,
+    D: FnMut(usize) -> T,
+{
+    type Item = Sample<T>;
+
+    fn next(&mut self) -> Option<Sample<T>> {
+        let setpoint = (self.setpoint)(self.index);
+        let disturbance = (self.disturbance)(self.index);
+        self.index += 1;
+        Some(self.simulation.step(setpoint, disturbance))
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::{self, Write};
+
+use num_traits::float::FloatCore;
+
+use super::Sample;
+use crate::pid::StepRecord;
+
+/// Writes a simulated trajectory as CSV, with a header and one row per step.
+///
+/// The columns are the time in seconds, the setpoint, the measurement, the controller
+/// output and the load disturbance.
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::simulation::{write_csv, Integrator, Sample, Simulation};
+///
+/// let pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.5, 0.0);
+/// let mut simulation = Simulation::new(pid, Integrator::new(1.0, 0.5));
+/// let mut trajectory = [Sample::default(); 2];
+/// simulation.run(&mut trajectory, |_| 2.0, |_| 0.0);
+///
+/// let mut csv = Vec::new();
+/// write_csv(&mut csv, 0.5, &trajectory).unwrap();
+/// assert_eq!(
+///     String::from_utf8(csv).unwrap(),
+///     "time,setpoint,measurement,output,disturbance\n0,2,0,2,0\n0.5,2,1,1,0\n"
+/// );
+/// ```
+pub fn write_csv<T: FloatCore, W: Write>(
+    mut writer: W,
+    sampling_time: T,
+    trajectory: &[Sample<T>],
+) -> io::Result<()> {
+    writeln!(writer, "time,setpoint,measurement,output,disturbance")?;
+    for (k, sample) in trajectory.iter().enumerate() {
+        let values = [sample.setpoint, sample.measurement, sample.output, sample.disturbance];
+        write_row(&mut writer, time(k, sampling_time), &values)?;
+    }
+    Ok(())
+}
+
+/// Writes the records of consecutive controller steps as CSV, with a header and one
+/// row per step.
+///
+/// The columns are the time in seconds, the setpoint, the measurement, the
+/// proportional, integral, derivative and feedforward terms and the controller output.
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::simulation::write_records_csv;
+///
+/// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 2.0);
+/// pid.step(0.5);
+///
+/// let mut csv = Vec::new();
+/// write_records_csv(&mut csv, 0.1, &[pid.record()]).unwrap();
+/// let csv = String::from_utf8(csv).unwrap();
+/// assert_eq!(csv.lines().nth(1), Some("0,2,0.5,1.5,0,0,0,1.5"));
+/// ```
+pub fn write_records_csv<'a, T, W, I>(mut writer: W, sampling_time: T, records: I) -> io::Result<()>
+where
+    T: FloatCore + 'a,
+    W: Write,
+    I: IntoIterator<Item = &'a StepRecord<T>>,
+{
+    writeln!(
+        writer,
+        "time,setpoint,measurement,proportional,integral,derivative,feedforward,output"
+    )?;
+    for (k, record) in records.into_iter().enumerate() {
+        let values = [
+            record.setpoint,
+            record.measurement,
+            record.proportional,
+            record.integral,
+            record.derivative,
+            record.feedforward,
+            record.output,
+        ];
+        write_row(&mut writer, time(k, sampling_time), &values)?;
+    }
+    Ok(())
+}
+
+/// Returns the time of the `k`-th step.
+fn time<T: FloatCore>(k: usize, sampling_time: T) -> T {
+    T::from(k).expect("Unable to cast the step index") * sampling_time
+}
+
+/// Writes a row made of the time followed by the values.
+fn write_row<T: FloatCore, W: Write>(writer: &mut W, time: T, values: &[T]) -> io::Result<()> {
+    write!(writer, "{}", number(time))?;
+    for &value in values {
+        write!(writer, ",{}", number(value))?;
+    }
+    writeln!(writer)
+}
+
+/// Converts a value to a `f64` for printing, without the sign of a negative zero.
+fn number<T: FloatCore>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN) + 0.0
+}
@@ -0,0 +1,57 @@
+use num_traits::float::{Float, FloatCore};
+
+use super::Plant;
+
+/// Implementation of a first-order process, `K / (τs + 1)`, discretized exactly for an
+/// input held constant over each sampling period.
+#[derive(Clone, Copy, Debug)]
+pub struct FirstOrder<T> {
+    /// Static gain.
+    gain: T,
+    /// Pole of the discretized process.
+    pole: T,
+
+    /// Output of the process.
+    output: T,
+}
+
+impl<T: Float + FloatCore> FirstOrder<T> {
+    /// Creates a new `FirstOrder` process at rest, with a static gain of `gain` and a
+    /// time constant of `time_constant` seconds.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `time_constant` or `sampling_time` are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::simulation::{FirstOrder, Plant};
+    ///
+    /// let mut heater = FirstOrder::new(2.0_f64, 10.0, 0.1);
+    /// // After one time constant, 63% of the final value is reached.
+    /// let output = (0..100).fold(0.0, |_, _| heater.step(1.0));
+    /// assert!((output - 2.0 * (1.0 - (-1.0_f64).exp())).abs() < 1e-9);
+    /// ```
+    pub fn new(gain: T, time_constant: T, sampling_time: T) -> Self {
+        assert!(time_constant > T::zero() && sampling_time > T::zero());
+        Self { gain, pole: Float::exp(-sampling_time / time_constant), output: T::zero() }
+    }
+}
+
+impl<T: Float + FloatCore> Plant<T> for FirstOrder<T> {
+    fn step(&mut self, input: T) -> T {
+        self.output = self.pole * self.output + (T::one() - self.pole) * self.gain * input;
+        self.output
+    }
+
+    fn output(&self) -> T {
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = T::zero();
+    }
+}
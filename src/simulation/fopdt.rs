@@ -0,0 +1,56 @@
+use num_traits::float::{Float, FloatCore};
+
+use super::{FirstOrder, Plant};
+use crate::filter::Delay;
+
+/// Implementation of a first-order plus dead time process, `K e^(-θs) / (τs + 1)`,
+/// the usual model of thermal and chemical processes, with a dead time of `D`
+/// sampling periods.
+#[derive(Clone, Copy, Debug)]
+pub struct Fopdt<T, const D: usize> {
+    /// Process without its dead time.
+    lag: FirstOrder<T>,
+    /// Inputs of the last `D` steps, waiting to reach the process.
+    delay: Delay<T, D>,
+}
+
+impl<T: Float + FloatCore, const D: usize> Fopdt<T, D> {
+    /// Creates a new `Fopdt` process at rest, with a static gain of `gain`, a time
+    /// constant of `time_constant` seconds and a dead time of `D` steps.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `time_constant` or `sampling_time` are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::simulation::{Fopdt, Plant};
+    ///
+    /// let mut oven = Fopdt::<f64, 3>::new(1.0, 5.0, 1.0);
+    /// let outputs: Vec<f64> = (0..5).map(|_| oven.step(1.0)).collect();
+    /// assert_eq!(outputs[..3], [0.0; 3]);
+    /// assert!(outputs[3] > 0.0);
+    /// ```
+    pub fn new(gain: T, time_constant: T, sampling_time: T) -> Self {
+        Self { lag: FirstOrder::new(gain, time_constant, sampling_time), delay: Delay::new() }
+    }
+}
+
+impl<T: Float + FloatCore, const D: usize> Plant<T> for Fopdt<T, D> {
+    fn step(&mut self, input: T) -> T {
+        let input = self.delay.step(input);
+        self.lag.step(input)
+    }
+
+    fn output(&self) -> T {
+        self.lag.output()
+    }
+
+    fn reset(&mut self) {
+        self.lag.reset();
+        self.delay.reset();
+    }
+}
@@ -0,0 +1,142 @@
+use std::format;
+use std::io::{self, BufRead};
+use std::string::String;
+use std::vec::Vec;
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Input, PID};
+
+/// A single step of a golden reference, such as one generated by Simulink or
+/// python-control: the inputs of the controller and the output it should produce.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<T> {
+    /// Setpoint of the step, kept from the previous step when `None`.
+    pub setpoint: Option<T>,
+    /// The inputs of the step.
+    pub input: Input<T>,
+    /// The expected output.
+    pub output: T,
+}
+
+/// The first step of a golden reference where the controller did not produce the
+/// expected output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mismatch<T> {
+    /// Index of the step.
+    pub index: usize,
+    /// Output of the reference.
+    pub expected: T,
+    /// Output of the controller.
+    pub actual: T,
+}
+
+/// Reads golden reference vectors from CSV, with a header and one row per step.
+///
+/// The columns are found by name in the header and may come in any order. The
+/// `measurement` and `output` columns are required, `setpoint`, `feedforward` and
+/// `rate` are optional and any other column, such as the time, is ignored. An empty
+/// `setpoint` or `rate` cell leaves it unset for that step.
+///
+/// # Examples
+///
+/// ```
+/// use control::simulation::read_vectors;
+///
+/// let csv = "time,setpoint,measurement,output\n0,2,0.5,1.5\n0.1,,1,1\n";
+/// let vectors = read_vectors::<f64, _>(csv.as_bytes()).unwrap();
+/// assert_eq!(vectors.len(), 2);
+/// assert_eq!(vectors[0].setpoint, Some(2.0));
+/// assert_eq!(vectors[1].setpoint, None);
+/// assert_eq!(vectors[1].input.measurement, 1.0);
+/// ```
+pub fn read_vectors<T: FloatCore, R: BufRead>(reader: R) -> io::Result<Vec<Vector<T>>> {
+    let mut lines = reader.lines();
+    let header = lines.next().ok_or_else(|| invalid(String::from("missing header")))??;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|&column| column == name);
+    let required =
+        |name: &str| column(name).ok_or_else(|| invalid(format!("missing {} column", name)));
+    let measurement = required("measurement")?;
+    let output = required("output")?;
+    let (setpoint, feedforward, rate) = (column("setpoint"), column("feedforward"), column("rate"));
+
+    let mut vectors = Vec::new();
+    for (row, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        // Rows are numbered from one, after the header.
+        let cell = |index: Option<usize>| -> io::Result<Option<T>> {
+            match index.and_then(|index| cells.get(index)) {
+                None | Some(&"") => Ok(None),
+                Some(cell) => {
+                    cell.parse::<f64>().ok().and_then(T::from).map(Some).ok_or_else(|| {
+                        invalid(format!("invalid number {:?} on row {}", cell, row + 1))
+                    })
+                }
+            }
+        };
+        let value = |index: usize| -> io::Result<T> {
+            cell(Some(index))?.ok_or_else(|| invalid(format!("missing value on row {}", row + 1)))
+        };
+        vectors.push(Vector {
+            setpoint: cell(setpoint)?,
+            input: Input {
+                measurement: value(measurement)?,
+                rate: cell(rate)?,
+                feedforward: cell(feedforward)?.unwrap_or_else(T::zero),
+            },
+            output: value(output)?,
+        });
+    }
+    Ok(vectors)
+}
+
+/// Steps `controller` through the golden reference `vectors` and returns the first
+/// step whose output differs from the expected one by more than `tolerance`, relative
+/// to the expected output when it is larger than one in magnitude.
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::simulation::{read_vectors, verify};
+///
+/// let csv = "setpoint,measurement,output\n2,0.5,1.6875\n2,1,1.5\n";
+/// let vectors = read_vectors(csv.as_bytes()).unwrap();
+///
+/// let mut pid = PID::new(1.0, 0.5, 0.0, 0.0, 0.5, 0.0);
+/// assert_eq!(verify(&mut pid, &vectors, 1e-9), Ok(()));
+///
+/// let mut pid = PID::new(1.0, 0.6, 0.0, 0.0, 0.5, 0.0);
+/// assert_eq!(verify(&mut pid, &vectors, 1e-9).unwrap_err().index, 0);
+/// ```
+pub fn verify<T: FloatCore>(
+    controller: &mut PID<T>,
+    vectors: &[Vector<T>],
+    tolerance: T,
+) -> Result<(), Mismatch<T>> {
+    for (index, vector) in vectors.iter().enumerate() {
+        if let Some(setpoint) = vector.setpoint {
+            controller.setpoint = setpoint;
+        }
+        let (state, actual) = controller.transition(controller.state(), vector.input);
+        controller.set_state(state);
+
+        let expected = vector.output;
+        // Written so that a not a number output is a mismatch.
+        let close = (actual - expected).abs() <= tolerance * expected.abs().max(T::one());
+        if !close {
+            return Err(Mismatch { index, expected, actual });
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error for malformed vectors.
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
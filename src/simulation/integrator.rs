@@ -0,0 +1,56 @@
+use num_traits::float::FloatCore;
+
+use super::Plant;
+
+/// Implementation of a pure integrating process, `K / s`, such as the level of a tank
+/// or the position of a motor driven in velocity.
+#[derive(Clone, Copy, Debug)]
+pub struct Integrator<T> {
+    /// Rate of change of the output per unit of input, in units per second.
+    gain: T,
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: T,
+
+    /// Output of the process.
+    output: T,
+}
+
+impl<T: FloatCore> Integrator<T> {
+    /// Creates a new `Integrator` process at rest, whose output changes by `gain` units
+    /// per second for each unit of input.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sampling_time` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::simulation::{Integrator, Plant};
+    ///
+    /// let mut tank = Integrator::new(0.5, 2.0);
+    /// tank.step(1.0);
+    /// assert_eq!(tank.step(3.0), 4.0);
+    /// ```
+    pub fn new(gain: T, sampling_time: T) -> Self {
+        assert!(sampling_time > T::zero());
+        Self { gain, sampling_time, output: T::zero() }
+    }
+}
+
+impl<T: FloatCore> Plant<T> for Integrator<T> {
+    fn step(&mut self, input: T) -> T {
+        self.output = self.output + self.gain * input * self.sampling_time;
+        self.output
+    }
+
+    fn output(&self) -> T {
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = T::zero();
+    }
+}
@@ -0,0 +1,145 @@
+use std::vec::Vec;
+
+use num_traits::float::FloatCore;
+
+use super::{NoiseSource, Plant, Sample, Simulation};
+use crate::controller::Controller;
+use crate::diagnostics::step_response;
+
+/// Aggregated step response metrics of the runs of a [`MonteCarlo`] evaluation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Robustness<T> {
+    /// Number of runs.
+    pub runs: usize,
+    /// Number of runs that did not settle within the tolerance band.
+    pub unsettled: usize,
+    /// Largest overshoot, in percent of the step.
+    pub worst_overshoot: T,
+    /// Mean overshoot, in percent of the step.
+    pub mean_overshoot: T,
+    /// Longest settling time of the runs that settled, in seconds.
+    pub worst_settling_time: T,
+    /// Mean settling time of the runs that settled, in seconds.
+    pub mean_settling_time: T,
+}
+
+/// Implementation of a Monte Carlo robustness evaluation, which simulates a setpoint
+/// step on many randomly perturbed plants and aggregates the worst-case performance,
+/// to check a tuning is robust before flashing it.
+#[derive(Clone, Copy, Debug)]
+pub struct MonteCarlo<T> {
+    /// Number of simulated loops.
+    runs: usize,
+    /// Number of steps of each simulation.
+    steps: usize,
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: T,
+    /// Setpoint of the step, from a plant at rest.
+    setpoint: T,
+    /// Half width of the settling band, as a fraction of the step.
+    tolerance: T,
+    /// Seed of the perturbations.
+    seed: u32,
+}
+
+impl<T: FloatCore> MonteCarlo<T> {
+    /// Creates a new `MonteCarlo` evaluation of `runs` simulations of `steps` steps,
+    /// each a step to `setpoint` from a plant at rest.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps.
+    /// The settling band is 2% of the step by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::simulation::{FirstOrder, MonteCarlo};
+    ///
+    /// let evaluation = MonteCarlo::new(50, 500, 0.1, 1.0);
+    /// let robustness = evaluation.run(|noise| {
+    ///     // Gain known within ±30%, time constant within ±50%.
+    ///     let plant = FirstOrder::new(noise.between(0.7, 1.3), noise.between(1.0, 3.0), 0.1);
+    ///     (PID::new(1.0, 0.5, 0.0, 0.0, 0.1, 0.0), plant)
+    /// });
+    /// assert_eq!(robustness.unsettled, 0);
+    /// assert!(robustness.worst_overshoot < 25.0);
+    /// ```
+    pub fn new(runs: usize, steps: usize, sampling_time: T, setpoint: T) -> Self {
+        let tolerance = T::from(0.02).expect("Unable to cast 0.02");
+        Self { runs, steps, sampling_time, setpoint, tolerance, seed: 1 }
+    }
+
+    /// Changes the half width of the settling band, as a fraction of the step.
+    pub fn set_tolerance(&mut self, tolerance: T) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Changes the seed of the perturbations, the same seed always producing the same
+    /// evaluation.
+    pub fn set_seed(&mut self, seed: u32) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the evaluation, `build` creating the controller and the perturbed plant of
+    /// each run from a noise source to draw the perturbations from.
+    pub fn run<C, P, F>(&self, mut build: F) -> Robustness<T>
+    where
+        C: Controller<T>,
+        P: Plant<T>,
+        F: FnMut(&mut NoiseSource) -> (C, P),
+    {
+        let mut noise = NoiseSource::new(self.seed);
+        let zero = T::zero();
+        let rest = Sample { setpoint: zero, measurement: zero, output: zero, disturbance: zero };
+        let mut trajectory = Vec::new();
+        trajectory.resize(self.steps, rest);
+        let mut measurements = Vec::with_capacity(self.steps);
+
+        let mut robustness = Robustness {
+            runs: self.runs,
+            unsettled: 0,
+            worst_overshoot: T::zero(),
+            mean_overshoot: T::zero(),
+            worst_settling_time: T::zero(),
+            mean_settling_time: T::zero(),
+        };
+        let mut settled = 0_usize;
+
+        for _ in 0..self.runs {
+            let (controller, plant) = build(&mut noise);
+            let mut simulation = Simulation::new(controller, plant);
+            simulation.run(&mut trajectory, |_| self.setpoint, |_| T::zero());
+            measurements.clear();
+            measurements.extend(trajectory.iter().map(|sample| sample.measurement));
+
+            let metrics =
+                step_response(&measurements, self.sampling_time, self.setpoint, self.tolerance);
+            let (overshoot, settling_time) = match metrics {
+                Some(metrics) => (metrics.overshoot, metrics.settling_time),
+                None => (T::zero(), None),
+            };
+            robustness.worst_overshoot = robustness.worst_overshoot.max(overshoot);
+            robustness.mean_overshoot = robustness.mean_overshoot + overshoot;
+            match settling_time {
+                Some(time) => {
+                    settled += 1;
+                    robustness.worst_settling_time = robustness.worst_settling_time.max(time);
+                    robustness.mean_settling_time = robustness.mean_settling_time + time;
+                }
+                None => robustness.unsettled += 1,
+            }
+        }
+
+        if self.runs > 0 {
+            let runs = T::from(self.runs).expect("Unable to cast the number of runs");
+            robustness.mean_overshoot = robustness.mean_overshoot / runs;
+        }
+        if settled > 0 {
+            let settled = T::from(settled).expect("Unable to cast the number of runs");
+            robustness.mean_settling_time = robustness.mean_settling_time / settled;
+        }
+        robustness
+    }
+}
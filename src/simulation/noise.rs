@@ -0,0 +1,75 @@
+use num_traits::float::FloatCore;
+
+use crate::constant;
+
+/// Implementation of a seedable pseudo-random noise source, to add measurement noise
+/// and load disturbances to a simulation while keeping it reproducible.
+///
+/// It uses a xorshift generator, fast and small but not suited to cryptography.
+#[derive(Clone, Copy, Debug)]
+pub struct NoiseSource {
+    /// State of the generator, never zero.
+    state: u32,
+}
+
+impl NoiseSource {
+    /// Creates a new `NoiseSource` from a `seed`, the same seed always producing the
+    /// same sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::simulation::NoiseSource;
+    ///
+    /// let mut noise = NoiseSource::new(42);
+    /// let samples: Vec<f64> = (0..10_000).map(|_| noise.gaussian(0.5)).collect();
+    /// let mean = samples.iter().sum::<f64>() / 10_000.0;
+    /// let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 10_000.0;
+    /// assert!(mean.abs() < 0.02);
+    /// assert!((variance.sqrt() - 0.5).abs() < 0.02);
+    ///
+    /// let mut again = NoiseSource::new(42);
+    /// assert_eq!(again.gaussian(0.5), samples[0]);
+    /// ```
+    pub fn new(seed: u32) -> Self {
+        // A zero state would only ever produce zeros.
+        let state = if seed == 0 { 0x9e37_79b9 } else { seed };
+        Self { state }
+    }
+
+    /// Returns the next pseudo-random integer.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Returns a sample uniformly distributed in the `[-amplitude, amplitude]`
+    /// interval.
+    pub fn uniform<T: FloatCore>(&mut self, amplitude: T) -> T {
+        // Any floating point type can hold a `u32`, rounded if need be.
+        let scale = constant::half::<T>().powi(32);
+        let unit = T::from(self.next_u32()).unwrap_or_else(T::zero) * scale;
+        amplitude * (unit + unit - T::one())
+    }
+
+    /// Returns a sample uniformly distributed in the `[min, max]` interval, to pick a
+    /// perturbed parameter.
+    pub fn between<T: FloatCore>(&mut self, min: T, max: T) -> T {
+        let half = constant::half::<T>();
+        let center = half * (min + max);
+        center + self.uniform(half * (max - min))
+    }
+
+    /// Returns a sample approximately normally distributed, with a mean of zero and a
+    /// standard deviation of `std_dev`, bounded to six standard deviations.
+    pub fn gaussian<T: FloatCore>(&mut self, std_dev: T) -> T {
+        // The sum of 12 uniform samples in [0, 1] has a variance of one.
+        let half = constant::half::<T>();
+        let sum = (0..12).fold(T::zero(), |sum, _| sum + self.uniform(half));
+        sum * std_dev
+    }
+}
@@ -0,0 +1,90 @@
+use num_traits::float::FloatCore;
+
+use super::Plant;
+use crate::constant;
+
+/// Implementation of a second-order process, `K ω² / (s² + 2ζωs + ω²)`, such as a
+/// spring loaded mechanism, integrated with a fourth order Runge-Kutta method.
+///
+/// It oscillates when underdamped, with a damping ratio below one.
+#[derive(Clone, Copy, Debug)]
+pub struct SecondOrder<T> {
+    /// Static gain.
+    gain: T,
+    /// Natural frequency, in radians per second.
+    frequency: T,
+    /// Damping ratio.
+    damping: T,
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: T,
+
+    /// Output of the process.
+    output: T,
+    /// Rate of change of the output.
+    rate: T,
+}
+
+impl<T: FloatCore> SecondOrder<T> {
+    /// Creates a new `SecondOrder` process at rest, with a static gain of `gain`, a
+    /// natural frequency of `frequency` radians per second and a damping ratio of
+    /// `damping`.
+    ///
+    /// `sampling_time` is the time difference in seconds between two consecutive steps,
+    /// it should be well below the period of the natural frequency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frequency` or `sampling_time` are not positive, or if `damping` is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::simulation::{Plant, SecondOrder};
+    ///
+    /// let mut spring = SecondOrder::new(1.0, 10.0, 0.2, 0.001);
+    /// let peak = (0..2000).map(|_| spring.step(1.0)).fold(0.0, f64::max);
+    /// // Overshoot of about 53% for a damping ratio of 0.2.
+    /// assert!((peak - 1.53).abs() < 0.01);
+    /// ```
+    pub fn new(gain: T, frequency: T, damping: T, sampling_time: T) -> Self {
+        assert!(frequency > T::zero() && sampling_time > T::zero());
+        assert!(damping >= T::zero());
+        Self { gain, frequency, damping, sampling_time, output: T::zero(), rate: T::zero() }
+    }
+
+    /// Returns the acceleration of the output for a given output, rate and input.
+    fn acceleration(&self, output: T, rate: T, input: T) -> T {
+        let two = constant::two::<T>();
+        let w = self.frequency;
+        w * w * (self.gain * input - output) - two * self.damping * w * rate
+    }
+}
+
+impl<T: FloatCore> Plant<T> for SecondOrder<T> {
+    fn step(&mut self, input: T) -> T {
+        let two = constant::two::<T>();
+        let six = two + two + two;
+        let dt = self.sampling_time;
+        let half = dt / two;
+        let (y, v) = (self.output, self.rate);
+
+        let (k1y, k1v) = (v, self.acceleration(y, v, input));
+        let (k2y, k2v) = (v + half * k1v, self.acceleration(y + half * k1y, v + half * k1v, input));
+        let (k3y, k3v) = (v + half * k2v, self.acceleration(y + half * k2y, v + half * k2v, input));
+        let (k4y, k4v) = (v + dt * k3v, self.acceleration(y + dt * k3y, v + dt * k3v, input));
+
+        self.output = y + dt * (k1y + two * k2y + two * k3y + k4y) / six;
+        self.rate = v + dt * (k1v + two * k2v + two * k3v + k4v) / six;
+        self.output
+    }
+
+    fn output(&self) -> T {
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = T::zero();
+        self.rate = T::zero();
+    }
+}
@@ -0,0 +1,175 @@
+//! Checks that a configuration yields stable dynamics before it is deployed.
+//!
+//! Polynomials are given by their coefficients in descending powers of `z`, which is
+//! also the ascending powers of `z⁻¹` of a transfer function.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Largest degree of the polynomials that can be checked.
+pub const MAX_DEGREE: usize = 16;
+
+/// Whether dynamics settle, keep going or diverge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stability {
+    /// Every pole is strictly inside the unit circle.
+    Stable,
+    /// A pole is on the unit circle, so the dynamics neither settle nor diverge.
+    Marginal,
+    /// A pole is outside the unit circle.
+    Unstable,
+}
+
+/// Returns whether every root of `polynomial` is strictly inside the unit circle,
+/// with the Jury criterion in its Schur–Cohn form. Returns `None` if the leading
+/// coefficient is zero or the degree is larger than [`MAX_DEGREE`].
+///
+/// # Examples
+///
+/// ```
+/// use control::stability::is_stable;
+///
+/// // (z - 0.5)(z + 0.8) = z² + 0.3z - 0.4
+/// assert_eq!(is_stable(&[1.0, 0.3, -0.4]), Some(true));
+/// // (z - 0.5)(z + 1.2) = z² + 0.7z - 0.6
+/// assert_eq!(is_stable(&[1.0, 0.7, -0.6]), Some(false));
+/// ```
+pub fn is_stable<T: FloatCore>(polynomial: &[T]) -> Option<bool> {
+    if polynomial.is_empty() || polynomial.len() > MAX_DEGREE + 1 || polynomial[0] == T::zero() {
+        return None;
+    }
+    let mut coefficients = [T::zero(); MAX_DEGREE + 1];
+    coefficients[..polynomial.len()].copy_from_slice(polynomial);
+
+    // Each step divides out a reflection coefficient, all of which must be smaller than
+    // one in magnitude, lowering the degree by one.
+    for degree in (1..polynomial.len()).rev() {
+        let reflection = coefficients[degree] / coefficients[0];
+        if reflection.abs() >= T::one() || reflection.is_nan() {
+            return Some(false);
+        }
+        let previous = coefficients;
+        for index in 0..degree {
+            coefficients[index] = previous[index] - reflection * previous[degree - index];
+        }
+    }
+    Some(true)
+}
+
+/// Returns the stability of the derivative low-pass filter of a controller.
+///
+/// A time constant of zero puts its pole at -1, so the derivative term rings,
+/// alternating sign every step.
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::stability::{derivative_filter, Stability};
+///
+/// let pid = PID::new(1.0, 0.0, 0.1, 0.01, 0.001, 0.0);
+/// assert_eq!(derivative_filter(&pid), Stability::Stable);
+///
+/// let pid = PID::new(1.0, 0.0, 0.1, 0.0, 0.001, 0.0);
+/// assert_eq!(derivative_filter(&pid), Stability::Marginal);
+/// ```
+pub fn derivative_filter<T: FloatCore>(controller: &PID<T>) -> Stability {
+    let (_, _, _, pole) = controller.coefficients();
+    if pole.abs() < T::one() {
+        Stability::Stable
+    } else if pole.abs() == T::one() {
+        Stability::Marginal
+    } else {
+        Stability::Unstable
+    }
+}
+
+/// Returns whether the loop of `controller` driving a plant with the discrete transfer
+/// function `numerator / denominator`, both in ascending powers of `z⁻¹`, is stable,
+/// ignoring the output bounds. Returns `None` if the denominator starts with zero or
+/// the closed loop is of a degree larger than [`MAX_DEGREE`].
+///
+/// # Examples
+///
+/// ```
+/// use control::pid::PID;
+/// use control::stability::closed_loop;
+///
+/// // A first-order process of unit gain and a time constant of 2 seconds, sampled
+/// // every 0.1 seconds, as simulated by `FirstOrder`.
+/// let a = (-0.05_f64).exp();
+/// let (numerator, denominator) = ([0.0, 1.0 - a], [1.0, -a]);
+///
+/// let pid = PID::new(2.0, 1.0, 0.0, 0.0, 0.1, 0.0);
+/// assert_eq!(closed_loop(&pid, &numerator, &denominator), Some(true));
+///
+/// let pid = PID::new(100.0, 0.0, 0.0, 0.0, 0.1, 0.0);
+/// assert_eq!(closed_loop(&pid, &numerator, &denominator), Some(false));
+/// ```
+pub fn closed_loop<T: FloatCore>(
+    controller: &PID<T>,
+    numerator: &[T],
+    denominator: &[T],
+) -> Option<bool> {
+    let (p, i, d, t) = controller.coefficients();
+    let one = T::one();
+    let zero = T::zero();
+    if denominator.is_empty() || denominator[0] == zero {
+        return None;
+    }
+    // Terms that are off do not contribute their poles, which would otherwise cancel
+    // out of the loop.
+    let integrator = if i != zero { [one, -one] } else { [one, zero] };
+    let filter = if d != zero { [one, -t] } else { [one, zero] };
+
+    // The controller is Nc / Dc, its derivative acting on the measurement.
+    let mut dc = [zero; 3];
+    multiply(&integrator, &filter, &mut dc);
+    let mut nc = [zero; 3];
+    for (term, &value) in nc.iter_mut().zip(dc.iter()) {
+        *term = p * value;
+    }
+    let mut term = [zero; 3];
+    multiply(&[i, i], &filter, &mut term);
+    add(&mut nc, &term);
+    multiply(&[-d, d], &integrator, &mut term);
+    add(&mut nc, &term);
+
+    // Characteristic polynomial A Dc + B Nc.
+    let len = numerator.len().max(denominator.len()) + 2;
+    if len > MAX_DEGREE + 1 {
+        return None;
+    }
+    let mut characteristic = [zero; MAX_DEGREE + 1];
+    let mut product = [zero; MAX_DEGREE + 1];
+    multiply(denominator, &dc, &mut product);
+    add(&mut characteristic, &product);
+    product = [zero; MAX_DEGREE + 1];
+    multiply(numerator, &nc, &mut product);
+    add(&mut characteristic, &product);
+
+    // Roots at the origin, from trailing zeros, are stable and can be dropped.
+    let mut len = len;
+    while len > 1 && characteristic[len - 1] == zero {
+        len -= 1;
+    }
+    is_stable(&characteristic[..len])
+}
+
+/// Multiplies the polynomials `a` and `b` into `product`, which must be long enough.
+fn multiply<T: FloatCore>(a: &[T], b: &[T], product: &mut [T]) {
+    product.iter_mut().for_each(|coefficient| *coefficient = T::zero());
+    for (index, &x) in a.iter().enumerate() {
+        for (offset, &y) in b.iter().enumerate() {
+            product[index + offset] = product[index + offset] + x * y;
+        }
+    }
+}
+
+/// Adds the polynomial `b` to `a`, which must be at least as long.
+fn add<T: FloatCore>(a: &mut [T], b: &[T]) {
+    for (x, &y) in a.iter_mut().zip(b) {
+        *x = *x + y;
+    }
+}
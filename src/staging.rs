@@ -0,0 +1,156 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Implementation of hybrid staged and modulating control, as found in HVAC equipment
+/// with several compressor or burner stages and one modulating element.
+///
+/// The stages switch on and off for coarse control while the controller output drives
+/// the modulating element for fine control around the setpoint. A stage switches on
+/// when the modulating output reaches the upper threshold and off when it falls to the
+/// lower one, the gap between them being the staging hysteresis, and at least the
+/// interstage delay must pass between two stage changes.
+///
+/// On every stage change the capacity of one stage is transferred to or from the
+/// controller integral, so the total capacity does not jump. The gap between the
+/// thresholds should be wider than the capacity of one stage, otherwise a load right
+/// at a stage boundary cycles that stage at the interstage delay.
+#[derive(Clone, Copy, Debug)]
+pub struct Staging<T> {
+    /// Controller driving the modulating element.
+    pub controller: PID<T>,
+
+    /// Number of stages.
+    stages: u8,
+    /// Number of stages switched on.
+    active: u8,
+    /// Number of stages switched on, as a number.
+    count: T,
+    /// Capacity of one stage, in units of the controller output.
+    capacity: T,
+    /// Modulating output switching one more stage on.
+    upper: T,
+    /// Modulating output switching one stage off.
+    lower: T,
+    /// Minimum number of steps between two stage changes.
+    delay: u32,
+    /// Number of steps since the last stage change, saturating.
+    elapsed: u32,
+}
+
+impl<T: FloatCore> Staging<T> {
+    /// Creates a new `Staging` of `stages` stages of `capacity` each, in units of the
+    /// output of `controller`, with all stages off.
+    ///
+    /// The thresholds start at the output bounds of `controller`, which should be
+    /// finite, and there is no interstage delay.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::staging::Staging;
+    ///
+    /// // A modulating burner from 0 to 100%, two stages of 80% each.
+    /// let mut pid = PID::new(10.0, 1.0, 0.0, 0.0, 1.0, 21.0);
+    /// pid.bound_output(0.0..=100.0);
+    /// let mut staging = Staging::new(pid, 2, 80.0);
+    /// staging.set_thresholds(10.0, 90.0).set_interstage_delay(60);
+    ///
+    /// // The modulating output saturates, a stage takes over its capacity.
+    /// assert_eq!(staging.step(10.0), (1, 20.0));
+    ///
+    /// // The modulating output saturates again, but the next stage has to wait for the
+    /// // interstage delay.
+    /// for _ in 1..60 {
+    ///     assert_eq!(staging.step(10.0).0, 1);
+    /// }
+    /// assert!(staging.controller.output() > 90.0);
+    /// assert_eq!(staging.step(10.0).0, 2);
+    /// ```
+    pub fn new(controller: PID<T>, stages: u8, capacity: T) -> Self {
+        assert!(capacity > T::zero());
+        let (lower, upper) = controller.output_bounds();
+        Self {
+            controller,
+            stages,
+            active: 0,
+            count: T::zero(),
+            capacity,
+            upper,
+            lower,
+            delay: 0,
+            elapsed: u32::MAX,
+        }
+    }
+
+    /// Changes the modulating outputs switching one stage off at `lower` and one more
+    /// stage on at `upper`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lower` is not below `upper`.
+    pub fn set_thresholds(&mut self, lower: T, upper: T) -> &mut Self {
+        assert!(lower < upper);
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    /// Changes the minimum number of steps between two stage changes.
+    pub fn set_interstage_delay(&mut self, steps: u32) -> &mut Self {
+        self.delay = steps;
+        self
+    }
+
+    /// Returns the number of stages switched on.
+    pub fn active(&self) -> u8 {
+        self.active
+    }
+
+    /// Returns the total capacity in use, that of the active stages plus the
+    /// modulating output.
+    pub fn total(&self) -> T {
+        self.count * self.capacity + self.controller.output()
+    }
+
+    /// Switches all stages off and clears the controller state.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+        self.active = 0;
+        self.count = T::zero();
+        self.elapsed = u32::MAX;
+    }
+
+    /// Performs a single step of the controller and the staging, and returns the
+    /// number of stages switched on and the modulating output.
+    pub fn step(&mut self, measurement: T) -> (u8, T) {
+        let output = self.controller.step(measurement);
+        self.elapsed = self.elapsed.saturating_add(1);
+
+        if self.elapsed >= self.delay {
+            if output >= self.upper && self.active < self.stages {
+                self.active += 1;
+                self.count = self.count + T::one();
+                self.transfer(output - self.capacity);
+            } else if output <= self.lower && self.active > 0 {
+                self.active -= 1;
+                self.count = self.count - T::one();
+                self.transfer(output + self.capacity);
+            }
+        }
+
+        (self.active, self.controller.output())
+    }
+
+    /// Moves the modulating output to `output` after a stage change.
+    fn transfer(&mut self, output: T) {
+        let (low, high) = self.controller.output_bounds();
+        self.controller.track(num_traits::clamp(output, low, high));
+        self.elapsed = 0;
+    }
+}
@@ -0,0 +1,118 @@
+//! Lock-free hand over of a new configuration to a control loop that cannot take
+//! critical sections.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Bit of the shared state set while the middle slot holds an unconsumed
+/// configuration.
+const FRESH: u8 = 0b100;
+/// Bits of the shared state holding the index of the middle slot.
+const INDEX: u8 = 0b011;
+
+/// A configuration, such as a set of gains, published by one context and consumed by
+/// the control loop at a step boundary, without locking.
+///
+/// It is a triple buffer: the publisher writes a slot of its own, then atomically
+/// swaps it with the middle slot, which the consumer swaps with its own slot when it
+/// finds it fresh. Neither side ever waits for the other, and the consumer always
+/// gets the last complete configuration. It requires atomic swaps, which some targets
+/// such as `thumbv6m` lack.
+pub struct Swap<C> {
+    /// Configuration slots, owned in turn by the publisher, the middle and the consumer.
+    slots: [UnsafeCell<C>; 3],
+    /// Index of the middle slot and whether it is fresh.
+    state: AtomicU8,
+    /// Index of the slot owned by the publisher.
+    back: AtomicU8,
+    /// Index of the slot owned by the consumer.
+    front: AtomicU8,
+}
+
+// The publisher and consumer handles only ever touch the slot they own, slots change
+// owner through the atomic state.
+unsafe impl<C: Send> Sync for Swap<C> {}
+
+/// The handle of a [`Swap`] publishing new configurations.
+pub struct Publisher<'a, C> {
+    /// Shared buffer.
+    swap: &'a Swap<C>,
+}
+
+/// The handle of a [`Swap`] consuming new configurations, meant for the control loop.
+pub struct Consumer<'a, C> {
+    /// Shared buffer.
+    swap: &'a Swap<C>,
+}
+
+impl<C: Copy> Swap<C> {
+    /// Creates a new `Swap` holding the `initial` configuration, not fresh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::swap::Swap;
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.01, 10.0);
+    /// let mut gains = Swap::new((1.0, 0.0, 0.0));
+    /// let (mut publisher, mut consumer) = gains.split();
+    ///
+    /// // From the tuning context.
+    /// publisher.publish((2.0, 0.0, 0.0));
+    ///
+    /// // In the control interrupt, at the start of a step.
+    /// if let Some((kp, ki, kd)) = consumer.consume() {
+    ///     pid.set_gains(kp, ki, kd);
+    /// }
+    /// assert_eq!(pid.step(8.0), 4.0);
+    /// assert_eq!(consumer.consume(), None);
+    /// ```
+    pub const fn new(initial: C) -> Self {
+        Self {
+            slots: [UnsafeCell::new(initial), UnsafeCell::new(initial), UnsafeCell::new(initial)],
+            state: AtomicU8::new(1),
+            back: AtomicU8::new(0),
+            front: AtomicU8::new(2),
+        }
+    }
+
+    /// Splits the buffer into its publishing and consuming handles.
+    pub fn split(&mut self) -> (Publisher<'_, C>, Consumer<'_, C>) {
+        let swap = &*self;
+        (Publisher { swap }, Consumer { swap })
+    }
+}
+
+impl<C: Copy> Publisher<'_, C> {
+    /// Publishes a new configuration, replacing any not yet consumed.
+    pub fn publish(&mut self, config: C) {
+        let back = self.swap.back.load(Ordering::Relaxed);
+        // SAFETY: the back slot is only ever accessed by the unique publisher.
+        unsafe { *self.swap.slots[back as usize].get() = config };
+        let middle = self.swap.state.swap(back | FRESH, Ordering::AcqRel);
+        self.swap.back.store(middle & INDEX, Ordering::Relaxed);
+    }
+}
+
+impl<C: Copy> Consumer<'_, C> {
+    /// Returns the last published configuration if it was not consumed yet.
+    pub fn consume(&mut self) -> Option<C> {
+        if self.swap.state.load(Ordering::Relaxed) & FRESH == 0 {
+            return None;
+        }
+        let front = self.swap.front.load(Ordering::Relaxed);
+        let middle = self.swap.state.swap(front, Ordering::AcqRel);
+        let front = middle & INDEX;
+        self.swap.front.store(front, Ordering::Relaxed);
+        // SAFETY: the front slot is only ever accessed by the unique consumer.
+        Some(unsafe { *self.swap.slots[front as usize].get() })
+    }
+
+    /// Returns the last consumed configuration.
+    pub fn current(&self) -> C {
+        let front = self.swap.front.load(Ordering::Relaxed);
+        // SAFETY: the front slot is only ever accessed by the unique consumer.
+        unsafe { *self.swap.slots[front as usize].get() }
+    }
+}
@@ -0,0 +1,265 @@
+//! Compact binary frames carrying the record of a controller step, giving host-side
+//! tools a stable wire format whatever the transport.
+//!
+//! # Format
+//!
+//! Each frame is [`FRAME_LEN`] bytes long. The payload below is followed by its
+//! CRC-16/CCITT-FALSE, little endian, then COBS encoded and terminated by a zero byte,
+//! so a receiver can resynchronize on any boundary.
+//!
+//! | Offset | Type     | Field                                            |
+//! |--------|----------|--------------------------------------------------|
+//! | 0      | `u8`     | Format version, [`VERSION`]                      |
+//! | 1      | `u8`     | Flags, see below                                 |
+//! | 2      | `u16`    | Sequence number, wrapping                        |
+//! | 4      | `f32`    | Setpoint                                         |
+//! | 8      | `f32`    | Measurement                                      |
+//! | 12     | `f32`    | Proportional term                                |
+//! | 16     | `f32`    | Integral term                                    |
+//! | 20     | `f32`    | Derivative term                                  |
+//! | 24     | `f32`    | Feedforward term                                 |
+//! | 28     | `f32`    | Output                                           |
+//!
+//! All the fields are little endian. Bits 0 and 1 of the flags hold the output
+//! saturation and bits 2 and 3 the rate limit, as 0 for none, 1 for the lower bound and
+//! 2 for the upper bound. Bit 4 is set in manual mode.
+//!
+//! # Stream
+//!
+//! On a lossless link, such as an RTT channel read by a debug probe, the
+//! [`StreamEncoder`] produces fixed size records of [`RECORD_LEN`] bytes instead,
+//! cheap enough to capture every step at full rate. Each record starts with
+//! [`MAGIC`], so a reader joining the stream late can align on it.
+//!
+//! | Offset | Type     | Field                                            |
+//! |--------|----------|--------------------------------------------------|
+//! | 0      | `u16`    | Magic number, [`MAGIC`]                          |
+//! | 2      | `u8`     | Format version, [`VERSION`]                      |
+//! | 3      | `u8`     | Flags, as in frames                              |
+//! | 4      | `u32`    | Sequence number, wrapping                        |
+//! | 8      | `f32`    | Setpoint                                         |
+//! | 12     | `f32`    | Measurement                                      |
+//! | 16     | `f32`    | Proportional term                                |
+//! | 20     | `f32`    | Integral term                                    |
+//! | 24     | `f32`    | Derivative term                                  |
+//! | 28     | `f32`    | Feedforward term                                 |
+//! | 32     | `f32`    | Output                                           |
+//!
+//! All the fields are little endian and aligned to their size.
+
+use num_traits::float::FloatCore;
+
+use crate::pid::{Saturation, StepRecord};
+
+/// Version of the frame format.
+pub const VERSION: u8 = 1;
+
+/// Length of the payload, without its CRC.
+const PAYLOAD_LEN: usize = 32;
+
+/// Length of an encoded frame, zero terminator included.
+pub const FRAME_LEN: usize = PAYLOAD_LEN + 4;
+
+/// Magic number starting every stream record.
+pub const MAGIC: u16 = 0x5aa5;
+
+/// Length of a stream record.
+pub const RECORD_LEN: usize = 36;
+
+/// Implementation of a telemetry frame encoder, numbering the frames so the receiver
+/// can detect lost ones.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Encoder {
+    /// Sequence number of the next frame.
+    sequence: u16,
+}
+
+impl Encoder {
+    /// Creates a new `Encoder`, the first frame having a sequence number of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::telemetry::{Encoder, FRAME_LEN};
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// let mut encoder = Encoder::new();
+    /// pid.step(4.0);
+    ///
+    /// let frame = encoder.encode(&pid.record());
+    /// assert_eq!(frame.len(), FRAME_LEN);
+    /// assert_eq!(frame.iter().position(|&byte| byte == 0), Some(FRAME_LEN - 1));
+    /// assert_eq!(encoder.sequence(), 1);
+    /// ```
+    pub fn new() -> Self {
+        Self { sequence: 0 }
+    }
+
+    /// Returns the sequence number of the next frame.
+    pub fn sequence(&self) -> u16 {
+        self.sequence
+    }
+
+    /// Encodes the record of a step into a frame.
+    pub fn encode<T: FloatCore>(&mut self, record: &StepRecord<T>) -> [u8; FRAME_LEN] {
+        let mut payload = [0; PAYLOAD_LEN + 2];
+        payload[0] = VERSION;
+        payload[1] =
+            flag(record.saturation) | (flag(record.rate_limit) << 2) | ((record.manual as u8) << 4);
+        payload[2..4].copy_from_slice(&self.sequence.to_le_bytes());
+
+        let values = [
+            record.setpoint,
+            record.measurement,
+            record.proportional,
+            record.integral,
+            record.derivative,
+            record.feedforward,
+            record.output,
+        ];
+        for (chunk, value) in payload[4..PAYLOAD_LEN].chunks_exact_mut(4).zip(values.iter()) {
+            let value = value.to_f32().unwrap_or(f32::NAN);
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+
+        let crc = crc16(&payload[..PAYLOAD_LEN]);
+        payload[PAYLOAD_LEN..].copy_from_slice(&crc.to_le_bytes());
+        self.sequence = self.sequence.wrapping_add(1);
+
+        let mut frame = [0; FRAME_LEN];
+        cobs(&payload, &mut frame);
+        frame
+    }
+}
+
+/// Implementation of a telemetry stream encoder, producing fixed size records for
+/// lossless links such as RTT channels.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamEncoder {
+    /// Sequence number of the next record.
+    sequence: u32,
+}
+
+impl StreamEncoder {
+    /// Creates a new `StreamEncoder`, the first record having a sequence number of
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::telemetry::{StreamEncoder, MAGIC, RECORD_LEN};
+    ///
+    /// let mut pid = PID::new(1.0, 0.0, 0.0, 0.0, 0.1, 10.0);
+    /// let mut encoder = StreamEncoder::new();
+    /// pid.step(4.0);
+    ///
+    /// // Written to an RTT up channel, for instance with `rtt_target::UpChannel::write`.
+    /// let record = encoder.encode(&pid.record());
+    /// assert_eq!(record.len(), RECORD_LEN);
+    /// assert_eq!(record[..2], MAGIC.to_le_bytes());
+    /// assert_eq!(record[32..], 6.0_f32.to_le_bytes());
+    /// ```
+    pub fn new() -> Self {
+        Self { sequence: 0 }
+    }
+
+    /// Returns the sequence number of the next record.
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /// Encodes the record of a step into a stream record.
+    pub fn encode<T: FloatCore>(&mut self, record: &StepRecord<T>) -> [u8; RECORD_LEN] {
+        let mut bytes = [0; RECORD_LEN];
+        bytes[..2].copy_from_slice(&MAGIC.to_le_bytes());
+        bytes[2] = VERSION;
+        bytes[3] =
+            flag(record.saturation) | (flag(record.rate_limit) << 2) | ((record.manual as u8) << 4);
+        bytes[4..8].copy_from_slice(&self.sequence.to_le_bytes());
+
+        let values = [
+            record.setpoint,
+            record.measurement,
+            record.proportional,
+            record.integral,
+            record.derivative,
+            record.feedforward,
+            record.output,
+        ];
+        for (chunk, value) in bytes[8..].chunks_exact_mut(4).zip(values.iter()) {
+            let value = value.to_f32().unwrap_or(f32::NAN);
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+
+        self.sequence = self.sequence.wrapping_add(1);
+        bytes
+    }
+}
+
+/// Returns the two bit flag of a saturation direction.
+pub(crate) fn flag(saturation: Saturation) -> u8 {
+    match saturation {
+        Saturation::Unsaturated => 0,
+        Saturation::Lower => 1,
+        Saturation::Upper => 2,
+    }
+}
+
+/// Computes the CRC-16/CCITT-FALSE of `data`.
+pub(crate) fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0xffff, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// COBS encodes `data`, shorter than 254 bytes, into `frame`, followed by a zero.
+pub(crate) fn cobs(data: &[u8], frame: &mut [u8]) {
+    let mut code = 0;
+    let mut index = 1;
+    for &byte in data {
+        if byte == 0 {
+            frame[code] = (index - code) as u8;
+            code = index;
+        } else {
+            frame[index] = byte;
+        }
+        index += 1;
+    }
+    frame[code] = (index - code) as u8;
+    frame[index] = 0;
+}
+
+/// COBS decodes `frame`, without its terminating zero, into `data` and returns the
+/// decoded length, or `None` if the frame is malformed or does not fit.
+pub(crate) fn uncobs(frame: &[u8], data: &mut [u8]) -> Option<usize> {
+    let mut read = 0;
+    let mut len = 0;
+    while read < frame.len() {
+        let code = usize::from(frame[read]);
+        if code == 0 || read + code > frame.len() {
+            return None;
+        }
+        read += 1;
+        for &byte in &frame[read..read + code - 1] {
+            if byte == 0 {
+                return None;
+            }
+            *data.get_mut(len)? = byte;
+            len += 1;
+        }
+        read += code - 1;
+        if code < 0xff && read < frame.len() {
+            *data.get_mut(len)? = 0;
+            len += 1;
+        }
+    }
+    Some(len)
+}
@@ -0,0 +1,195 @@
+//! Typed quantities from the `uom` crate for the setpoint, the measurement and the
+//! output of a controller, so unit mistakes are caught at compile time.
+
+use core::marker::PhantomData;
+use core::ops::{Div, Mul};
+
+use num_traits::float::FloatCore;
+use uom::si::{Dimension, Quantity, Units};
+use uom::Conversion;
+
+use crate::pid::{Saturation, PID};
+
+/// A `uom` quantity, converted to and from its value in base units.
+pub trait Base: Copy {
+    /// Underlying storage type.
+    type Value;
+
+    /// Creates a quantity from its value in base units.
+    fn from_base(value: Self::Value) -> Self;
+
+    /// Returns the value of the quantity in base units.
+    fn base(self) -> Self::Value;
+}
+
+impl<D, U, V> Base for Quantity<D, U, V>
+where
+    D: Dimension + ?Sized,
+    U: Units<V> + ?Sized,
+    V: uom::num::Num + Conversion<V> + Copy,
+{
+    type Value = V;
+
+    fn from_base(value: V) -> Self {
+        Quantity { dimension: PhantomData, units: PhantomData, value }
+    }
+
+    fn base(self) -> V {
+        self.value
+    }
+}
+
+/// A storage type of `uom` quantities supported by [`UnitPid`].
+pub trait Storage: FloatCore {
+    /// Time quantity of this storage type.
+    type Time: Base<Value = Self>;
+}
+
+impl Storage for f32 {
+    type Time = uom::si::f32::Time;
+}
+
+impl Storage for f64 {
+    type Time = uom::si::f64::Time;
+}
+
+/// Time quantity of the storage type `V`.
+type Seconds<V> = <V as Storage>::Time;
+
+/// Implementation of a [`PID`] controller with a measurement of quantity `M` and an
+/// output of quantity `O`.
+///
+/// Each gain must carry the ratio of units that turns the error, its integral or its
+/// derivative into the output, so for a heater driven from a temperature the
+/// proportional gain is a power per temperature. The wrapped controller works in base
+/// SI units.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitPid<M: Base, O> {
+    /// Controller in base units.
+    pub controller: PID<M::Value>,
+
+    /// Quantities of the measurement and of the output.
+    quantities: PhantomData<(M, O)>,
+}
+
+impl<V, M, O> UnitPid<M, O>
+where
+    V: Storage,
+    M: Base<Value = V>,
+    O: Base<Value = V>,
+{
+    /// Creates a new `UnitPid` controller, see [`PID::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tau` is negative or `sampling_time` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::units::UnitPid;
+    /// use uom::si::f64::{Energy, Power, TemperatureInterval, ThermodynamicTemperature, Time};
+    /// use uom::si::{energy::joule, power::watt, temperature_interval::kelvin};
+    /// use uom::si::{thermodynamic_temperature::degree_celsius, time::second};
+    ///
+    /// let degree = TemperatureInterval::new::<kelvin>(1.0);
+    /// let kp = Power::new::<watt>(50.0) / degree;
+    /// let ki = Power::new::<watt>(1.0) / degree / Time::new::<second>(1.0);
+    /// let kd = Energy::new::<joule>(0.0) / degree;
+    ///
+    /// let mut heater = UnitPid::new(
+    ///     kp,
+    ///     ki,
+    ///     kd,
+    ///     Time::new::<second>(0.0),
+    ///     Time::new::<second>(1.0),
+    ///     ThermodynamicTemperature::new::<degree_celsius>(60.0),
+    /// );
+    /// let power: Power = heater.step(ThermodynamicTemperature::new::<degree_celsius>(58.0));
+    /// assert!((power.get::<watt>() - 101.0).abs() < 1e-9);
+    /// ```
+    pub fn new<KP, KI, KD, KM, DM>(
+        kp: KP,
+        ki: KI,
+        kd: KD,
+        tau: Seconds<V>,
+        sampling_time: Seconds<V>,
+        setpoint: M,
+    ) -> Self
+    where
+        KP: Mul<M, Output = O>,
+        KI: Mul<M, Output = KM>,
+        KM: Mul<Seconds<V>, Output = O>,
+        KD: Mul<M, Output = DM>,
+        DM: Div<Seconds<V>, Output = O>,
+    {
+        let (kp, ki, kd) = Self::gains(kp, ki, kd);
+        let controller = PID::new(kp, ki, kd, tau.base(), sampling_time.base(), setpoint.base());
+        Self { controller, quantities: PhantomData }
+    }
+
+    /// Changes the gains, see [`PID::set_gains`].
+    pub fn set_gains<KP, KI, KD, KM, DM>(&mut self, kp: KP, ki: KI, kd: KD) -> &mut Self
+    where
+        KP: Mul<M, Output = O>,
+        KI: Mul<M, Output = KM>,
+        KM: Mul<Seconds<V>, Output = O>,
+        KD: Mul<M, Output = DM>,
+        DM: Div<Seconds<V>, Output = O>,
+    {
+        let (kp, ki, kd) = Self::gains(kp, ki, kd);
+        self.controller.set_gains(kp, ki, kd);
+        self
+    }
+
+    /// Returns the setpoint.
+    pub fn setpoint(&self) -> M {
+        M::from_base(self.controller.setpoint)
+    }
+
+    /// Changes the setpoint.
+    pub fn set_setpoint(&mut self, setpoint: M) -> &mut Self {
+        self.controller.setpoint = setpoint.base();
+        self
+    }
+
+    /// Restricts the output to the `[min, max]` interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` > `max`.
+    pub fn bound_output(&mut self, min: O, max: O) -> &mut Self {
+        self.controller.bound_output(min.base()..=max.base());
+        self
+    }
+
+    /// Returns the output of the last step.
+    pub fn output(&self) -> O {
+        O::from_base(self.controller.output())
+    }
+
+    /// Returns whether the output of the last step had to be clamped.
+    pub fn saturation(&self) -> Saturation {
+        self.controller.saturation()
+    }
+
+    /// Performs a single step of the control loop and returns the output.
+    pub fn step(&mut self, measurement: M) -> O {
+        O::from_base(self.controller.step(measurement.base()))
+    }
+
+    /// Returns the values in base units of the gains.
+    fn gains<KP, KI, KD, KM, DM>(kp: KP, ki: KI, kd: KD) -> (V, V, V)
+    where
+        KP: Mul<M, Output = O>,
+        KI: Mul<M, Output = KM>,
+        KM: Mul<Seconds<V>, Output = O>,
+        KD: Mul<M, Output = DM>,
+        DM: Div<Seconds<V>, Output = O>,
+    {
+        // Multiplying by quantities of one base unit leaves the value of a gain untouched.
+        let unit = M::from_base(V::one());
+        let second = Seconds::<V>::from_base(V::one());
+        ((kp * unit).base(), (ki * unit * second).base(), (kd * unit / second).base())
+    }
+}
@@ -0,0 +1,141 @@
+//! A wasm-bindgen friendly wrapper of the controller and the closed-loop simulation,
+//! so interactive tuning and teaching tools can run in the browser.
+//!
+//! All values are `f64`, trajectories are returned to JavaScript as `Float64Array`.
+
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::pid::PID;
+use crate::simulation::{FirstOrder, Plant, SecondOrder, Simulation};
+
+/// JavaScript wrapper of a [`PID`] controller.
+#[wasm_bindgen(js_name = PID)]
+#[derive(Clone, Copy, Debug)]
+pub struct WasmPid {
+    /// Wrapped controller.
+    controller: PID<f64>,
+    /// Time difference in seconds between two consecutive steps.
+    sampling_time: f64,
+}
+
+/// Outputs of a simulation run by a [`WasmPid`].
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Trajectory {
+    /// Measurement of each step.
+    measurement: Vec<f64>,
+    /// Controller output of each step.
+    output: Vec<f64>,
+}
+
+#[wasm_bindgen(js_class = PID)]
+impl WasmPid {
+    /// Creates a new controller, see [`PID::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::wasm::WasmPid;
+    ///
+    /// let pid = WasmPid::new(2.0, 1.0, 0.0, 0.0, 0.1, 0.0);
+    /// let trajectory = pid.simulate_first_order(1.0, 2.0, &[1.0; 600]);
+    /// let measurement = trajectory.measurement();
+    /// assert!((measurement[599] - 1.0).abs() < 1e-3);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(kp: f64, ki: f64, kd: f64, tau: f64, sampling_time: f64, setpoint: f64) -> Self {
+        let controller = PID::new(kp, ki, kd, tau, sampling_time, setpoint);
+        Self { controller, sampling_time }
+    }
+
+    /// Returns the setpoint.
+    #[wasm_bindgen(getter)]
+    pub fn setpoint(&self) -> f64 {
+        self.controller.setpoint
+    }
+
+    /// Changes the setpoint.
+    #[wasm_bindgen(setter)]
+    pub fn set_setpoint(&mut self, setpoint: f64) {
+        self.controller.setpoint = setpoint;
+    }
+
+    /// Returns the output of the last step.
+    #[wasm_bindgen(getter)]
+    pub fn output(&self) -> f64 {
+        self.controller.output()
+    }
+
+    /// Changes the gains, see [`PID::set_gains`].
+    #[wasm_bindgen(js_name = setGains)]
+    pub fn set_gains(&mut self, kp: f64, ki: f64, kd: f64) {
+        self.controller.set_gains(kp, ki, kd);
+    }
+
+    /// Restricts the output to the `[min, max]` interval, see [`PID::bound_output`].
+    #[wasm_bindgen(js_name = boundOutput)]
+    pub fn bound_output(&mut self, min: f64, max: f64) {
+        self.controller.bound_output(min..=max);
+    }
+
+    /// Clears the controller state, see [`PID::reset`].
+    pub fn reset(&mut self) {
+        self.controller.reset();
+    }
+
+    /// Performs a single step of the control loop and returns the output.
+    pub fn step(&mut self, measurement: f64) -> f64 {
+        self.controller.step(measurement)
+    }
+
+    /// Simulates a copy of the controller driving a [`FirstOrder`] process, for one
+    /// step per setpoint.
+    #[wasm_bindgen(js_name = simulateFirstOrder)]
+    pub fn simulate_first_order(
+        &self,
+        gain: f64,
+        time_constant: f64,
+        setpoints: &[f64],
+    ) -> Trajectory {
+        self.simulate(FirstOrder::new(gain, time_constant, self.sampling_time), setpoints)
+    }
+
+    /// Simulates a copy of the controller driving a [`SecondOrder`] process, for one
+    /// step per setpoint.
+    #[wasm_bindgen(js_name = simulateSecondOrder)]
+    pub fn simulate_second_order(
+        &self,
+        gain: f64,
+        frequency: f64,
+        damping: f64,
+        setpoints: &[f64],
+    ) -> Trajectory {
+        let plant = SecondOrder::new(gain, frequency, damping, self.sampling_time);
+        self.simulate(plant, setpoints)
+    }
+}
+
+impl WasmPid {
+    /// Simulates a copy of the controller driving `plant`, for one step per setpoint.
+    fn simulate<P: Plant<f64>>(&self, plant: P, setpoints: &[f64]) -> Trajectory {
+        let mut simulation = Simulation::new(self.controller, plant);
+        let steps = simulation.iter(|k| setpoints[k], |_| 0.0).take(setpoints.len());
+        let (measurement, output) = steps.map(|sample| (sample.measurement, sample.output)).unzip();
+        Trajectory { measurement, output }
+    }
+}
+
+#[wasm_bindgen]
+impl Trajectory {
+    /// Returns the measurement of each step.
+    pub fn measurement(&self) -> Vec<f64> {
+        self.measurement.clone()
+    }
+
+    /// Returns the controller output of each step.
+    pub fn output(&self) -> Vec<f64> {
+        self.output.clone()
+    }
+}
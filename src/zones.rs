@@ -0,0 +1,98 @@
+use num_traits::float::FloatCore;
+
+use crate::pid::PID;
+
+/// Implementation of a multi-zone temperature controller, such as those of heated beds
+/// and multi-zone ovens, where each zone has its own loop and heat flows between
+/// neighbouring zones.
+///
+/// The coupling matrix gives, for each zone, how much of the output of every other
+/// zone reaches it, in units of its own output: `coupling[i][j]` is the part of the
+/// output of zone `j` heating zone `i`. Each loop subtracts the heat coming from its
+/// neighbours at the last step as a feedforward, so it does not wait for its
+/// measurement to rise before backing off, and the zones stop fighting each other. The
+/// diagonal of the matrix is ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct Zones<T, const N: usize> {
+    /// Loops, one per zone.
+    pub controllers: [PID<T>; N],
+
+    /// Part of the output of each zone reaching every other zone, row major.
+    coupling: [[T; N]; N],
+    /// Outputs of the last step.
+    outputs: [T; N],
+}
+
+impl<T: FloatCore, const N: usize> Zones<T, N> {
+    /// Creates a new `Zones` from the loops of each zone and their coupling matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use control::pid::PID;
+    /// use control::zones::Zones;
+    ///
+    /// let zone = PID::new(2.0, 0.0, 0.0, 0.0, 1.0, 60.0);
+    /// // A quarter of the heat of each zone reaches the other one.
+    /// let mut bed = Zones::new([zone, zone], [[0.0, 0.25], [0.25, 0.0]]);
+    ///
+    /// assert_eq!(bed.step([50.0, 40.0]), [20.0, 40.0]);
+    /// // The first zone backs off by a quarter of the heat of the second one.
+    /// assert_eq!(bed.step([50.0, 40.0]), [10.0, 35.0]);
+    /// ```
+    pub fn new(controllers: [PID<T>; N], coupling: [[T; N]; N]) -> Self {
+        Self { controllers, coupling, outputs: [T::zero(); N] }
+    }
+
+    /// Replaces the coupling matrix.
+    pub fn set_coupling(&mut self, coupling: [[T; N]; N]) -> &mut Self {
+        self.coupling = coupling;
+        self
+    }
+
+    /// Changes the setpoint of every zone.
+    pub fn set_setpoints(&mut self, setpoints: [T; N]) -> &mut Self {
+        for (controller, &setpoint) in self.controllers.iter_mut().zip(setpoints.iter()) {
+            controller.setpoint = setpoint;
+        }
+        self
+    }
+
+    /// Returns the heat each zone receives from its neighbours, in units of its own
+    /// output, from the outputs of the last step.
+    pub fn coupled(&self) -> [T; N] {
+        let mut coupled = [T::zero(); N];
+        for (i, (value, row)) in coupled.iter_mut().zip(self.coupling.iter()).enumerate() {
+            *value = row
+                .iter()
+                .zip(self.outputs.iter())
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(T::zero(), |sum, (_, (&c, &output))| sum + c * output);
+        }
+        coupled
+    }
+
+    /// Clears the state of every loop and the outputs of the last step.
+    pub fn reset(&mut self) {
+        for controller in self.controllers.iter_mut() {
+            controller.reset();
+        }
+        self.outputs = [T::zero(); N];
+    }
+
+    /// Performs a single step of every zone and returns their outputs.
+    pub fn step(&mut self, measurements: [T; N]) -> [T; N] {
+        let coupled = self.coupled();
+        for (((output, controller), &measurement), &heat) in self
+            .outputs
+            .iter_mut()
+            .zip(self.controllers.iter_mut())
+            .zip(measurements.iter())
+            .zip(coupled.iter())
+        {
+            *output = controller.step_with_feedforward(measurement, -heat);
+        }
+        self.outputs
+    }
+}